@@ -1,20 +1,41 @@
 mod util;
+use chrono::{NaiveDateTime, Utc};
+use polars::prelude::{DataFrame, DataType};
 use std::collections::HashMap;
 use std::error::Error;
 use std::path::PathBuf;
 use util::schema::{default_schema, parse_schema};
 use util::{dataframe::create_dataframe, output::Console};
 
-use crate::util::ddl::{ddl_path, emit_create_table, emit_er_ddl, table_name_from_path};
+use crate::util::dataframe::data_frame_from_sample;
+use crate::util::ddl::{
+    ddl_path, emit_create_table, emit_er_ddl, emit_series_table, table_name_from_path,
+};
 use crate::util::generator::generate;
+use crate::util::infer::{infer_columns, InferredColumn, Profiling};
+pub use crate::util::infer::{DEFAULT_MAX_ENUM, DEFAULT_MIMIC_MAX_ENUM};
 use crate::util::load_cmd::{emit_load_cmd, load_cmd_path};
+pub use crate::util::locale::Locale;
 use crate::util::multi_file_sink::{MultiFileSink, SinkFormat};
-use crate::util::output::{CSVFile, Output, ParquetFile};
-use crate::util::parser::parse as parse_erd;
+pub use crate::util::output::CsvFormat;
+use crate::util::output::{times_as_text, CSVFile, Output, ParquetFile};
+use crate::util::parser::{attribute_schema, parse as parse_erd};
+use crate::util::rng::RunRng;
 use crate::util::scanner::scan as scan_erd;
+use crate::util::schema::Schema;
+pub use crate::util::schema_file::SchemaFormat;
+use crate::util::schema_file::{load_schema_file, render_schema_file};
+use crate::util::temporal::{timestamp, uses_clock};
+pub use crate::util::timeseries::SeriesOptions;
+use crate::util::timeseries::{create_time_series, key_column, series_length};
+use crate::util::validate::check_columns;
 type RunResult<T> = Result<T, Box<dyn Error>>;
+/// `emit_create_table` or `emit_series_table`.
+type EmitTable =
+    fn(&str, &[Schema], &polars::prelude::Schema, Dialect) -> Result<String, DialectError>;
 
-pub use util::dialect::{to_sql_type, Dialect, DialectError};
+pub use util::dialect::{column_sql_type, dtype_sql_type, to_sql_type, Dialect, DialectError};
+pub use util::validate::UnknownTypeError;
 
 /// Output format selector for the `er` subcommand.
 #[derive(Clone, Copy, Debug)]
@@ -32,18 +53,71 @@ impl From<ErFormat> for SinkFormat {
     }
 }
 
+/// Settings shared by every generating subcommand: the database target and
+/// the output flags.
+#[derive(Clone, Debug)]
+pub struct RunOptions {
+    /// Also write DDL and a load command for this database.
+    pub target: Option<Dialect>,
+    pub no_ddl: bool,
+    pub no_load: bool,
+    pub seed: Option<u64>,
+    /// Derive each cell from (seed, table, column, row).
+    pub stable_rows: bool,
+    /// `--now`: the instant `now` and `today` bounds resolve against.
+    pub now: Option<String>,
+    pub csv_format: CsvFormat,
+    pub locale: Locale,
+    /// Check every column before generating; `false` is `--lenient`.
+    pub strict: bool,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            target: None,
+            no_ddl: false,
+            no_load: false,
+            seed: None,
+            stable_rows: false,
+            now: None,
+            csv_format: CsvFormat::default(),
+            locale: Locale::default(),
+            strict: true,
+        }
+    }
+}
+
+impl RunOptions {
+    /// These options with the CSV format `target` reads.
+    fn for_target(self) -> Result<Self, String> {
+        Ok(Self {
+            csv_format: self.csv_format.for_target(self.target)?,
+            ..self
+        })
+    }
+}
+
 /// Entry point for the `synthtab er <FILE>` subcommand.
-#[allow(clippy::too_many_arguments)]
 pub fn run_er(
     file: &str,
     rows: usize,
     rows_per: Vec<(String, usize)>,
     out: PathBuf,
     format: ErFormat,
-    target: Option<Dialect>,
-    no_ddl: bool,
-    no_load: bool,
+    options: RunOptions,
 ) -> RunResult<()> {
+    let RunOptions {
+        target,
+        no_ddl,
+        no_load,
+        seed,
+        stable_rows,
+        now,
+        csv_format,
+        locale,
+        strict,
+    } = options.for_target()?;
     let is_parquet = matches!(format, ErFormat::Parquet);
 
     let contents = std::fs::read_to_string(file)
@@ -51,23 +125,35 @@ pub fn run_er(
 
     let tokens = scan_erd(&contents).map_err(|e| format!("{file}:{}: {}", e.line, e.message))?;
     let ast = parse_erd(tokens).map_err(|e| format!("{file}: {}", e.message))?;
-
-    // D4: warn when BigQuery/Spark + Parquet (logical-type alignment is documented, not auto-cast)
-    if let Some(d) = target {
-        if is_parquet && matches!(d, Dialect::Bigquery | Dialect::Spark) {
-            eprintln!(
-                "warning: --target {} with --format parquet: \
-                 review docs/DIALECTS.md for recommended Parquet logical types",
-                d.as_str()
-            );
-        }
+    let clock = run_clock(now.as_deref())?;
+    let rows_per_map: HashMap<String, usize> = rows_per.into_iter().collect();
+    let mut relative = false;
+    for entity in &ast.entities {
+        let columns = entity
+            .attributes
+            .iter()
+            .map(attribute_schema)
+            .collect::<Result<Vec<_>, _>>()?;
+        let rows = rows_per_map.get(&entity.name).copied().unwrap_or(rows);
+        check_columns(&columns, rows, strict, clock)
+            .map_err(|e| format!("{file}: entity '{}': {e}", entity.name))?;
+        relative |= uses_clock(&columns);
     }
+    warn_unpinned(seed, &now, relative);
 
-    let rows_per_map: HashMap<String, usize> = rows_per.into_iter().collect();
-    let frames = generate(&ast, rows, &rows_per_map).map_err(|e| e.message)?;
+    let mut rng = RunRng::new(seed, stable_rows).at(clock);
+    let mut frames =
+        generate(&ast, rows, &rows_per_map, &mut rng, locale).map_err(|e| e.message)?;
     let ordered_names: Vec<String> = frames.iter().map(|(n, _)| n.clone()).collect();
+    let frame_schemas: HashMap<String, polars::prelude::Schema> = frames
+        .iter()
+        .map(|(n, df)| (n.clone(), df.schema()))
+        .collect();
 
-    let sink = MultiFileSink::new(out.clone(), format.into())?;
+    for (_, df) in frames.iter_mut() {
+        for_target(df, is_parquet, target)?;
+    }
+    let sink = MultiFileSink::new(out.clone(), format.into(), csv_format.clone())?;
     for (name, mut df) in frames {
         let path = sink.write(&name, &mut df)?;
         eprintln!("wrote {}", path.display());
@@ -82,7 +168,7 @@ pub fn run_er(
             }
 
             if !no_load {
-                let load = emit_load_cmd(table, path_str, dialect, is_parquet);
+                let load = emit_load_cmd(table, path_str, dialect, is_parquet, &df, &csv_format);
                 let load_path = load_cmd_path(path_str, dialect);
                 std::fs::write(&load_path, &load)
                     .map_err(|e| format!("failed to write load command '{load_path}': {e}"))?;
@@ -94,7 +180,7 @@ pub fn run_er(
     // Emit combined DDL file for all entities in topological order (D5)
     if let Some(dialect) = target {
         if !no_ddl {
-            let ddl = emit_er_ddl(&ast, &ordered_names, dialect)
+            let ddl = emit_er_ddl(&ast, &ordered_names, &frame_schemas, dialect)
                 .map_err(|e| format!("DDL emit failed: {e}"))?;
             let ddl_file = out.join(format!("schema.ddl.{}.sql", dialect.as_str()));
             std::fs::write(&ddl_file, &ddl)
@@ -106,82 +192,350 @@ pub fn run_er(
     Ok(())
 }
 
-// Consolidate into a RunOptions struct when D3 flags stabilise.
+/// Entry point for the `synthtab infer <FILE>` subcommand: print a schema
+/// for the sample's columns, as a `--schema` string or, given `format`, as a
+/// schema file.
+pub fn run_infer(file: &str, format: Option<SchemaFormat>, max_enum: usize) -> RunResult<()> {
+    let sample = data_frame_from_sample(file)?;
+    if sample.width() == 0 {
+        return Err(format!("'{file}' has no columns to infer a schema from").into());
+    }
+    let columns = infer_columns(&sample, &Profiling::infer(max_enum))
+        .map_err(|e| format!("failed to profile '{file}': {e}"))?;
+    let rendered = match format {
+        None => {
+            let tokens: Vec<String> = columns
+                .iter()
+                .map(InferredColumn::to_schema_string)
+                .collect();
+            tokens.join(",") + "\n"
+        }
+        Some(format) => render_schema_file(
+            columns.iter().map(InferredColumn::to_column_spec).collect(),
+            format,
+        )?,
+    };
+    print!("{rendered}");
+    Ok(())
+}
+
+/// Columns from `--schema` or `--schema-file`, checked for `rows` rows at
+/// `now` and each defaulting to `locale`; `None` when neither flag is given.
+fn schema_columns(
+    schema: Option<String>,
+    schema_file: Option<String>,
+    rows: usize,
+    locale: Locale,
+    strict: bool,
+    now: NaiveDateTime,
+) -> RunResult<Option<Vec<Schema>>> {
+    let columns = match (schema, schema_file) {
+        (Some(ref s), _) => {
+            let parsed = parse_schema(s.as_str())?;
+            if parsed.is_empty() {
+                return Err(format!(
+                    "schema string '{s}' produced no valid columns; expected 'name:TYPE[,name:TYPE...]'"
+                )
+                .into());
+            }
+            check_columns(&parsed, rows, strict, now)?;
+            parsed
+        }
+        (None, Some(path)) => {
+            let file = load_schema_file(&path)?;
+            file.check(rows, strict, now)?;
+            file.columns
+        }
+        (None, None) => return Ok(None),
+    };
+    Ok(Some(with_locale(columns, locale)))
+}
+
+/// The instant `now` and `today` bounds resolve against: `--now` when given,
+/// otherwise the clock, read once for the whole run.
+fn run_clock(now: Option<&str>) -> RunResult<NaiveDateTime> {
+    let clock = Utc::now().naive_utc();
+    match now {
+        Some(text) => Ok(timestamp(text, clock).map_err(|e| format!("--now: {e}"))?),
+        None => Ok(clock),
+    }
+}
+
+/// A seed alone does not reproduce bounds that follow the clock.
+fn warn_unpinned(seed: Option<u64>, now: &Option<String>, relative: bool) {
+    if seed.is_some() && now.is_none() && relative {
+        eprintln!(
+            "warning: `now` and `today` bounds follow the clock, so --seed alone does not \
+             reproduce this run; pin them with --now"
+        );
+    }
+}
+
+fn with_locale(columns: Vec<Schema>, locale: Locale) -> Vec<Schema> {
+    columns
+        .into_iter()
+        .map(|mut column| {
+            column.locale = column.locale.or(Some(locale));
+            column
+        })
+        .collect()
+}
+
+/// Entry point for the `synthtab ts` subcommand.
+pub fn run_ts(
+    schema: Option<String>,
+    schema_file: Option<String>,
+    series: SeriesOptions,
+    file_target: Option<String>,
+    parquet: bool,
+    options: RunOptions,
+) -> RunResult<()> {
+    let options = options.for_target()?;
+    let RunOptions {
+        target,
+        seed,
+        stable_rows,
+        ref now,
+        locale,
+        strict,
+        ..
+    } = options;
+    check_output_flags(parquet, target, &file_target)?;
+    let clock = run_clock(now.as_deref())?;
+    let length = series_length(&series, clock)?;
+    let values = schema_columns(schema, schema_file, length, locale, strict, clock)?
+        .ok_or("synthtab ts needs value columns from --schema or --schema-file")?;
+    check_columns(&[key_column(&series.key)?], series.entities, strict, clock)?;
+    let relative_span = [&series.start, &series.end]
+        .iter()
+        .any(|bound| bound.starts_with("now"));
+    warn_unpinned(seed, now, relative_span || uses_clock(&values));
+
+    let mut rng = RunRng::new(seed, stable_rows).at(clock);
+    let table = file_target
+        .as_deref()
+        .map(table_name_from_path)
+        .unwrap_or("");
+    let (mut data_frame, columns) = create_time_series(values, &series, &mut rng, table)
+        .map_err(|e| format!("failed to build time series: {e}"))?;
+
+    write_table(
+        &mut data_frame,
+        &columns,
+        emit_series_table,
+        file_target,
+        !parquet,
+        parquet,
+        &options,
+    )
+}
+
+/// Entry point for flat mode: generate `rows` rows of one table.
 #[allow(clippy::too_many_arguments)]
 pub fn run(
     schema: Option<String>,
+    schema_file: Option<String>,
     rows: usize,
     file_target: Option<String>,
     csv: bool,
     parquet: bool,
     append_target: Option<String>,
     delete_target: Option<String>,
-    target: Option<Dialect>,
-    no_ddl: bool,
-    no_load: bool,
+    options: RunOptions,
 ) -> RunResult<()> {
+    let options = options.for_target()?;
+    let RunOptions {
+        target,
+        seed,
+        stable_rows,
+        ref now,
+        locale,
+        strict,
+        ..
+    } = options;
     let csv = csv || !parquet;
 
+    check_output_flags(parquet, target, &file_target)?;
+
+    if schema.is_some() && schema_file.is_some() {
+        return Err("--schema and --schema-file are mutually exclusive; pick one".into());
+    }
+
+    let clock = run_clock(now.as_deref())?;
+    let tokenized_schema = schema_columns(schema, schema_file, rows, locale, strict, clock)?
+        .unwrap_or_else(|| with_locale(default_schema(), locale));
+    warn_unpinned(seed, now, uses_clock(&tokenized_schema));
+
+    let mut rng = RunRng::new(seed, stable_rows).at(clock);
+    let table = file_target
+        .as_deref()
+        .map(table_name_from_path)
+        .unwrap_or("");
+    let mut data_frame = create_dataframe(
+        tokenized_schema.clone(),
+        rows,
+        append_target,
+        delete_target,
+        &mut rng,
+        table,
+    )
+    .map_err(|e| format!("failed to build dataframe: {e}"))?;
+
+    write_table(
+        &mut data_frame,
+        &tokenized_schema,
+        emit_create_table,
+        file_target,
+        csv,
+        parquet,
+        &options,
+    )
+}
+
+/// Entry point for the `synthtab mimic <SAMPLE>` subcommand: profile the
+/// sample, then generate `rows` lookalike rows (as many as the sample has
+/// when `None`) and write them like flat mode does.
+pub fn run_mimic(
+    sample: &str,
+    rows: Option<usize>,
+    file_target: Option<String>,
+    parquet: bool,
+    reuse_categories: bool,
+    max_enum: usize,
+    options: RunOptions,
+) -> RunResult<()> {
+    let options = options.for_target()?;
+    let RunOptions {
+        target,
+        seed,
+        stable_rows,
+        ref now,
+        locale,
+        strict,
+        ..
+    } = options;
+    check_output_flags(parquet, target, &file_target)?;
+
+    let sample_frame = data_frame_from_sample(sample)?;
+    if sample_frame.width() == 0 {
+        return Err(format!("'{sample}' has no columns to profile").into());
+    }
+    let profiling = Profiling::mimic(max_enum, reuse_categories);
+    let columns = infer_columns(&sample_frame, &profiling)
+        .map_err(|e| format!("failed to profile '{sample}': {e}"))?
+        .iter()
+        .map(InferredColumn::to_schema)
+        .collect::<Result<Vec<_>, _>>()?;
+    let columns = with_locale(columns, locale);
+    let clock = run_clock(now.as_deref())?;
+    let rows = rows.unwrap_or(sample_frame.height());
+    check_columns(&columns, rows, strict, clock)?;
+
+    let mut rng = RunRng::new(seed, stable_rows).at(clock);
+    let table = file_target
+        .as_deref()
+        .map(table_name_from_path)
+        .unwrap_or("");
+    let mut data_frame = create_dataframe(columns.clone(), rows, None, None, &mut rng, table)
+        .map_err(|e| format!("failed to build dataframe: {e}"))?;
+
+    write_table(
+        &mut data_frame,
+        &columns,
+        emit_create_table,
+        file_target,
+        !parquet,
+        parquet,
+        &options,
+    )
+}
+
+/// Spark reads no Parquet `TIME` type, so Parquet written for it carries
+/// times as text, matching the `STRING` its DDL declares. BigQuery and Spark
+/// declare `LIST`/`STRUCT` columns as native `ARRAY`/`STRUCT`, which neither
+/// loads from CSV, and Spark reads no `BINARY` from CSV, so those need Parquet.
+fn for_target(df: &mut DataFrame, parquet: bool, target: Option<Dialect>) -> RunResult<()> {
+    if parquet && target == Some(Dialect::Spark) {
+        *df = times_as_text(df)?;
+    }
+    if let (false, Some(dialect @ (Dialect::Bigquery | Dialect::Spark))) = (parquet, target) {
+        let unloadable = df.get_columns().iter().find_map(|s| match s.dtype() {
+            DataType::List(_) | DataType::Struct(_) => Some((s.name(), "nested")),
+            DataType::Binary if dialect == Dialect::Spark => Some((s.name(), "BINARY")),
+            _ => None,
+        });
+        if let Some((name, kind)) = unloadable {
+            return Err(format!(
+                "column '{name}': --target {} cannot load a {kind} column from CSV; write Parquet instead",
+                dialect.as_str()
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Parquet output and database targets both need a file to write next to.
+fn check_output_flags(
+    parquet: bool,
+    target: Option<Dialect>,
+    file_target: &Option<String>,
+) -> RunResult<()> {
     if parquet && file_target.is_none() {
         return Err(
             "--parquet output requires --file-target <PATH>; refusing to discard generated rows"
                 .into(),
         );
     }
-
     if target.is_some() && file_target.is_none() {
         return Err(
             "--target requires --file-target so the DDL file can be placed next to the data".into(),
         );
     }
+    Ok(())
+}
 
-    // D4: warn when BigQuery/Spark + Parquet
-    if let Some(d) = target {
-        if parquet && matches!(d, Dialect::Bigquery | Dialect::Spark) {
-            eprintln!(
-                "warning: --target {} with --parquet: \
-                 review docs/DIALECTS.md for recommended Parquet logical types",
-                d.as_str()
-            );
-        }
-    }
-
-    let tokenized_schema = match schema {
-        Some(ref s) => {
-            let parsed = parse_schema(s.as_str());
-            if parsed.is_empty() {
-                return Err(format!(
-                    "schema string '{s}' produced no valid columns; expected 'name:TYPE[,name:TYPE...]'"
-                )
-                .into());
-            }
-            parsed
-        }
-        None => default_schema(),
-    };
-
-    let mut data_frame =
-        create_dataframe(tokenized_schema.clone(), rows, append_target, delete_target)
-            .map_err(|e| format!("failed to build dataframe: {e}"))?;
-
+/// Write a flat table to its file or stdout, plus its DDL, built by
+/// `emit_ddl`, and load command files when `options` sets a target.
+fn write_table(
+    data_frame: &mut DataFrame,
+    columns: &[Schema],
+    emit_ddl: EmitTable,
+    file_target: Option<String>,
+    csv: bool,
+    parquet: bool,
+    options: &RunOptions,
+) -> RunResult<()> {
+    let RunOptions {
+        target,
+        no_ddl,
+        no_load,
+        ref csv_format,
+        ..
+    } = *options;
+    for_target(data_frame, parquet, target)?;
     match (csv, parquet, &file_target) {
         (_, true, Some(path)) => ParquetFile {
             file_name: path.clone(),
         }
-        .write(&mut data_frame)?,
+        .write(data_frame)?,
         (true, _, Some(path)) => CSVFile {
             file_name: path.clone(),
+            format: csv_format.clone(),
+        }
+        .write(data_frame)?,
+        _ => Console {
+            format: csv_format.clone(),
         }
-        .write(&mut data_frame)?,
-        _ => Console {}.write(&mut data_frame)?,
+        .write(data_frame)?,
     }
 
     if let Some(dialect) = target {
-        let path = file_target.as_ref().expect("guarded above");
+        let path = file_target.as_ref().expect("guarded by check_output_flags");
         let table = table_name_from_path(path);
 
         if !no_ddl {
-            let ddl = emit_create_table(table, &tokenized_schema, dialect)
+            let ddl = emit_ddl(table, columns, &data_frame.schema(), dialect)
                 .map_err(|e| format!("DDL emit failed: {e}"))?;
             let out_path = ddl_path(path, dialect);
             std::fs::write(&out_path, &ddl)
@@ -190,7 +544,7 @@ pub fn run(
         }
 
         if !no_load {
-            let load = emit_load_cmd(table, path, dialect, parquet);
+            let load = emit_load_cmd(table, path, dialect, parquet, data_frame, csv_format);
             let out_path = load_cmd_path(path, dialect);
             std::fs::write(&out_path, &load)
                 .map_err(|e| format!("failed to write load command '{out_path}': {e}"))?;
@@ -219,15 +573,18 @@ mod test {
     ) -> RunResult<()> {
         run(
             schema,
+            None,
             rows,
             file_target,
             csv,
             parquet,
             append_target,
             delete_target,
-            target,
-            no_ddl,
-            false,
+            RunOptions {
+                target,
+                no_ddl,
+                ..RunOptions::default()
+            },
         )
     }
 
@@ -317,6 +674,23 @@ mod test {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn schema_and_schema_file_together_are_rejected() {
+        let result = run(
+            Some("id:INT_INC".to_string()),
+            Some("schema.toml".to_string()),
+            3,
+            None,
+            true,
+            false,
+            None,
+            None,
+            RunOptions::default(),
+        );
+        let msg = result.unwrap_err().to_string();
+        assert!(msg.contains("mutually exclusive"), "got: {msg}");
+    }
+
     #[test]
     fn run_default_schema_succeeds() {
         let result = run9(None, 5, None, true, false, None, None, None, false);
@@ -344,6 +718,32 @@ mod test {
         );
     }
 
+    #[test]
+    fn run_ts_requires_value_columns() {
+        let result = run_ts(
+            None,
+            None,
+            SeriesOptions {
+                start: "2024-06-01".into(),
+                end: "2024-06-02".into(),
+                interval: "1h".into(),
+                key: "device_id".into(),
+                entities: 2,
+                time_column: "ts".into(),
+                jitter: None,
+                gap_ratio: 0.0,
+                late_ratio: 0.0,
+                max_delay: None,
+                arrival_column: None,
+            },
+            None,
+            false,
+            RunOptions::default(),
+        );
+        let msg = result.unwrap_err().to_string();
+        assert!(msg.contains("--schema or --schema-file"), "got: {msg}");
+    }
+
     #[test]
     fn run_er_writes_files_per_entity() {
         let src = "\
@@ -362,9 +762,7 @@ erDiagram
             vec![],
             dir.clone(),
             ErFormat::Csv,
-            None,
-            false,
-            false,
+            RunOptions::default(),
         );
         assert!(r.is_ok(), "run_er failed: {r:?}");
         assert!(dir.join("PARENT.csv").exists());
@@ -391,9 +789,7 @@ erDiagram
             vec![],
             dir.clone(),
             ErFormat::Csv,
-            None,
-            false,
-            false,
+            RunOptions::default(),
         );
         assert!(r.is_ok(), "run_er failed: {r:?}");
         assert!(dir.join("STUDENT.csv").exists());
@@ -421,9 +817,10 @@ erDiagram
             vec![],
             dir.clone(),
             ErFormat::Csv,
-            Some(Dialect::Postgres),
-            false,
-            false,
+            RunOptions {
+                target: Some(Dialect::Postgres),
+                ..RunOptions::default()
+            },
         );
         assert!(r.is_ok(), "run_er failed: {r:?}");
         assert!(dir.join("schema.ddl.postgres.sql").exists(), "DDL missing");
@@ -2,8 +2,12 @@ use clap::{Args as CLAPArgs, Parser as CLAPParser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 
 /// Easily generate fake data with using the following types:
-/// STRING, INT, INT_INC, INT_RNG, DIGIT, DECIMAL, DATE, TIME, DATE_TIME, NAME, ZIP_CODE, COUNTRY_CODE
-/// LAT, LON, PHONE, LOREM_WORD, LOREM_SENTENCE, LOREM_PARAGRAPH, UUID , PRICE
+/// STRING, INT, BIGINT, INT_INC, BIGINT_INC, INT_RNG, DIGIT, BOOLEAN, DECIMAL, DATE, TIME, DATE_TIME, NAME, ZIP_CODE, COUNTRY_CODE
+/// LAT, LON, PHONE, LOREM_WORD, LOREM_SENTENCE, LOREM_PARAGRAPH, UUID , PRICE, ENUM, PATTERN, TEMPLATE, EXPR
+/// EMAIL, URL, DOMAIN, IPV4, IPV6, MAC_ADDRESS, USER_AGENT
+/// COMPANY, JOB_TITLE, INDUSTRY, CREDIT_CARD, IBAN, BIC, CURRENCY_CODE
+/// BINARY, HEX, BASE64, TOKEN
+/// LIST<T>, STRUCT<a:T, b:U>
 #[derive(CLAPParser)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
@@ -18,6 +22,12 @@ struct Cli {
 enum Command {
     /// Generate relationally-consistent multi-table data from a Mermaid ER diagram
     Er(ErArgs),
+    /// Generate one row per entity per interval with increasing timestamps
+    Ts(Box<TsArgs>),
+    /// Print a schema inferred from an existing CSV or Parquet file
+    Infer(InferArgs),
+    /// Generate rows with the statistics of a CSV or Parquet sample
+    Mimic(Box<MimicArgs>),
 }
 
 #[derive(CLAPArgs)]
@@ -25,6 +35,9 @@ struct FlatArgs {
     /// Data Schema "col:STRING, col2:INT, col3:TIME"
     #[arg(short, long)]
     schema: Option<String>,
+    /// Read the schema from a TOML, YAML or JSON file instead of --schema
+    #[arg(long, conflicts_with = "schema")]
+    schema_file: Option<String>,
     /// Output file name (required for parquet file output)
     #[arg(short, long)]
     file_target: Option<String>,
@@ -52,6 +65,8 @@ struct FlatArgs {
     /// Suppress load-command file emission when --target is set
     #[arg(long)]
     no_load: bool,
+    #[command(flatten)]
+    output: OutputArgs,
 }
 
 #[derive(CLAPArgs)]
@@ -79,6 +94,191 @@ struct ErArgs {
     /// Suppress load-command file emission when --target is set
     #[arg(long)]
     no_load: bool,
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+#[derive(CLAPArgs)]
+struct TsArgs {
+    /// Value columns "temp:DECIMAL(walk(20, 0.5), scale=1), load:INT(seasonal(50, 20, 288, 5))"
+    #[arg(short, long)]
+    schema: Option<String>,
+    /// Read the value columns from a TOML, YAML or JSON file instead of --schema
+    #[arg(long, conflicts_with = "schema")]
+    schema_file: Option<String>,
+    /// First timestamp: 2024-06-01, 2024-06-01T08:00 or now-1d
+    #[arg(long)]
+    start: String,
+    /// Timestamps stop before this one
+    #[arg(long)]
+    end: String,
+    /// Time between readings: 30s, 5m, 1h, 1d
+    #[arg(short, long)]
+    interval: String,
+    /// Entity key column, "name" (numbered from 1) or "name:TYPE"
+    #[arg(short, long, default_value = "entity_id")]
+    key: String,
+    /// Number of entities, each with its own series
+    #[arg(short, long, default_value_t = 1)]
+    entities: usize,
+    /// Name of the timestamp column
+    #[arg(long, default_value = "ts")]
+    time_column: String,
+    /// Move each timestamp forward by a random amount below this duration
+    #[arg(long)]
+    jitter: Option<String>,
+    /// Share of readings to drop, 0 to 1
+    #[arg(long, default_value_t = 0.0)]
+    gap_ratio: f64,
+    /// Share of readings that arrive late, after later readings, 0 to 1
+    #[arg(long, default_value_t = 0.0, requires = "max_delay")]
+    late_ratio: f64,
+    /// Longest delay of a late reading
+    #[arg(long)]
+    max_delay: Option<String>,
+    /// Also write each reading's arrival time to this column
+    #[arg(long)]
+    arrival_column: Option<String>,
+    /// Output file name (required for parquet file output)
+    #[arg(short, long)]
+    file_target: Option<String>,
+    /// Parquet output
+    #[arg(short, long)]
+    parquet: bool,
+    /// Emit dialect-correct DDL and load-command files next to the data file
+    #[arg(long, value_enum)]
+    target: Option<synthtab::Dialect>,
+    /// Suppress DDL file emission when --target is set
+    #[arg(long)]
+    no_ddl: bool,
+    /// Suppress load-command file emission when --target is set
+    #[arg(long)]
+    no_load: bool,
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+#[derive(CLAPArgs)]
+struct InferArgs {
+    /// CSV or Parquet sample; `.parquet` and `.pq` files are read as Parquet
+    file: String,
+    /// Print a --schema string, or a schema file for --schema-file
+    #[arg(short = 'F', long, value_enum, default_value_t = InferFormat::Schema)]
+    format: InferFormat,
+    /// Text columns with at most this many distinct values become an ENUM;
+    /// 0 never infers one
+    #[arg(long, default_value_t = synthtab::DEFAULT_MAX_ENUM)]
+    max_enum: usize,
+}
+
+#[derive(CLAPArgs)]
+struct MimicArgs {
+    /// CSV or Parquet sample; `.parquet` and `.pq` files are read as Parquet
+    sample: String,
+    /// Rows to generate [default: as many as the sample]
+    #[arg(short, long)]
+    rows: Option<usize>,
+    /// Output file name (required for parquet file output)
+    #[arg(short, long)]
+    file_target: Option<String>,
+    /// Parquet output
+    #[arg(short, long)]
+    parquet: bool,
+    /// Let categorical columns repeat the sample's values instead of
+    /// stand-in labels
+    #[arg(long)]
+    reuse_categories: bool,
+    /// Text columns with at most this many distinct values are categorical
+    #[arg(long, default_value_t = synthtab::DEFAULT_MIMIC_MAX_ENUM)]
+    max_enum: usize,
+    /// Emit dialect-correct DDL and load-command files next to the data file
+    #[arg(long, value_enum)]
+    target: Option<synthtab::Dialect>,
+    /// Suppress DDL file emission when --target is set
+    #[arg(long)]
+    no_ddl: bool,
+    /// Suppress load-command file emission when --target is set
+    #[arg(long)]
+    no_load: bool,
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+/// Flags every generating subcommand shares: reproducibility, CSV rendering,
+/// locale and validation.
+#[derive(CLAPArgs)]
+struct OutputArgs {
+    /// Seed the random generator so identical flags produce identical output
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Derive each cell from (seed, table, column, row) so a row's values do
+    /// not change when the row count or the column order changes
+    #[arg(long, requires = "seed")]
+    stable_rows: bool,
+    /// Resolve `now` and `today` bounds against this instant instead of the
+    /// clock (`2024-06-01`, `2024-06-01T08:30`)
+    #[arg(long, value_name = "TS")]
+    now: Option<String>,
+    /// Text written for null cells in CSV output
+    #[arg(long, default_value = "")]
+    null_token: String,
+    /// strftime pattern for DATE cells in CSV output
+    #[arg(long, default_value = "%Y-%m-%d")]
+    date_format: String,
+    /// strftime pattern for TIME cells in CSV output
+    #[arg(long, default_value = "%H:%M:%S")]
+    time_format: String,
+    /// strftime pattern for DATE_TIME cells in CSV output
+    #[arg(long, default_value = "%Y-%m-%dT%H:%M:%S%:z")]
+    datetime_format: String,
+    /// Data set for names, addresses, phones and companies; a column's
+    /// locale= option overrides it
+    #[arg(long, value_enum, ignore_case = true, default_value_t = synthtab::Locale::En)]
+    locale: synthtab::Locale,
+    /// Check every column type and modifier before generating, failing on
+    /// unknown types (the default)
+    #[arg(long, overrides_with = "lenient")]
+    strict: bool,
+    /// Warn about unknown column types and fill them with "unknown"
+    #[arg(long, overrides_with = "strict")]
+    lenient: bool,
+}
+
+impl OutputArgs {
+    /// The run options these flags set, for a run that writes DDL and load
+    /// commands for `target`.
+    fn run_options(
+        self,
+        target: Option<synthtab::Dialect>,
+        no_ddl: bool,
+        no_load: bool,
+    ) -> synthtab::RunOptions {
+        synthtab::RunOptions {
+            target,
+            no_ddl,
+            no_load,
+            seed: self.seed,
+            stable_rows: self.stable_rows,
+            now: self.now,
+            csv_format: synthtab::CsvFormat {
+                null_value: self.null_token,
+                date_format: self.date_format,
+                time_format: self.time_format,
+                datetime_format: self.datetime_format,
+                ..Default::default()
+            },
+            locale: self.locale,
+            strict: !self.lenient,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum InferFormat {
+    Schema,
+    Toml,
+    Yaml,
+    Json,
 }
 
 #[derive(Clone, Copy, Debug, ValueEnum)]
@@ -109,21 +309,68 @@ fn main() {
                 ErFormat::Csv => synthtab::ErFormat::Csv,
                 ErFormat::Parquet => synthtab::ErFormat::Parquet,
             },
-            args.target,
-            args.no_ddl,
-            args.no_load,
+            args.output
+                .run_options(args.target, args.no_ddl, args.no_load),
+        ),
+        Some(Command::Ts(args)) => {
+            let args = *args;
+            synthtab::run_ts(
+                args.schema,
+                args.schema_file,
+                synthtab::SeriesOptions {
+                    start: args.start,
+                    end: args.end,
+                    interval: args.interval,
+                    key: args.key,
+                    entities: args.entities,
+                    time_column: args.time_column,
+                    jitter: args.jitter,
+                    gap_ratio: args.gap_ratio,
+                    late_ratio: args.late_ratio,
+                    max_delay: args.max_delay,
+                    arrival_column: args.arrival_column,
+                },
+                args.file_target,
+                args.parquet,
+                args.output
+                    .run_options(args.target, args.no_ddl, args.no_load),
+            )
+        }
+        Some(Command::Infer(args)) => synthtab::run_infer(
+            &args.file,
+            match args.format {
+                InferFormat::Schema => None,
+                InferFormat::Toml => Some(synthtab::SchemaFormat::Toml),
+                InferFormat::Yaml => Some(synthtab::SchemaFormat::Yaml),
+                InferFormat::Json => Some(synthtab::SchemaFormat::Json),
+            },
+            args.max_enum,
         ),
+        Some(Command::Mimic(args)) => {
+            let args = *args;
+            synthtab::run_mimic(
+                &args.sample,
+                args.rows,
+                args.file_target,
+                args.parquet,
+                args.reuse_categories,
+                args.max_enum,
+                args.output
+                    .run_options(args.target, args.no_ddl, args.no_load),
+            )
+        }
         None => synthtab::run(
             cli.flat.schema,
+            cli.flat.schema_file,
             cli.flat.rows,
             cli.flat.file_target,
             cli.flat.csv,
             cli.flat.parquet,
             cli.flat.append_target,
             cli.flat.delete_target,
-            cli.flat.target,
-            cli.flat.no_ddl,
-            cli.flat.no_load,
+            cli.flat
+                .output
+                .run_options(cli.flat.target, cli.flat.no_ddl, cli.flat.no_load),
         ),
     };
     if let Err(e) = result {
@@ -1,12 +1,14 @@
+use crate::util::derived::{derivation_of, render_pending, Pending};
 use crate::util::fake::build_incremental_int;
-use crate::util::fake::create_column;
-use crate::util::fake::fake_uuid;
+use crate::util::fake::{apply_null_ratio, create_column};
+use crate::util::rng::RunRng;
 use crate::util::schema::Schema;
 use polars::prelude::*;
 use rand::Rng;
 use regex::Regex;
 use std::error::Error;
 use std::sync::OnceLock;
+use uuid::Uuid;
 
 type DataFrameResult = Result<DataFrame, Box<dyn Error>>;
 type DeleteTargetResult = Result<Vec<i32>, Box<dyn Error>>;
@@ -25,16 +27,59 @@ fn data_frame_from_file(path: &str) -> DataFrameResult {
     Ok(df)
 }
 
+/// Read a sample table: Parquet for a `.parquet` or `.pq` file, otherwise
+/// CSV with a header row. CSV column types are inferred from every row, and
+/// ISO dates and timestamps come back as typed columns.
+pub(crate) fn data_frame_from_sample(path: &str) -> DataFrameResult {
+    let extension = std::path::Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase);
+    if matches!(extension.as_deref(), Some("parquet" | "pq")) {
+        return data_frame_from_file(path);
+    }
+    let df = CsvReader::from_path(path)
+        .map_err(|e| format!("failed to open CSV file '{path}': {e}"))?
+        .has_header(true)
+        .infer_schema(None)
+        .with_try_parse_dates(true)
+        .finish()
+        .map_err(|e| format!("failed to read CSV file '{path}': {e}"))?;
+    Ok(df)
+}
+
+/// `table` keys per-cell RNGs in `--stable-rows` mode; it is ignored otherwise.
 pub fn create_dataframe(
     schema: Vec<Schema>,
     size: usize,
     append_target: Option<String>,
     delete_target: Option<String>,
+    rng: &mut RunRng,
+    table: &str,
 ) -> DataFrameResult {
-    let cols: Vec<Series> = schema
-        .into_iter()
-        .map(|element| create_column(element, size))
-        .collect();
+    let mut cols: Vec<Option<Series>> = vec![None; schema.len()];
+    let mut derived: Vec<Pending> = Vec::new();
+    for (slot, element) in schema.into_iter().enumerate() {
+        if let Some(derivation) = derivation_of(&element)? {
+            derived.push(Pending {
+                slot,
+                column: element,
+                derivation,
+            });
+            continue;
+        }
+        let name = element.name.clone();
+        let null_ratio = element.null_ratio;
+        let now = rng.now();
+        let mut col = create_column(element, size, &mut rng.column(table, &name), now)?;
+        if let Some(ratio) = null_ratio {
+            col = apply_null_ratio(col, ratio, &mut rng.nulls(table, &name))
+                .map_err(|e| format!("failed to apply nulls to column '{name}': {e}"))?;
+        }
+        cols[slot] = Some(col);
+    }
+    render_pending(derived, &mut cols, rng, table)?;
+    let cols: Vec<Series> = cols.into_iter().flatten().collect();
 
     let data_frame = match append_target {
         Some(file) => {
@@ -52,7 +97,7 @@ pub fn create_dataframe(
 
     let data_frame = match delete_target {
         Some(target) => {
-            let delete_indexes = parse_delete_target(target.as_str(), size)?;
+            let delete_indexes = parse_delete_target(target.as_str(), size, rng.stream())?;
             filter_by_index(data_frame, delete_indexes)
         }
         None => data_frame,
@@ -61,9 +106,12 @@ pub fn create_dataframe(
     Ok(data_frame)
 }
 
-fn parse_delete_target(text: &str, rows: usize) -> DeleteTargetResult {
+fn parse_delete_target<R: Rng + ?Sized>(
+    text: &str,
+    rows: usize,
+    rng: &mut R,
+) -> DeleteTargetResult {
     if text == "random" || text == "rand" {
-        let mut rng = rand::thread_rng();
         let random_count = rng.gen_range(1..=rows);
         let mut random_numbers: Vec<i32> = (0..random_count)
             .map(|_| rng.gen_range(0..=rows) as i32)
@@ -110,7 +158,7 @@ fn filter_by_index(df: DataFrame, list: Vec<i32>) -> DataFrame {
     }
 
     let temp_col = build_incremental_int(df.height() as i32, 0, df.height() as i32);
-    let col_name = fake_uuid();
+    let col_name = Uuid::new_v4().to_string();
     let temp_series = Series::new(col_name.as_str(), temp_col);
 
     let mut iter = list.into_iter();
@@ -132,6 +180,11 @@ fn filter_by_index(df: DataFrame, list: Vec<i32>) -> DataFrame {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::util::rng::seeded_rng;
+
+    fn run_rng(seed: Option<u64>) -> RunRng {
+        RunRng::new(seed, false)
+    }
 
     fn sample_schema() -> Vec<Schema> {
         vec![
@@ -139,16 +192,25 @@ mod test {
                 name: String::from("col1"),
                 datatype: String::from("INT"),
                 modifier: None,
+                null_ratio: None,
+                locale: None,
+                children: Vec::new(),
             },
             Schema {
                 name: String::from("col2"),
                 datatype: String::from("STRING"),
                 modifier: None,
+                null_ratio: None,
+                locale: None,
+                children: Vec::new(),
             },
             Schema {
                 name: String::from("col3"),
                 datatype: String::from("LOREM_WORD"),
                 modifier: None,
+                null_ratio: None,
+                locale: None,
+                children: Vec::new(),
             },
         ]
     }
@@ -157,28 +219,36 @@ mod test {
     fn test_create_dataframe() {
         let schema = sample_schema();
 
-        let df = create_dataframe(schema.clone(), 10, None, Some("1,2".to_string())).unwrap();
+        let df = create_dataframe(
+            schema.clone(),
+            10,
+            None,
+            Some("1,2".to_string()),
+            &mut run_rng(None),
+            "t",
+        )
+        .unwrap();
         assert_eq!(df.shape(), (8, 3));
 
-        let df = create_dataframe(schema.clone(), 10, None, None).unwrap();
+        let df = create_dataframe(schema.clone(), 10, None, None, &mut run_rng(None), "t").unwrap();
         assert_eq!(df.shape(), (10, 3));
     }
 
     #[test]
     fn test_parse_delete_target_single() {
-        let r = parse_delete_target("3", 10).unwrap();
+        let r = parse_delete_target("3", 10, &mut seeded_rng(None)).unwrap();
         assert_eq!(r, vec![3]);
     }
 
     #[test]
     fn test_parse_delete_target_comma_list() {
-        let r = parse_delete_target("0,2,5", 10).unwrap();
+        let r = parse_delete_target("0,2,5", 10, &mut seeded_rng(None)).unwrap();
         assert_eq!(r, vec![0, 2, 5]);
     }
 
     #[test]
     fn test_parse_delete_target_positive_range() {
-        let r = parse_delete_target("1-4", 10).unwrap();
+        let r = parse_delete_target("1-4", 10, &mut seeded_rng(None)).unwrap();
         assert_eq!(r, vec![1, 2, 3, 4]);
     }
 
@@ -186,18 +256,18 @@ mod test {
     fn test_parse_delete_target_negative_range() {
         // -2 .. 2 inclusive should yield 5 numbers; the current ambiguous regex
         // mis-parses this and the function falls through to an error.
-        let r = parse_delete_target("-2-2", 10).unwrap();
+        let r = parse_delete_target("-2-2", 10, &mut seeded_rng(None)).unwrap();
         assert_eq!(r, vec![-2, -1, 0, 1, 2]);
     }
 
     #[test]
     fn test_parse_delete_target_empty_returns_err() {
-        assert!(parse_delete_target("", 10).is_err());
+        assert!(parse_delete_target("", 10, &mut seeded_rng(None)).is_err());
     }
 
     #[test]
     fn test_filter_by_index_empty_list_is_noop() {
-        let df = create_dataframe(sample_schema(), 5, None, None).unwrap();
+        let df = create_dataframe(sample_schema(), 5, None, None, &mut run_rng(None), "t").unwrap();
         // Must not panic on empty index list and must return all rows unchanged.
         let out = filter_by_index(df, vec![]);
         assert_eq!(out.shape(), (5, 3));
@@ -221,7 +291,7 @@ mod test {
 
     #[test]
     fn test_parse_delete_target_random_returns_nonempty_in_range() {
-        let r = parse_delete_target("random", 10).unwrap();
+        let r = parse_delete_target("random", 10, &mut seeded_rng(None)).unwrap();
         assert!(!r.is_empty());
         for i in &r {
             assert!(*i >= 0 && *i <= 10, "index {i} out of [0,10]");
@@ -230,13 +300,59 @@ mod test {
 
     #[test]
     fn test_parse_delete_target_rand_alias_works() {
-        let r = parse_delete_target("rand", 5).unwrap();
+        let r = parse_delete_target("rand", 5, &mut seeded_rng(None)).unwrap();
         assert!(!r.is_empty());
     }
 
+    #[test]
+    fn test_parse_delete_target_random_is_reproducible_with_seed() {
+        let a = parse_delete_target("random", 50, &mut seeded_rng(Some(9))).unwrap();
+        let b = parse_delete_target("random", 50, &mut seeded_rng(Some(9))).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_create_dataframe_same_seed_is_identical() {
+        let a =
+            create_dataframe(sample_schema(), 10, None, None, &mut run_rng(Some(3)), "t").unwrap();
+        let b =
+            create_dataframe(sample_schema(), 10, None, None, &mut run_rng(Some(3)), "t").unwrap();
+        assert!(a.equals(&b));
+    }
+
+    #[test]
+    fn test_create_dataframe_stable_rows_survive_row_count_change() {
+        let mut small_rng = RunRng::new(Some(3), true);
+        let mut large_rng = RunRng::new(Some(3), true);
+        let small = create_dataframe(sample_schema(), 10, None, None, &mut small_rng, "t").unwrap();
+        let large =
+            create_dataframe(sample_schema(), 100, None, None, &mut large_rng, "t").unwrap();
+        assert!(small.equals(&large.head(Some(10))));
+    }
+
+    #[test]
+    fn test_create_dataframe_stable_rows_ignore_column_order() {
+        let schema = sample_schema();
+        let reversed: Vec<Schema> = schema.iter().rev().cloned().collect();
+        let a =
+            create_dataframe(schema, 10, None, None, &mut RunRng::new(Some(3), true), "t").unwrap();
+        let b = create_dataframe(
+            reversed,
+            10,
+            None,
+            None,
+            &mut RunRng::new(Some(3), true),
+            "t",
+        )
+        .unwrap();
+        for name in ["col1", "col2", "col3"] {
+            assert!(a.column(name).unwrap().equals(b.column(name).unwrap()));
+        }
+    }
+
     #[test]
     fn test_parse_delete_target_inverted_range_returns_error() {
-        let r = parse_delete_target("9-1", 10);
+        let r = parse_delete_target("9-1", 10, &mut seeded_rng(None));
         assert!(r.is_err());
     }
 }
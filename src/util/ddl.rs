@@ -3,29 +3,64 @@
 //! D2: flat-table `CREATE TABLE` from a synthtab schema.
 //! D5: ER-mode `CREATE TABLE` with FK constraints, emitted in topological order.
 
-use crate::util::dialect::{to_sql_type, Dialect, DialectError};
+use crate::util::dialect::{column_sql_type, dtype_sql_type, to_sql_type, Dialect, DialectError};
 use crate::util::erd_ast::{ErdAst, KeyKind};
-use crate::util::parser::mermaid_type_to_synthtab;
+use crate::util::parser::{attribute_schema, mermaid_type_to_synthtab};
 use crate::util::schema::Schema;
+use polars::prelude::Schema as FrameSchema;
+use std::collections::HashMap;
 
 /// Emit a `CREATE TABLE` DDL string for `table_name` using `columns` and
-/// `dialect`. Returns an error only if a column's type has no mapping for the
-/// chosen dialect (i.e. `to_sql_type` fails).
+/// `dialect`. `frame` is the schema of the generated data, which supplies the
+/// types of `EXPR` columns. Returns an error only if a column's type has no
+/// mapping for the chosen dialect (i.e. `column_sql_type` fails).
 pub fn emit_create_table(
     table_name: &str,
     columns: &[Schema],
+    frame: &FrameSchema,
     dialect: Dialect,
 ) -> Result<String, DialectError> {
-    let mut pk_seen = false;
+    create_table(table_name, columns, frame, dialect, true)
+}
+
+/// `CREATE TABLE` for a `synthtab ts` table. Every entity repeats its key and
+/// restarts its sequences, so no column is declared the primary key.
+pub fn emit_series_table(
+    table_name: &str,
+    columns: &[Schema],
+    frame: &FrameSchema,
+    dialect: Dialect,
+) -> Result<String, DialectError> {
+    create_table(table_name, columns, frame, dialect, false)
+}
+
+fn create_table(
+    table_name: &str,
+    columns: &[Schema],
+    frame: &FrameSchema,
+    dialect: Dialect,
+    with_pk: bool,
+) -> Result<String, DialectError> {
+    let mut pk_seen = !with_pk;
     let mut col_defs: Vec<String> = Vec::with_capacity(columns.len());
 
     for col in columns {
-        let is_pk = col.datatype == "INT_INC" && !pk_seen;
+        // A key column may not hold NULL, so a nullable counter stays a
+        // plain column and the next one (if any) becomes the key.
+        let is_pk = matches!(col.datatype.as_str(), "INT_INC" | "BIGINT_INC")
+            && !col.is_nullable()
+            && !pk_seen;
         if is_pk {
             pk_seen = true;
         }
-        let sql_type = to_sql_type(&col.datatype, dialect, is_pk)?;
-        col_defs.push(format!("  {} {}", col.name, sql_type));
+        let sql_type = sql_type_for(col, frame, dialect, is_pk)?;
+        col_defs.push(column_def(
+            &col.name,
+            &sql_type,
+            col.is_nullable(),
+            is_pk,
+            dialect,
+        ));
     }
 
     Ok(format!(
@@ -35,6 +70,56 @@ pub fn emit_create_table(
     ))
 }
 
+/// SQL type for one column. `EXPR` columns have no declared type, so theirs
+/// comes from the dtype the expression produced.
+fn sql_type_for(
+    col: &Schema,
+    frame: &FrameSchema,
+    dialect: Dialect,
+    is_pk: bool,
+) -> Result<String, DialectError> {
+    if col.datatype != "EXPR" {
+        return column_sql_type(col, dialect, is_pk);
+    }
+    let dtype = frame.get(&col.name).ok_or_else(|| DialectError {
+        message: format!(
+            "column '{}': EXPR type is unknown before generation",
+            col.name
+        ),
+    })?;
+    dtype_sql_type(dtype, dialect).map_err(|e| DialectError {
+        message: format!("column '{}': {}", col.name, e.message),
+    })
+}
+
+/// One `name TYPE [NULL|NOT NULL]` line. Primary keys are left as mapped
+/// (the key already implies NOT NULL), and BigQuery/Spark get no explicit
+/// `NULL` because neither accepts the keyword.
+fn column_def(name: &str, sql_type: &str, nullable: bool, is_pk: bool, dialect: Dialect) -> String {
+    if is_pk {
+        return format!("  {name} {sql_type}");
+    }
+    let base = sql_type.strip_suffix(" NOT NULL").unwrap_or(sql_type);
+    match (nullable, dialect) {
+        (false, _) => format!("  {name} {base} NOT NULL"),
+        (true, Dialect::Bigquery | Dialect::Spark) => format!("  {name} {base}"),
+        (true, _) => format!("  {name} {base} NULL"),
+    }
+}
+
+/// SQL type of a column holding `entity`'s primary key: the key's own type,
+/// hint included, without identity decoration, or `INTEGER` when the entity
+/// has no PK.
+fn key_sql_type(ast: &ErdAst, entity: &str, dialect: Dialect) -> Result<String, DialectError> {
+    match ast.entity(entity).and_then(|e| e.pk()) {
+        Some(pk) => {
+            let column = attribute_schema(pk).map_err(|e| DialectError { message: e.message })?;
+            column_sql_type(&column, dialect, false)
+        }
+        None => Ok("INTEGER".to_string()),
+    }
+}
+
 /// Derive the DDL output path from a data file path.
 ///
 /// `data_path` is the `--file-target` value (e.g. `"./out/users.csv"`).
@@ -56,7 +141,8 @@ pub fn table_name_from_path(data_path: &str) -> &str {
 }
 
 /// Emit DDL for all entities in `ordered_names` (topological order) plus any
-/// M:N junction tables derived from the AST relationships.
+/// M:N junction tables derived from the AST relationships. `frames` maps each
+/// entity to the schema of its generated data, for `EXPR` column types.
 ///
 /// Each table includes `FOREIGN KEY` constraints for MySQL, Postgres, and SQL
 /// Server (BigQuery and Spark do not enforce FK constraints natively, so
@@ -64,6 +150,7 @@ pub fn table_name_from_path(data_path: &str) -> &str {
 pub fn emit_er_ddl(
     ast: &ErdAst,
     ordered_names: &[String],
+    frames: &HashMap<String, FrameSchema>,
     dialect: Dialect,
 ) -> Result<String, DialectError> {
     let mut out = String::new();
@@ -74,16 +161,33 @@ pub fn emit_er_ddl(
 
     // Emit one CREATE TABLE per entity in topological order.
     for entity_name in ordered_names {
-        let entity = ast.entity(entity_name).expect("entity from ordered list");
+        // Junction frames share the list; they are emitted below.
+        let Some(entity) = ast.entity(entity_name) else {
+            continue;
+        };
+        let empty = FrameSchema::new();
+        let frame = frames.get(entity_name).unwrap_or(&empty);
         let mut col_defs: Vec<String> = Vec::new();
         let mut fk_constraints: Vec<String> = Vec::new();
 
         // Declared columns
         for attr in &entity.attributes {
-            let synthtab_type = mermaid_type_to_synthtab(&attr.data_type).unwrap_or("STRING");
+            let column = attribute_schema(attr).map_err(|e| DialectError { message: e.message })?;
             let is_pk = attr.key == Some(KeyKind::Pk);
-            let sql_type = to_sql_type(synthtab_type, dialect, is_pk)?;
-            col_defs.push(format!("  {} {}", attr.name, sql_type));
+            // FK attributes hold parent keys, so they keep the mapped type.
+            let sql_type = if attr.key == Some(KeyKind::Fk) {
+                let mapped = mermaid_type_to_synthtab(&attr.data_type).unwrap_or("STRING");
+                to_sql_type(mapped, dialect, is_pk)?
+            } else {
+                sql_type_for(&column, frame, dialect, is_pk)?
+            };
+            col_defs.push(column_def(
+                &attr.name,
+                &sql_type,
+                column.is_nullable(),
+                is_pk,
+                dialect,
+            ));
         }
 
         // FK columns and constraints from relationships where this entity is child
@@ -104,7 +208,8 @@ pub fn emit_er_ddl(
                 // Only add FK column if not already declared by user
                 let already_declared = entity.attributes.iter().any(|a| a.name == fk_col);
                 if !already_declared {
-                    col_defs.push(format!("  {} INTEGER", fk_col));
+                    let sql_type = key_sql_type(ast, parent, dialect)?;
+                    col_defs.push(column_def(&fk_col, &sql_type, false, false, dialect));
                 }
                 if emit_fk_constraints {
                     let parent_entity = ast.entity(parent).expect("parent in AST");
@@ -141,8 +246,20 @@ pub fn emit_er_ddl(
         let left_fk = format!("{}_id", rel.left.to_lowercase());
         let right_fk = format!("{}_id", rel.right.to_lowercase());
         let mut col_defs = vec![
-            format!("  {} INTEGER", left_fk),
-            format!("  {} INTEGER", right_fk),
+            column_def(
+                &left_fk,
+                &key_sql_type(ast, &rel.left, dialect)?,
+                false,
+                false,
+                dialect,
+            ),
+            column_def(
+                &right_fk,
+                &key_sql_type(ast, &rel.right, dialect)?,
+                false,
+                false,
+                dialect,
+            ),
         ];
         if emit_fk_constraints {
             let left_entity = ast.entity(&rel.left).expect("left entity in AST");
@@ -185,13 +302,17 @@ mod test {
             name: name.into(),
             datatype: datatype.into(),
             modifier: None,
+            null_ratio: None,
+            locale: None,
+            children: Vec::new(),
         }
     }
 
     #[test]
     fn postgres_create_table_has_correct_shape() {
         let cols = vec![col("id", "INT_INC"), col("name", "STRING")];
-        let ddl = emit_create_table("users", &cols, Dialect::Postgres).unwrap();
+        let ddl =
+            emit_create_table("users", &cols, &FrameSchema::new(), Dialect::Postgres).unwrap();
         assert!(ddl.starts_with("CREATE TABLE users ("), "got: {ddl}");
         assert!(ddl.contains("id SERIAL PRIMARY KEY"), "got: {ddl}");
         assert!(ddl.contains("name TEXT"), "got: {ddl}");
@@ -201,7 +322,7 @@ mod test {
     #[test]
     fn mysql_uses_auto_increment_for_first_int_inc() {
         let cols = vec![col("id", "INT_INC"), col("seq", "INT_INC")];
-        let ddl = emit_create_table("t", &cols, Dialect::Mysql).unwrap();
+        let ddl = emit_create_table("t", &cols, &FrameSchema::new(), Dialect::Mysql).unwrap();
         assert!(
             ddl.contains("id INT AUTO_INCREMENT PRIMARY KEY"),
             "got: {ddl}"
@@ -212,7 +333,8 @@ mod test {
     #[test]
     fn sqlserver_uses_identity_for_pk() {
         let cols = vec![col("id", "INT_INC"), col("email", "STRING")];
-        let ddl = emit_create_table("accts", &cols, Dialect::Sqlserver).unwrap();
+        let ddl =
+            emit_create_table("accts", &cols, &FrameSchema::new(), Dialect::Sqlserver).unwrap();
         assert!(
             ddl.contains("id INT IDENTITY(1,1) PRIMARY KEY"),
             "got: {ddl}"
@@ -223,7 +345,8 @@ mod test {
     #[test]
     fn bigquery_no_native_pk_decoration() {
         let cols = vec![col("id", "INT_INC"), col("price", "DECIMAL")];
-        let ddl = emit_create_table("orders", &cols, Dialect::Bigquery).unwrap();
+        let ddl =
+            emit_create_table("orders", &cols, &FrameSchema::new(), Dialect::Bigquery).unwrap();
         assert!(ddl.contains("id INT64"), "got: {ddl}");
         assert!(ddl.contains("price NUMERIC"), "got: {ddl}");
     }
@@ -232,9 +355,12 @@ mod test {
     fn all_types_round_trip_without_error() {
         let types = [
             "INT_INC",
+            "BIGINT_INC",
             "INT",
+            "BIGINT",
             "INT_RNG",
             "DIGIT",
+            "BOOLEAN",
             "DECIMAL",
             "PRICE",
             "STRING",
@@ -258,6 +384,24 @@ mod test {
             "LOREM_SENTENCE",
             "LOREM_PARAGRAPH",
             "UUID",
+            "EMAIL",
+            "URL",
+            "DOMAIN",
+            "IPV4",
+            "IPV6",
+            "MAC_ADDRESS",
+            "USER_AGENT",
+            "COMPANY",
+            "JOB_TITLE",
+            "INDUSTRY",
+            "CREDIT_CARD",
+            "IBAN",
+            "BIC",
+            "CURRENCY_CODE",
+            "BINARY",
+            "HEX",
+            "BASE64",
+            "TOKEN",
         ];
         for dialect in [
             Dialect::Mysql,
@@ -267,7 +411,7 @@ mod test {
             Dialect::Spark,
         ] {
             let cols: Vec<Schema> = types.iter().map(|t| col(t, t)).collect();
-            let result = emit_create_table("all_types", &cols, dialect);
+            let result = emit_create_table("all_types", &cols, &FrameSchema::new(), dialect);
             assert!(result.is_ok(), "dialect={dialect:?}: {result:?}");
         }
     }
@@ -299,4 +443,148 @@ mod test {
         assert_eq!(table_name_from_path("/data/orders.parquet"), "orders");
         assert_eq!(table_name_from_path("plain"), "plain");
     }
+
+    #[test]
+    fn nullable_columns_switch_between_null_and_not_null() {
+        let mut email = col("email", "STRING");
+        email.null_ratio = Some(0.1);
+        let cols = vec![col("id", "INT_INC"), email, col("name", "NAME")];
+
+        let ddl =
+            emit_create_table("users", &cols, &FrameSchema::new(), Dialect::Postgres).unwrap();
+        assert!(ddl.contains("id SERIAL PRIMARY KEY,"), "got: {ddl}");
+        assert!(ddl.contains("email TEXT NULL,"), "got: {ddl}");
+        assert!(ddl.contains("name TEXT NOT NULL"), "got: {ddl}");
+
+        let ddl =
+            emit_create_table("users", &cols, &FrameSchema::new(), Dialect::Bigquery).unwrap();
+        assert!(ddl.contains("email STRING,"), "got: {ddl}");
+        assert!(ddl.contains("name STRING NOT NULL"), "got: {ddl}");
+    }
+
+    #[test]
+    fn non_pk_int_inc_is_not_doubled_up() {
+        let cols = vec![col("id", "INT_INC"), col("seq", "INT_INC")];
+        let ddl = emit_create_table("t", &cols, &FrameSchema::new(), Dialect::Postgres).unwrap();
+        assert!(ddl.contains("seq INTEGER NOT NULL\n"), "got: {ddl}");
+        assert!(!ddl.contains("NOT NULL NOT NULL"), "got: {ddl}");
+    }
+
+    #[test]
+    fn series_tables_have_no_primary_key() {
+        let cols = vec![
+            col("device_id", "INT_INC"),
+            col("ts", "DATE_TIME"),
+            col("seq", "BIGINT_INC"),
+        ];
+        let ddl =
+            emit_series_table("readings", &cols, &FrameSchema::new(), Dialect::Mysql).unwrap();
+        assert!(ddl.contains("device_id INT NOT NULL,"), "got: {ddl}");
+        assert!(ddl.contains("seq BIGINT NOT NULL\n"), "got: {ddl}");
+        assert!(!ddl.contains("PRIMARY KEY"), "got: {ddl}");
+    }
+
+    #[test]
+    fn nullable_int_inc_is_not_the_primary_key() {
+        let mut id = col("id", "INT_INC");
+        id.null_ratio = Some(0.2);
+        let cols = vec![id, col("seq", "BIGINT_INC")];
+        let ddl = emit_create_table("t", &cols, &FrameSchema::new(), Dialect::Postgres).unwrap();
+        assert!(ddl.contains("id INTEGER NULL,"), "got: {ddl}");
+        assert!(ddl.contains("seq BIGSERIAL PRIMARY KEY"), "got: {ddl}");
+
+        let mut id = col("id", "INT_INC");
+        id.null_ratio = Some(0.2);
+        let ddl = emit_create_table("t", &[id], &FrameSchema::new(), Dialect::Mysql).unwrap();
+        assert!(!ddl.contains("PRIMARY KEY"), "got: {ddl}");
+    }
+
+    #[test]
+    fn er_ddl_follows_attribute_hints() {
+        use crate::util::parser::parse;
+        use crate::util::scanner::scan;
+        let src = "erDiagram\nC { int id PK\n string nickname \"?\"\n string name }\nO { int id PK }\nC ||--o{ O : places\n";
+        let ast = parse(scan(src).unwrap()).unwrap();
+        let ddl = emit_er_ddl(
+            &ast,
+            &["C".to_string(), "O".to_string()],
+            &HashMap::new(),
+            Dialect::Mysql,
+        )
+        .unwrap();
+        assert!(ddl.contains("nickname VARCHAR(255) NULL"), "got: {ddl}");
+        assert!(ddl.contains("name VARCHAR(255) NOT NULL"), "got: {ddl}");
+        assert!(ddl.contains("c_id INT NOT NULL"), "got: {ddl}");
+    }
+
+    #[test]
+    fn bigint_keys_and_their_foreign_keys_are_bigint() {
+        use crate::util::parser::parse;
+        use crate::util::scanner::scan;
+        let src = "erDiagram\nC { bigint id PK }\nO { int id PK }\nC ||--o{ O : places\n";
+        let ast = parse(scan(src).unwrap()).unwrap();
+        let names = ["C".to_string(), "O".to_string()];
+        let ddl = emit_er_ddl(&ast, &names, &HashMap::new(), Dialect::Postgres).unwrap();
+        assert!(ddl.contains("id BIGSERIAL PRIMARY KEY"), "got: {ddl}");
+        assert!(ddl.contains("c_id BIGINT NOT NULL"), "got: {ddl}");
+        let ddl = emit_er_ddl(&ast, &names, &HashMap::new(), Dialect::Bigquery).unwrap();
+        assert!(ddl.contains("c_id INT64"), "got: {ddl}");
+    }
+
+    #[test]
+    fn formatted_sequences_stay_primary_keys() {
+        let id = Schema::new("id", "INT_INC", Some("(prefix='ORD-', width=6)")).unwrap();
+        let cols = vec![id, col("seq", "INT_INC")];
+        let ddl = emit_create_table("orders", &cols, &FrameSchema::new(), Dialect::Mysql).unwrap();
+        assert!(ddl.contains("id VARCHAR(15) PRIMARY KEY"), "got: {ddl}");
+        assert!(ddl.contains("seq INT NOT NULL"), "got: {ddl}");
+
+        use crate::util::parser::parse;
+        use crate::util::scanner::scan;
+        let src = "erDiagram\nORD { string id PK \"INT_INC(prefix='ORD-')\" }\nITEM { int id PK }\nORD ||--o{ ITEM : has\n";
+        let ast = parse(scan(src).unwrap()).unwrap();
+        let names = ["ORD".to_string(), "ITEM".to_string()];
+        let ddl = emit_er_ddl(&ast, &names, &HashMap::new(), Dialect::Postgres).unwrap();
+        assert!(ddl.contains("id VARCHAR(15) PRIMARY KEY"), "got: {ddl}");
+        assert!(ddl.contains("ord_id VARCHAR(15) NOT NULL"), "got: {ddl}");
+    }
+
+    #[test]
+    fn enum_columns_carry_their_values() {
+        let status = Schema::new("status", "ENUM", Some("('new', 'paid')")).unwrap();
+        let ddl =
+            emit_create_table("orders", &[status], &FrameSchema::new(), Dialect::Postgres).unwrap();
+        assert!(
+            ddl.contains("status VARCHAR(4) CHECK (status IN ('new', 'paid')) NOT NULL"),
+            "got: {ddl}"
+        );
+
+        use crate::util::parser::parse;
+        use crate::util::scanner::scan;
+        let src = "erDiagram\nO { int id PK\n string state \"ENUM?('new'=3, 'paid'=1)\" }\n";
+        let ast = parse(scan(src).unwrap()).unwrap();
+        let ddl = emit_er_ddl(&ast, &["O".to_string()], &HashMap::new(), Dialect::Mysql).unwrap();
+        assert!(ddl.contains("state ENUM('new','paid') NULL"), "got: {ddl}");
+    }
+
+    #[test]
+    fn expr_columns_take_the_generated_dtype() {
+        use polars::prelude::{DataType, Field};
+        let cols = vec![
+            col("qty", "INT"),
+            Schema::new("total", "EXPR", Some("(qty * 2.5)")).unwrap(),
+        ];
+        let frame = FrameSchema::from_iter([
+            Field::new("qty", DataType::Int32),
+            Field::new("total", DataType::Float64),
+        ]);
+        let ddl = emit_create_table("t", &cols, &frame, Dialect::Postgres).unwrap();
+        assert!(
+            ddl.contains("total DOUBLE PRECISION NOT NULL"),
+            "got: {ddl}"
+        );
+
+        let err = emit_create_table("t", &cols, &FrameSchema::new(), Dialect::Postgres);
+        assert!(err.is_err());
+    }
 }
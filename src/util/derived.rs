@@ -0,0 +1,305 @@
+//! Derived columns: `TEMPLATE` and `EXPR` columns computed from other
+//! columns of the same row.
+//!
+//! Derived columns draw no randomness. Frame builders generate every other
+//! column first, in schema order, then call `render_pending`, which fills the
+//! derived columns in dependency order so one may reference another.
+
+use crate::util::expr::{evaluate, expr_spec, references};
+use crate::util::fake::apply_null_ratio;
+use crate::util::modifier::ModifierError;
+use crate::util::rng::RunRng;
+use crate::util::schema::Schema;
+use crate::util::template::{template_spec, Template};
+use polars::prelude::*;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivedError {
+    pub message: String,
+}
+
+impl fmt::Display for DerivedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for DerivedError {}
+
+/// How a derived column is computed.
+pub enum Derivation {
+    Template(Template),
+    Expr(Expr),
+}
+
+impl Derivation {
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            Derivation::Template(_) => "TEMPLATE",
+            Derivation::Expr(_) => "EXPR",
+        }
+    }
+
+    pub(crate) fn references(&self) -> Vec<String> {
+        match self {
+            Derivation::Template(t) => t.references().into_iter().map(String::from).collect(),
+            Derivation::Expr(e) => references(e),
+        }
+    }
+
+    fn render(&self, name: &str, frame: &DataFrame) -> PolarsResult<Series> {
+        match self {
+            Derivation::Template(t) => t.render(name, frame),
+            Derivation::Expr(e) => evaluate(e, name, frame),
+        }
+    }
+}
+
+/// The derivation of a `TEMPLATE` or `EXPR` column, or `None` for a column
+/// that is generated directly.
+pub fn derivation_of(element: &Schema) -> Result<Option<Derivation>, ModifierError> {
+    match element.datatype.as_str() {
+        "TEMPLATE" => template_spec(element).map(|t| Some(Derivation::Template(t))),
+        "EXPR" => expr_spec(element).map(|e| Some(Derivation::Expr(e))),
+        _ => Ok(None),
+    }
+}
+
+/// A derived column waiting to be rendered into `slot` of its frame.
+pub struct Pending {
+    pub slot: usize,
+    pub column: Schema,
+    pub derivation: Derivation,
+}
+
+/// Render every pending column into `cols`, in dependency order, then apply
+/// its null ratio. All other slots must already be filled.
+///
+/// Errors on a reference to an unknown column and on a dependency cycle.
+pub fn render_pending(
+    pending: Vec<Pending>,
+    cols: &mut [Option<Series>],
+    rng: &mut RunRng,
+    table: &str,
+) -> Result<(), DerivedError> {
+    let built: Vec<String> = cols
+        .iter()
+        .flatten()
+        .map(|s| s.name().to_string())
+        .collect();
+    for i in render_order(&pending, &built)? {
+        let p = &pending[i];
+        let name = p.column.name.as_str();
+        let err = |e: PolarsError| DerivedError {
+            message: format!(
+                "column '{name}': failed to render {}: {e}",
+                p.derivation.kind()
+            ),
+        };
+        let frame = DataFrame::new(cols.iter().flatten().cloned().collect()).map_err(err)?;
+        let mut col = p.derivation.render(name, &frame).map_err(err)?;
+        if let Some(ratio) = p.column.null_ratio {
+            col = apply_null_ratio(col, ratio, &mut rng.nulls(table, name)).map_err(|e| {
+                DerivedError {
+                    message: format!("failed to apply nulls to column '{name}': {e}"),
+                }
+            })?;
+        }
+        cols[p.slot] = Some(col);
+    }
+    Ok(())
+}
+
+/// Check that the derived columns of `columns` reference only columns of
+/// `columns` and form no cycle, without rendering anything.
+pub fn check_derivations(columns: &[Schema]) -> Result<(), Box<dyn Error>> {
+    let mut pending = Vec::new();
+    let mut built = Vec::new();
+    for (slot, column) in columns.iter().enumerate() {
+        match derivation_of(column)? {
+            Some(derivation) => pending.push(Pending {
+                slot,
+                column: column.clone(),
+                derivation,
+            }),
+            None => built.push(column.name.clone()),
+        }
+    }
+    render_order(&pending, &built)?;
+    Ok(())
+}
+
+/// Indexes into `pending` such that every column comes after the derived
+/// columns it references. `built` names the columns generated directly.
+fn render_order(pending: &[Pending], built: &[String]) -> Result<Vec<usize>, DerivedError> {
+    let by_name: HashMap<&str, usize> = pending
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (p.column.name.as_str(), i))
+        .collect();
+    let deps: Vec<Vec<String>> = pending.iter().map(|p| p.derivation.references()).collect();
+    for (p, refs) in pending.iter().zip(&deps) {
+        for reference in refs {
+            if !built.contains(reference) && !by_name.contains_key(reference.as_str()) {
+                return Err(DerivedError {
+                    message: format!(
+                        "column '{}': {} references unknown column '{reference}'",
+                        p.column.name,
+                        p.derivation.kind()
+                    ),
+                });
+            }
+        }
+    }
+
+    #[derive(Clone, Copy, PartialEq)]
+    enum Mark {
+        Unvisited,
+        InProgress,
+        Done,
+    }
+    struct Walk<'a> {
+        pending: &'a [Pending],
+        deps: &'a [Vec<String>],
+        by_name: &'a HashMap<&'a str, usize>,
+        marks: Vec<Mark>,
+        path: Vec<usize>,
+        order: Vec<usize>,
+    }
+    impl Walk<'_> {
+        fn visit(&mut self, i: usize) -> Result<(), DerivedError> {
+            match self.marks[i] {
+                Mark::Done => return Ok(()),
+                Mark::InProgress => {
+                    let start = self.path.iter().position(|&p| p == i).unwrap_or(0);
+                    let cycle: Vec<&str> = self.path[start..]
+                        .iter()
+                        .chain(std::iter::once(&i))
+                        .map(|&p| self.pending[p].column.name.as_str())
+                        .collect();
+                    return Err(DerivedError {
+                        message: format!("derived columns form a cycle: {}", cycle.join(" -> ")),
+                    });
+                }
+                Mark::Unvisited => {}
+            }
+            self.marks[i] = Mark::InProgress;
+            self.path.push(i);
+            for reference in &self.deps[i] {
+                if let Some(&dep) = self.by_name.get(reference.as_str()) {
+                    self.visit(dep)?;
+                }
+            }
+            self.path.pop();
+            self.marks[i] = Mark::Done;
+            self.order.push(i);
+            Ok(())
+        }
+    }
+
+    let mut walk = Walk {
+        pending,
+        deps: &deps,
+        by_name: &by_name,
+        marks: vec![Mark::Unvisited; pending.len()],
+        path: Vec::new(),
+        order: Vec::with_capacity(pending.len()),
+    };
+    for i in 0..pending.len() {
+        walk.visit(i)?;
+    }
+    Ok(walk.order)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn pending(slot: usize, name: &str, datatype: &str, modifier: &str) -> Pending {
+        let column = Schema::new(name, datatype, Some(modifier)).unwrap();
+        let derivation = derivation_of(&column).unwrap().unwrap();
+        Pending {
+            slot,
+            column,
+            derivation,
+        }
+    }
+
+    #[test]
+    fn renders_in_dependency_order_and_propagates_nulls() {
+        let mut cols = vec![
+            Some(Series::new("first", vec![Some("Ada"), None])),
+            Some(Series::new("last", vec!["Lovelace", "Hopper"])),
+            None,
+            None,
+        ];
+        let templates = vec![
+            pending(2, "email", "TEMPLATE", r#"("{handle}@example.com")"#),
+            pending(
+                3,
+                "handle",
+                "TEMPLATE",
+                r#"("{first|lower}.{last|upper|truncate(3)}")"#,
+            ),
+        ];
+        let mut rng = RunRng::new(Some(1), false);
+        render_pending(templates, &mut cols, &mut rng, "t").unwrap();
+        let email = cols[2].as_ref().unwrap();
+        let email: Vec<Option<&str>> = email.str().unwrap().into_iter().collect();
+        assert_eq!(email, vec![Some("ada.LOV@example.com"), None]);
+    }
+
+    #[test]
+    fn expressions_and_templates_can_reference_each_other() {
+        let mut cols = vec![Some(Series::new("qty", vec![2i32, 3])), None, None];
+        let derived = vec![
+            pending(1, "label", "TEMPLATE", r#"("x{double}")"#),
+            pending(2, "double", "EXPR", "(qty * 2)"),
+        ];
+        let mut rng = RunRng::new(Some(1), false);
+        render_pending(derived, &mut cols, &mut rng, "t").unwrap();
+        let label = cols[1].as_ref().unwrap();
+        let label: Vec<Option<&str>> = label.str().unwrap().into_iter().collect();
+        assert_eq!(label, vec![Some("x4"), Some("x6")]);
+    }
+
+    #[test]
+    fn reports_cycles_and_unknown_columns() {
+        let mut rng = RunRng::new(Some(1), false);
+        let err = render_pending(
+            vec![
+                pending(0, "a", "TEMPLATE", r#"("{b}")"#),
+                pending(1, "b", "EXPR", "(a + 1)"),
+            ],
+            &mut [None, None],
+            &mut rng,
+            "t",
+        )
+        .unwrap_err();
+        assert_eq!(err.message, "derived columns form a cycle: a -> b -> a");
+
+        let err = render_pending(
+            vec![pending(0, "a", "TEMPLATE", r#"("{a}")"#)],
+            &mut [None],
+            &mut rng,
+            "t",
+        )
+        .unwrap_err();
+        assert!(err.message.contains("cycle: a -> a"), "{}", err.message);
+
+        let err = render_pending(
+            vec![pending(0, "a", "EXPR", "(nope * 2)")],
+            &mut [None],
+            &mut rng,
+            "t",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.message,
+            "column 'a': EXPR references unknown column 'nope'"
+        );
+    }
+}
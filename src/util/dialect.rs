@@ -5,11 +5,18 @@
 //! - `to_sql_type`: mapping from a synthtab schema type (uppercase keys matching
 //!   `src/util/fake.rs::create_column`) to a dialect-specific SQL column type
 //!   string per PRD §6.2.
+//! - `column_sql_type`: the same mapping for a whole schema column, for types
+//!   whose SQL type depends on their modifier (e.g. `ENUM` values).
 //!
 //! D1 is the lookup table only. DDL string assembly, load commands, Parquet
 //! logical-type wiring, and the `--target` CLI flag land in D2+.
 
+use crate::util::fake::{
+    byte_length, decimal_digits, enum_spec, pattern_spec, sequence_spec, string_length, token_spec,
+};
+use crate::util::schema::Schema;
 use clap::ValueEnum;
+use polars::prelude::DataType;
 use std::error::Error;
 use std::fmt;
 
@@ -71,7 +78,8 @@ impl Dialect {
 ///
 /// `synthtab_type` must be one of the uppercase keys accepted by
 /// `src/util/fake.rs::create_column` (e.g. `"INT_INC"`, `"STRING"`,
-/// `"DATE_TIME"`). `is_pk` only changes the result for `"INT_INC"`; for every
+/// `"DATE_TIME"`). `is_pk` only changes the result for `"INT_INC"` and
+/// `"BIGINT_INC"`; for every
 /// other type it is accepted but ignored. PK decoration of non-`INT_INC`
 /// columns is deferred to D2 where the DDL emitter has full column context.
 pub fn to_sql_type(
@@ -93,6 +101,18 @@ pub fn to_sql_type(
         ("INT_INC", Bigquery) => "INT64",
         ("INT_INC", Spark) => "INT",
 
+        // BIGINT_INC: INT_INC for 64-bit keys
+        ("BIGINT_INC", Mysql) if is_pk => "BIGINT AUTO_INCREMENT PRIMARY KEY",
+        ("BIGINT_INC", Postgres) if is_pk => "BIGSERIAL PRIMARY KEY",
+        ("BIGINT_INC", Sqlserver) if is_pk => "BIGINT IDENTITY(1,1) PRIMARY KEY",
+        ("BIGINT_INC", Bigquery) => "INT64",
+        ("BIGINT_INC", Spark) => "BIGINT",
+        ("BIGINT_INC", _) => "BIGINT NOT NULL",
+
+        // BIGINT: plain 64-bit integers
+        ("BIGINT", Bigquery) => "INT64",
+        ("BIGINT", _) => "BIGINT",
+
         // INT, INT_RNG: plain integers
         ("INT", Mysql) | ("INT_RNG", Mysql) => "INT",
         ("INT", Postgres) | ("INT_RNG", Postgres) => "INTEGER",
@@ -104,6 +124,12 @@ pub fn to_sql_type(
         ("DIGIT", Mysql) | ("DIGIT", Postgres) | ("DIGIT", Sqlserver) => "CHAR(1)",
         ("DIGIT", Bigquery) | ("DIGIT", Spark) => "STRING",
 
+        // BOOLEAN: MySQL's BOOLEAN is an alias of TINYINT(1)
+        ("BOOLEAN", Mysql) => "TINYINT(1)",
+        ("BOOLEAN", Postgres) | ("BOOLEAN", Spark) => "BOOLEAN",
+        ("BOOLEAN", Sqlserver) => "BIT",
+        ("BOOLEAN", Bigquery) => "BOOL",
+
         // DECIMAL
         ("DECIMAL", Mysql) => "DECIMAL(10,2)",
         ("DECIMAL", Postgres) => "NUMERIC(10,2)",
@@ -188,6 +214,46 @@ pub fn to_sql_type(
         ("PHONE", Mysql) | ("PHONE", Postgres) | ("PHONE", Sqlserver) => "VARCHAR(20)",
         ("PHONE", Bigquery) | ("PHONE", Spark) => "STRING",
 
+        // Internet types; Postgres has native network address types
+        ("EMAIL", Mysql) | ("EMAIL", Postgres) => "VARCHAR(254)",
+        ("EMAIL", Sqlserver) => "NVARCHAR(254)",
+        ("DOMAIN", Mysql) | ("DOMAIN", Postgres) => "VARCHAR(253)",
+        ("DOMAIN", Sqlserver) => "NVARCHAR(253)",
+        ("URL", Mysql) => "VARCHAR(2048)",
+        ("URL", Postgres) => "TEXT",
+        ("URL", Sqlserver) => "NVARCHAR(2048)",
+        ("USER_AGENT", Mysql) => "VARCHAR(512)",
+        ("USER_AGENT", Postgres) => "TEXT",
+        ("USER_AGENT", Sqlserver) => "NVARCHAR(512)",
+        ("IPV4", Postgres) | ("IPV6", Postgres) => "INET",
+        ("IPV4", Mysql) | ("IPV4", Sqlserver) => "VARCHAR(15)",
+        ("IPV6", Mysql) | ("IPV6", Sqlserver) => "VARCHAR(39)",
+        ("MAC_ADDRESS", Postgres) => "MACADDR",
+        ("MAC_ADDRESS", Mysql) | ("MAC_ADDRESS", Sqlserver) => "CHAR(17)",
+        (
+            "EMAIL" | "DOMAIN" | "URL" | "USER_AGENT" | "IPV4" | "IPV6" | "MAC_ADDRESS",
+            Bigquery | Spark,
+        ) => "STRING",
+
+        // Company and finance types; account numbers are fixed-format ASCII
+        ("COMPANY", Mysql) => "VARCHAR(255)",
+        ("COMPANY", Sqlserver) => "NVARCHAR(255)",
+        ("JOB_TITLE", Mysql) | ("INDUSTRY", Mysql) => "VARCHAR(100)",
+        ("JOB_TITLE", Sqlserver) | ("INDUSTRY", Sqlserver) => "NVARCHAR(100)",
+        ("COMPANY", Postgres) | ("JOB_TITLE", Postgres) | ("INDUSTRY", Postgres) => "TEXT",
+        ("CREDIT_CARD", Mysql) | ("CREDIT_CARD", Postgres) | ("CREDIT_CARD", Sqlserver) => {
+            "VARCHAR(19)"
+        }
+        ("IBAN", Mysql) | ("IBAN", Postgres) | ("IBAN", Sqlserver) => "VARCHAR(34)",
+        ("BIC", Mysql) | ("BIC", Postgres) | ("BIC", Sqlserver) => "VARCHAR(11)",
+        ("CURRENCY_CODE", Mysql) | ("CURRENCY_CODE", Postgres) | ("CURRENCY_CODE", Sqlserver) => {
+            "CHAR(3)"
+        }
+        (
+            "COMPANY" | "JOB_TITLE" | "INDUSTRY" | "CREDIT_CARD" | "IBAN" | "BIC" | "CURRENCY_CODE",
+            Bigquery | Spark,
+        ) => "STRING",
+
         // LOREM_WORD
         ("LOREM_WORD", Mysql) | ("LOREM_WORD", Postgres) => "VARCHAR(50)",
         ("LOREM_WORD", Sqlserver) => "NVARCHAR(50)",
@@ -209,6 +275,44 @@ pub fn to_sql_type(
         | ("LOREM_PARAGRAPH", Bigquery)
         | ("LOREM_PARAGRAPH", Spark) => "STRING",
 
+        // TEMPLATE: composed text, same as STRING
+        ("TEMPLATE", Mysql) => "VARCHAR(255)",
+        ("TEMPLATE", Postgres) => "TEXT",
+        ("TEMPLATE", Sqlserver) => "NVARCHAR(255)",
+        ("TEMPLATE", Bigquery) | ("TEMPLATE", Spark) => "STRING",
+
+        // PATTERN: unsized fallback; `column_sql_type` sizes it from the regex
+        ("PATTERN", Mysql) => "VARCHAR(255)",
+        ("PATTERN", Postgres) => "TEXT",
+        ("PATTERN", Sqlserver) => "NVARCHAR(255)",
+        ("PATTERN", Bigquery) | ("PATTERN", Spark) => "STRING",
+
+        // ENUM: unsized fallback; `column_sql_type` sizes it from the values
+        ("ENUM", Mysql) => "VARCHAR(255)",
+        ("ENUM", Postgres) => "TEXT",
+        ("ENUM", Sqlserver) => "NVARCHAR(255)",
+        ("ENUM", Bigquery) | ("ENUM", Spark) => "STRING",
+
+        // BINARY: unsized fallback; `column_sql_type` sizes it from the byte count
+        ("BINARY", Mysql) | ("BINARY", Sqlserver) => "VARBINARY(255)",
+        ("BINARY", Postgres) => "BYTEA",
+        ("BINARY", Bigquery) => "BYTES",
+        ("BINARY", Spark) => "BINARY",
+
+        // HEX / BASE64 / TOKEN: unsized fallback; `column_sql_type` sizes them
+        ("HEX", Mysql) | ("BASE64", Mysql) | ("TOKEN", Mysql) => "VARCHAR(255)",
+        ("HEX", Postgres) | ("BASE64", Postgres) | ("TOKEN", Postgres) => "TEXT",
+        ("HEX", Sqlserver) | ("BASE64", Sqlserver) | ("TOKEN", Sqlserver) => "NVARCHAR(255)",
+        ("HEX" | "BASE64" | "TOKEN", Bigquery | Spark) => "STRING",
+
+        // LIST / STRUCT: JSON documents; `column_sql_type` builds native
+        // ARRAY and STRUCT types for BigQuery and Spark from the element types
+        ("LIST", Mysql) | ("STRUCT", Mysql) => "JSON",
+        ("LIST", Postgres) | ("STRUCT", Postgres) => "JSONB",
+        ("LIST", Sqlserver) | ("STRUCT", Sqlserver) => "NVARCHAR(MAX)",
+        ("LIST", Bigquery) | ("STRUCT", Bigquery) => "JSON",
+        ("LIST", Spark) | ("STRUCT", Spark) => "STRING",
+
         // UUID
         ("UUID", Mysql) => "CHAR(36)",
         ("UUID", Postgres) => "UUID",
@@ -227,17 +331,256 @@ pub fn to_sql_type(
     Ok(mapped.to_string())
 }
 
+/// Map a schema column to a SQL column-type string for the given dialect.
+///
+/// Same as `to_sql_type` except for types sized from their modifier:
+/// - `ENUM` becomes a native `ENUM` on MySQL, a `VARCHAR` sized to the
+///   longest value with a `CHECK` constraint on Postgres, and a sized
+///   `NVARCHAR` on SQL Server.
+/// - `PATTERN` becomes a `VARCHAR` sized to the longest string the pattern
+///   can produce, and `STRING(lo..hi)` one sized to `hi`.
+/// - `DECIMAL(precision=p, scale=s)` becomes `DECIMAL(p,s)` on every
+///   dialect, mapped like an `EXPR` column of that dtype.
+/// - `BINARY` becomes a `VARBINARY` sized to its longest value on MySQL and
+///   SQL Server, and `HEX`, `BASE64` and `TOKEN` a `VARCHAR` sized to their
+///   longest encoding.
+///
+/// BigQuery and Spark have a single unsized `STRING`, so both keep the plain
+/// mapping. They do get native nested types: `LIST<T>` becomes `ARRAY<T>` and
+/// `STRUCT<a:T>` becomes `STRUCT<a T>` (`STRUCT<a: T>` on Spark).
+pub fn column_sql_type(
+    column: &Schema,
+    dialect: Dialect,
+    is_pk: bool,
+) -> Result<String, DialectError> {
+    let modifier_err =
+        |e: crate::util::modifier::ModifierError| DialectError { message: e.message };
+    if matches!(column.datatype.as_str(), "INT_INC" | "BIGINT_INC") {
+        if let Some((prefix, width)) = sequence_spec(column).map_err(modifier_err)?.format {
+            return Ok(formatted_sequence_type(
+                &column.datatype,
+                &prefix,
+                width,
+                dialect,
+                is_pk,
+            ));
+        }
+    }
+    if column.datatype == "DECIMAL" {
+        // A range or distribution without precision= is written as Float64.
+        match decimal_digits(column).map_err(modifier_err)? {
+            Some((p, s)) => return dtype_sql_type(&DataType::Decimal(Some(p), Some(s)), dialect),
+            None if column.modifier.is_some() => {
+                return dtype_sql_type(&DataType::Float64, dialect)
+            }
+            None => {}
+        }
+    }
+    if matches!(dialect, Dialect::Bigquery | Dialect::Spark) {
+        if matches!(column.datatype.as_str(), "LIST" | "STRUCT") {
+            return nested_type(column, dialect);
+        }
+        return to_sql_type(&column.datatype, dialect, is_pk);
+    }
+    match column.datatype.as_str() {
+        "ENUM" => {
+            let spec = enum_spec(column).map_err(modifier_err)?;
+            let quoted: Vec<String> = spec
+                .values
+                .iter()
+                .map(|v| format!("'{}'", v.replace('\'', "''")))
+                .collect();
+            let width = spec.values.iter().map(|v| v.chars().count()).max();
+            let varchar = sized_varchar(width.unwrap_or(1), dialect);
+            Ok(match dialect {
+                Dialect::Mysql => format!("ENUM({})", quoted.join(",")),
+                Dialect::Postgres => format!(
+                    "{varchar} CHECK ({} IN ({}))",
+                    column.name,
+                    quoted.join(", ")
+                ),
+                _ => varchar,
+            })
+        }
+        "PATTERN" => {
+            let regex = pattern_spec(column).map_err(modifier_err)?;
+            Ok(sized_varchar(regex.capacity(), dialect))
+        }
+        "STRING" => match string_length(column).map_err(modifier_err)? {
+            Some((_, chars)) => Ok(sized_varchar(chars, dialect)),
+            None => to_sql_type("STRING", dialect, is_pk),
+        },
+        "BINARY" => {
+            let (_, bytes) = byte_length(column).map_err(modifier_err)?;
+            Ok(sized_varbinary(bytes, dialect))
+        }
+        "HEX" => {
+            let (_, bytes) = byte_length(column).map_err(modifier_err)?;
+            Ok(sized_varchar(bytes * 2, dialect))
+        }
+        "BASE64" => {
+            let (_, bytes) = byte_length(column).map_err(modifier_err)?;
+            Ok(sized_varchar(bytes.div_ceil(3) * 4, dialect))
+        }
+        "TOKEN" => {
+            let (prefix, length) = token_spec(column).map_err(modifier_err)?;
+            Ok(sized_varchar(prefix.chars().count() + length, dialect))
+        }
+        other => to_sql_type(other, dialect, is_pk),
+    }
+}
+
+/// `ARRAY<...>` or `STRUCT<...>` for BigQuery and Spark, built from the
+/// element types. BigQuery cannot hold an array directly inside an array.
+fn nested_type(column: &Schema, dialect: Dialect) -> Result<String, DialectError> {
+    if column.datatype == "LIST" {
+        let item = &column.children[0];
+        if dialect == Dialect::Bigquery && item.datatype == "LIST" {
+            return Err(DialectError {
+                message: format!(
+                    "column '{}': BigQuery cannot nest an ARRAY directly in an ARRAY; wrap the inner LIST in a STRUCT",
+                    column.name
+                ),
+            });
+        }
+        return Ok(format!("ARRAY<{}>", column_sql_type(item, dialect, false)?));
+    }
+    let separator = match dialect {
+        Dialect::Spark => ": ",
+        _ => " ",
+    };
+    let fields = column
+        .children
+        .iter()
+        .map(|f| {
+            Ok(format!(
+                "{}{separator}{}",
+                f.name,
+                column_sql_type(f, dialect, false)?
+            ))
+        })
+        .collect::<Result<Vec<_>, DialectError>>()?;
+    Ok(format!("STRUCT<{}>", fields.join(", ")))
+}
+
+/// Map a Polars dtype to a SQL column-type string for the given dialect.
+///
+/// Used for `EXPR` columns, whose type is whatever the expression produced.
+/// Text and temporal dtypes share the `STRING`, `DATE`, `DATE_TIME` and
+/// `TIME` mappings of `to_sql_type`.
+pub fn dtype_sql_type(dtype: &DataType, dialect: Dialect) -> Result<String, DialectError> {
+    use Dialect::*;
+    let mapped = match (dtype, dialect) {
+        (DataType::Boolean, d) => return to_sql_type("BOOLEAN", d, false),
+
+        (
+            DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::UInt8 | DataType::UInt16,
+            d,
+        ) => return to_sql_type("INT", d, false),
+
+        (DataType::Int64 | DataType::UInt32 | DataType::UInt64, Bigquery) => "INT64",
+        (DataType::Int64 | DataType::UInt32 | DataType::UInt64, _) => "BIGINT",
+
+        (DataType::Float32, Mysql) | (DataType::Float32, Spark) => "FLOAT",
+        (DataType::Float32, Postgres) | (DataType::Float32, Sqlserver) => "REAL",
+        (DataType::Float64, Mysql) | (DataType::Float64, Spark) => "DOUBLE",
+        (DataType::Float64, Postgres) => "DOUBLE PRECISION",
+        (DataType::Float64, Sqlserver) => "FLOAT",
+        (DataType::Float32 | DataType::Float64, Bigquery) => "FLOAT64",
+
+        // NUMERIC holds 29 integer digits and 9 decimal places
+        (DataType::Decimal(Some(p), Some(s)), Bigquery) if *s > 9 || p - s > 29 => "BIGNUMERIC",
+        (DataType::Decimal(_, _), Bigquery) => "NUMERIC",
+        (DataType::Decimal(precision, scale), d) => {
+            let keyword = if d == Postgres { "NUMERIC" } else { "DECIMAL" };
+            let (p, s) = (precision.unwrap_or(38), scale.unwrap_or(0));
+            return Ok(format!("{keyword}({p},{s})"));
+        }
+
+        (DataType::String, d) => return to_sql_type("STRING", d, false),
+        (DataType::Binary, d) => return to_sql_type("BINARY", d, false),
+        (DataType::Date, d) => return to_sql_type("DATE", d, false),
+        (DataType::Datetime(..), d) => return to_sql_type("DATE_TIME", d, false),
+        (DataType::Time, d) => return to_sql_type("TIME", d, false),
+
+        (other, d) => {
+            return Err(DialectError {
+                message: format!("expression type '{other}' has no '{}' mapping", d.as_str()),
+            });
+        }
+    };
+    Ok(mapped.to_string())
+}
+
+/// A formatted `INT_INC`/`BIGINT_INC` sequence such as `ORD-000001` is text
+/// wide enough for the prefix and any value of the underlying integer. It is
+/// still the table's key, but with no identity decoration: the generated
+/// values are loaded as they are.
+fn formatted_sequence_type(
+    datatype: &str,
+    prefix: &str,
+    width: usize,
+    dialect: Dialect,
+    is_pk: bool,
+) -> String {
+    if matches!(dialect, Dialect::Bigquery | Dialect::Spark) {
+        return "STRING".to_string();
+    }
+    let digits = match datatype {
+        "INT_INC" => i32::MIN.to_string().len(),
+        _ => i64::MIN.to_string().len(),
+    };
+    let varchar = sized_varchar(prefix.chars().count() + digits.max(width), dialect);
+    if is_pk {
+        format!("{varchar} PRIMARY KEY")
+    } else {
+        varchar
+    }
+}
+
+/// Widest `VARCHAR(n)` emitted before falling back to an unbounded text type;
+/// SQL Server caps `NVARCHAR(n)` at 4000.
+const MAX_SIZED_VARCHAR: usize = 4000;
+
+fn sized_varchar(width: usize, dialect: Dialect) -> String {
+    let width = width.max(1);
+    match dialect {
+        Dialect::Sqlserver if width > MAX_SIZED_VARCHAR => "NVARCHAR(MAX)".to_string(),
+        Dialect::Sqlserver => format!("NVARCHAR({width})"),
+        _ if width > MAX_SIZED_VARCHAR => "TEXT".to_string(),
+        _ => format!("VARCHAR({width})"),
+    }
+}
+
+/// Widest `VARBINARY(n)` SQL Server accepts; MySQL gets a `MEDIUMBLOB`
+/// beyond the `VARCHAR` limit so large values do not eat its row size.
+const MAX_SIZED_VARBINARY: usize = 8000;
+
+fn sized_varbinary(width: usize, dialect: Dialect) -> String {
+    let width = width.max(1);
+    match dialect {
+        Dialect::Postgres => "BYTEA".to_string(),
+        Dialect::Mysql if width > MAX_SIZED_VARCHAR => "MEDIUMBLOB".to_string(),
+        Dialect::Sqlserver if width > MAX_SIZED_VARBINARY => "VARBINARY(MAX)".to_string(),
+        _ => format!("VARBINARY({width})"),
+    }
+}
+
 mod test {
     #![allow(unused_imports, dead_code)]
     use super::*;
+    use crate::util::locale::LOCALIZED_TYPES;
 
     /// Canonical list of every synthtab type covered by D1, drawn from PRD §6.2.
     /// Used by the contract test in `every_type_has_mapping_for_every_dialect`.
     const SUPPORTED_TYPES: &[&str] = &[
         "INT_INC",
+        "BIGINT_INC",
         "INT",
+        "BIGINT",
         "INT_RNG",
         "DIGIT",
+        "BOOLEAN",
         "DECIMAL",
         "PRICE",
         "STRING",
@@ -261,6 +604,29 @@ mod test {
         "LOREM_SENTENCE",
         "LOREM_PARAGRAPH",
         "UUID",
+        "EMAIL",
+        "URL",
+        "DOMAIN",
+        "IPV4",
+        "IPV6",
+        "MAC_ADDRESS",
+        "USER_AGENT",
+        "COMPANY",
+        "JOB_TITLE",
+        "INDUSTRY",
+        "CREDIT_CARD",
+        "IBAN",
+        "BIC",
+        "CURRENCY_CODE",
+        "BINARY",
+        "HEX",
+        "BASE64",
+        "TOKEN",
+        "ENUM",
+        "PATTERN",
+        "TEMPLATE",
+        "LIST",
+        "STRUCT",
     ];
 
     const ALL_DIALECTS: &[Dialect] = &[
@@ -446,6 +812,282 @@ mod test {
         );
     }
 
+    #[test]
+    fn internet_types_use_native_postgres_network_types() {
+        assert_eq!(
+            to_sql_type("IPV4", Dialect::Postgres, false).unwrap(),
+            "INET"
+        );
+        assert_eq!(
+            to_sql_type("IPV6", Dialect::Postgres, false).unwrap(),
+            "INET"
+        );
+        assert_eq!(
+            to_sql_type("MAC_ADDRESS", Dialect::Postgres, false).unwrap(),
+            "MACADDR"
+        );
+        assert_eq!(
+            to_sql_type("IPV6", Dialect::Mysql, false).unwrap(),
+            "VARCHAR(39)"
+        );
+        assert_eq!(
+            to_sql_type("EMAIL", Dialect::Sqlserver, false).unwrap(),
+            "NVARCHAR(254)"
+        );
+        assert_eq!(
+            to_sql_type("USER_AGENT", Dialect::Spark, false).unwrap(),
+            "STRING"
+        );
+    }
+
+    #[test]
+    fn formatted_sequences_are_sized_text_keys() {
+        let id = Schema::new("id", "BIGINT_INC", Some("(prefix='INV/', width=8)")).unwrap();
+        assert_eq!(
+            column_sql_type(&id, Dialect::Postgres, true).unwrap(),
+            "VARCHAR(24) PRIMARY KEY"
+        );
+        assert_eq!(
+            column_sql_type(&id, Dialect::Sqlserver, false).unwrap(),
+            "NVARCHAR(24)"
+        );
+        assert_eq!(
+            column_sql_type(&id, Dialect::Bigquery, true).unwrap(),
+            "STRING"
+        );
+        let plain = Schema::new("id", "INT_INC", Some("(start=100, step=5)")).unwrap();
+        assert_eq!(
+            column_sql_type(&plain, Dialect::Mysql, true).unwrap(),
+            "INT AUTO_INCREMENT PRIMARY KEY"
+        );
+    }
+
+    #[test]
+    fn nested_types_are_native_on_bigquery_and_spark_only() {
+        let tags = Schema::from_string("tags:LIST<LOREM_WORD>(0..5)")
+            .unwrap()
+            .unwrap();
+        let address = Schema::from_string("address:STRUCT<street:STRING, zip:ZIP_CODE>?")
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            column_sql_type(&tags, Dialect::Postgres, false).unwrap(),
+            "JSONB"
+        );
+        assert_eq!(
+            column_sql_type(&address, Dialect::Mysql, false).unwrap(),
+            "JSON"
+        );
+        assert_eq!(
+            column_sql_type(&tags, Dialect::Sqlserver, false).unwrap(),
+            "NVARCHAR(MAX)"
+        );
+        assert_eq!(
+            column_sql_type(&tags, Dialect::Bigquery, false).unwrap(),
+            "ARRAY<STRING>"
+        );
+        assert_eq!(
+            column_sql_type(&address, Dialect::Bigquery, false).unwrap(),
+            "STRUCT<street STRING, zip STRING>"
+        );
+        assert_eq!(
+            column_sql_type(&address, Dialect::Spark, false).unwrap(),
+            "STRUCT<street: STRING, zip: STRING>"
+        );
+    }
+
+    #[test]
+    fn bigquery_rejects_arrays_of_arrays() {
+        let grid = Schema::from_string("grid:LIST<LIST<INT>>")
+            .unwrap()
+            .unwrap();
+        let err = column_sql_type(&grid, Dialect::Bigquery, false).unwrap_err();
+        assert!(
+            err.message.contains("wrap the inner LIST in a STRUCT"),
+            "{}",
+            err.message
+        );
+        assert_eq!(
+            column_sql_type(&grid, Dialect::Spark, false).unwrap(),
+            "ARRAY<ARRAY<INT>>"
+        );
+    }
+
+    #[test]
+    fn sized_strings_and_decimals_follow_their_modifier() {
+        let nick = Schema::new("nick", "STRING", Some("(1..40)")).unwrap();
+        assert_eq!(
+            column_sql_type(&nick, Dialect::Postgres, false).unwrap(),
+            "VARCHAR(40)"
+        );
+        assert_eq!(
+            column_sql_type(&nick, Dialect::Sqlserver, false).unwrap(),
+            "NVARCHAR(40)"
+        );
+        assert_eq!(
+            column_sql_type(&nick, Dialect::Spark, false).unwrap(),
+            "STRING"
+        );
+        let plain = Schema::new("s", "STRING", None).unwrap();
+        assert_eq!(
+            column_sql_type(&plain, Dialect::Mysql, false).unwrap(),
+            "VARCHAR(255)"
+        );
+
+        let balance = Schema::new("b", "DECIMAL", Some("(precision=12, scale=4)")).unwrap();
+        for (dialect, expected) in [
+            (Dialect::Mysql, "DECIMAL(12,4)"),
+            (Dialect::Postgres, "NUMERIC(12,4)"),
+            (Dialect::Sqlserver, "DECIMAL(12,4)"),
+            (Dialect::Bigquery, "NUMERIC"),
+            (Dialect::Spark, "DECIMAL(12,4)"),
+        ] {
+            assert_eq!(column_sql_type(&balance, dialect, false).unwrap(), expected);
+        }
+        let wide = Schema::new("b", "DECIMAL", Some("(precision=38, scale=20)")).unwrap();
+        assert_eq!(
+            column_sql_type(&wide, Dialect::Bigquery, false).unwrap(),
+            "BIGNUMERIC"
+        );
+        let ranged = Schema::new("b", "DECIMAL", Some("(0..1, scale=3)")).unwrap();
+        assert_eq!(
+            column_sql_type(&ranged, Dialect::Postgres, false).unwrap(),
+            "DOUBLE PRECISION"
+        );
+        let plain = Schema::new("b", "DECIMAL", None).unwrap();
+        assert_eq!(
+            column_sql_type(&plain, Dialect::Postgres, false).unwrap(),
+            "NUMERIC(10,2)"
+        );
+    }
+
+    #[test]
+    fn binary_and_encoded_types_are_sized_from_their_length() {
+        let blob = Schema::new("blob", "BINARY", Some("(8..64)")).unwrap();
+        assert_eq!(
+            column_sql_type(&blob, Dialect::Mysql, false).unwrap(),
+            "VARBINARY(64)"
+        );
+        assert_eq!(
+            column_sql_type(&blob, Dialect::Postgres, false).unwrap(),
+            "BYTEA"
+        );
+        assert_eq!(
+            column_sql_type(&blob, Dialect::Sqlserver, false).unwrap(),
+            "VARBINARY(64)"
+        );
+        assert_eq!(
+            column_sql_type(&blob, Dialect::Bigquery, false).unwrap(),
+            "BYTES"
+        );
+        assert_eq!(
+            column_sql_type(&blob, Dialect::Spark, false).unwrap(),
+            "BINARY"
+        );
+        let big = Schema::new("blob", "BINARY", Some("(10000)")).unwrap();
+        assert_eq!(
+            column_sql_type(&big, Dialect::Mysql, false).unwrap(),
+            "MEDIUMBLOB"
+        );
+        assert_eq!(
+            column_sql_type(&big, Dialect::Sqlserver, false).unwrap(),
+            "VARBINARY(MAX)"
+        );
+
+        let digest = Schema::new("digest", "HEX", None).unwrap();
+        assert_eq!(
+            column_sql_type(&digest, Dialect::Postgres, false).unwrap(),
+            "VARCHAR(64)"
+        );
+        let key = Schema::new("key", "BASE64", Some("(16)")).unwrap();
+        assert_eq!(
+            column_sql_type(&key, Dialect::Mysql, false).unwrap(),
+            "VARCHAR(24)"
+        );
+        let token = Schema::new("token", "TOKEN", Some("('sk_live_', 24)")).unwrap();
+        assert_eq!(
+            column_sql_type(&token, Dialect::Postgres, false).unwrap(),
+            "VARCHAR(32)"
+        );
+        assert_eq!(
+            column_sql_type(&token, Dialect::Bigquery, false).unwrap(),
+            "STRING"
+        );
+    }
+
+    #[test]
+    fn localized_text_is_unicode_on_sqlserver() {
+        // PHONE, ZIP_CODE and STATE_ABBR are ASCII in every locale.
+        for ty in LOCALIZED_TYPES
+            .iter()
+            .filter(|t| !matches!(**t, "PHONE" | "ZIP_CODE" | "STATE_ABBR"))
+        {
+            let sql = to_sql_type(ty, Dialect::Sqlserver, false).unwrap();
+            assert!(sql.starts_with("NVARCHAR"), "{ty}: {sql}");
+        }
+    }
+
+    #[test]
+    fn finance_types_fit_their_fixed_formats() {
+        assert_eq!(
+            to_sql_type("IBAN", Dialect::Mysql, false).unwrap(),
+            "VARCHAR(34)"
+        );
+        assert_eq!(
+            to_sql_type("BIC", Dialect::Sqlserver, false).unwrap(),
+            "VARCHAR(11)"
+        );
+        assert_eq!(
+            to_sql_type("CREDIT_CARD", Dialect::Postgres, false).unwrap(),
+            "VARCHAR(19)"
+        );
+        assert_eq!(
+            to_sql_type("CURRENCY_CODE", Dialect::Postgres, false).unwrap(),
+            "CHAR(3)"
+        );
+        assert_eq!(
+            to_sql_type("COMPANY", Dialect::Bigquery, false).unwrap(),
+            "STRING"
+        );
+    }
+
+    #[test]
+    fn bigint_types_map_per_dialect() {
+        assert_eq!(
+            to_sql_type("BIGINT_INC", Dialect::Postgres, true).unwrap(),
+            "BIGSERIAL PRIMARY KEY"
+        );
+        assert_eq!(
+            to_sql_type("BIGINT_INC", Dialect::Mysql, false).unwrap(),
+            "BIGINT NOT NULL"
+        );
+        assert_eq!(
+            to_sql_type("BIGINT", Dialect::Sqlserver, false).unwrap(),
+            "BIGINT"
+        );
+        // BIGINT must agree with the generated Int64 dtype
+        for d in ALL_DIALECTS {
+            assert_eq!(
+                to_sql_type("BIGINT", *d, false).unwrap(),
+                dtype_sql_type(&DataType::Int64, *d).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn boolean_maps_per_dialect() {
+        let cases = [
+            (Dialect::Mysql, "TINYINT(1)"),
+            (Dialect::Postgres, "BOOLEAN"),
+            (Dialect::Sqlserver, "BIT"),
+            (Dialect::Bigquery, "BOOL"),
+            (Dialect::Spark, "BOOLEAN"),
+        ];
+        for (d, want) in cases {
+            assert_eq!(to_sql_type("BOOLEAN", d, false).unwrap(), want, "{d:?}");
+        }
+    }
+
     #[test]
     fn date_time_types_map_per_dialect() {
         assert_eq!(to_sql_type("DATE", Dialect::Mysql, false).unwrap(), "DATE");
@@ -472,6 +1114,60 @@ mod test {
         );
     }
 
+    #[test]
+    fn enum_columns_use_values_per_dialect() {
+        let modifier = r"('active'=70, 'on hold'=20, 'it\'s'=10)";
+        let column = Schema::new("status", "ENUM", Some(modifier)).unwrap();
+        assert_eq!(
+            column_sql_type(&column, Dialect::Mysql, false).unwrap(),
+            "ENUM('active','on hold','it''s')"
+        );
+        assert_eq!(
+            column_sql_type(&column, Dialect::Postgres, false).unwrap(),
+            "VARCHAR(7) CHECK (status IN ('active', 'on hold', 'it''s'))"
+        );
+        assert_eq!(
+            column_sql_type(&column, Dialect::Sqlserver, false).unwrap(),
+            "NVARCHAR(7)"
+        );
+        assert_eq!(
+            column_sql_type(&column, Dialect::Spark, false).unwrap(),
+            "STRING"
+        );
+    }
+
+    #[test]
+    fn pattern_columns_are_sized_from_the_regex() {
+        let column = Schema::new("sku", "PATTERN", Some(r"('[A-Z]{3}-\d{6}')")).unwrap();
+        assert_eq!(
+            column_sql_type(&column, Dialect::Postgres, false).unwrap(),
+            "VARCHAR(10)"
+        );
+        assert_eq!(
+            column_sql_type(&column, Dialect::Sqlserver, false).unwrap(),
+            "NVARCHAR(10)"
+        );
+        assert_eq!(
+            column_sql_type(&column, Dialect::Bigquery, false).unwrap(),
+            "STRING"
+        );
+
+        let column = Schema::new("blob", "PATTERN", Some("('[a-z]{5000}')")).unwrap();
+        assert_eq!(
+            column_sql_type(&column, Dialect::Mysql, false).unwrap(),
+            "TEXT"
+        );
+    }
+
+    #[test]
+    fn column_sql_type_defers_to_to_sql_type() {
+        let column = Schema::new("id", "INT_INC", None).unwrap();
+        assert_eq!(
+            column_sql_type(&column, Dialect::Postgres, true).unwrap(),
+            to_sql_type("INT_INC", Dialect::Postgres, true).unwrap()
+        );
+    }
+
     // ---------- Error paths ----------
 
     #[test]
@@ -510,7 +1206,7 @@ mod test {
     fn is_pk_is_ignored_for_non_int_inc_types() {
         for d in ALL_DIALECTS {
             for t in SUPPORTED_TYPES {
-                if *t == "INT_INC" {
+                if matches!(*t, "INT_INC" | "BIGINT_INC") {
                     continue;
                 }
                 let with_pk = to_sql_type(t, *d, true).unwrap();
@@ -545,4 +1241,35 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn expression_dtypes_map_per_dialect() {
+        let cases = [
+            (DataType::Boolean, Dialect::Sqlserver, "BIT"),
+            (DataType::Boolean, Dialect::Mysql, "TINYINT(1)"),
+            (DataType::Int32, Dialect::Postgres, "INTEGER"),
+            (DataType::Int64, Dialect::Bigquery, "INT64"),
+            (DataType::Float64, Dialect::Postgres, "DOUBLE PRECISION"),
+            (DataType::String, Dialect::Mysql, "VARCHAR(255)"),
+            (
+                DataType::Decimal(Some(10), Some(2)),
+                Dialect::Postgres,
+                "NUMERIC(10,2)",
+            ),
+            (
+                DataType::Decimal(Some(12), Some(4)),
+                Dialect::Spark,
+                "DECIMAL(12,4)",
+            ),
+            (
+                DataType::Decimal(Some(10), Some(2)),
+                Dialect::Bigquery,
+                "NUMERIC",
+            ),
+        ];
+        for (dtype, d, want) in cases {
+            assert_eq!(dtype_sql_type(&dtype, d).unwrap(), want, "{dtype:?} {d:?}");
+        }
+        assert!(dtype_sql_type(&DataType::Null, Dialect::Mysql).is_err());
+    }
 }
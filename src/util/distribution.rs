@@ -0,0 +1,356 @@
+//! Distributions for numeric columns.
+//!
+//! ```text
+//! score:INT(normal(50, 10), clamp=0..100)
+//! amount:DECIMAL(lognormal(3, 1), clamp=0..5000, scale=2)
+//! visits:INT(poisson(4))
+//! product_rank:INT(zipf(1000, 1.1))
+//! price:PRICE(exponential(0.05), clamp=1..500)
+//! temp:DECIMAL(walk(20, 0.5), clamp=-10..45, scale=1)
+//! load:INT(seasonal(60, 25, 288, 5), clamp=0..100)
+//! ```
+//!
+//! Each draw is clamped to `clamp=lo..hi` if given, then rounded to the
+//! column's scale: whole numbers for `INT`, `scale=N` places for `DECIMAL`
+//! and cents for `PRICE`.
+//!
+//! `walk` and `seasonal` depend on the row: `walk(start, sd)` moves each row
+//! from the previous one by a `normal(0, sd)` step, and `seasonal(mean,
+//! amplitude, period, sd)` follows a sine wave that repeats every `period`
+//! rows, with `normal(0, sd)` noise on top.
+
+use crate::util::modifier::{Modifier, Value};
+use rand::distributions::{Distribution, Uniform};
+use rand::Rng;
+use rand_distr::{Exp, LogNormal, Normal, Poisson, Zipf};
+
+const DISTRIBUTIONS: &str =
+    "normal(mean, sd), lognormal(mu, sigma), exponential(rate), poisson(mean), zipf(n, s), uniform(lo, hi), walk(start, sd), seasonal(mean, amplitude, period, sd)";
+
+pub(crate) enum Dist {
+    Normal(Normal<f64>),
+    LogNormal(LogNormal<f64>),
+    Exponential(Exp<f64>),
+    Poisson(Poisson<f64>),
+    Zipf(Zipf<f64>),
+    Uniform(Uniform<f64>),
+}
+
+impl Distribution<f64> for Dist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        match self {
+            Dist::Normal(d) => d.sample(rng),
+            Dist::LogNormal(d) => d.sample(rng),
+            Dist::Exponential(d) => d.sample(rng),
+            Dist::Poisson(d) => d.sample(rng),
+            Dist::Zipf(d) => d.sample(rng),
+            Dist::Uniform(d) => d.sample(rng),
+        }
+    }
+}
+
+/// How a column's draws relate to the row they land in.
+pub(crate) enum Pattern {
+    /// Every row is an independent draw.
+    Independent,
+    /// Each draw is a step from the previous row's value, starting here.
+    Walk(f64),
+    /// Each draw is added to a sine wave of this amplitude and period.
+    Seasonal { amplitude: f64, period: f64 },
+}
+
+/// A distribution with its optional clamp and rounding.
+pub(crate) struct NumericSpec {
+    pub dist: Dist,
+    pub pattern: Pattern,
+    pub clamp: Option<(f64, f64)>,
+    pub scale: Option<i32>,
+}
+
+impl NumericSpec {
+    /// Turn one raw `dist` draw per row into the column's values.
+    pub fn series(&self, draws: Vec<f64>) -> Vec<f64> {
+        match self.pattern {
+            Pattern::Independent => draws.into_iter().map(|v| self.finish(v)).collect(),
+            Pattern::Walk(start) => {
+                // The level itself is only clamped, so rounding never
+                // accumulates across steps.
+                let mut level = start;
+                draws
+                    .into_iter()
+                    .map(|step| {
+                        level = self.clamp(level + step);
+                        self.finish(level)
+                    })
+                    .collect()
+            }
+            Pattern::Seasonal { amplitude, period } => draws
+                .into_iter()
+                .enumerate()
+                .map(|(row, v)| {
+                    let phase = std::f64::consts::TAU * row as f64 / period;
+                    self.finish(v + amplitude * phase.sin())
+                })
+                .collect(),
+        }
+    }
+
+    fn clamp(&self, v: f64) -> f64 {
+        match self.clamp {
+            Some((lo, hi)) => v.clamp(lo, hi),
+            None => v,
+        }
+    }
+
+    fn finish(&self, v: f64) -> f64 {
+        let v = self.clamp(v);
+        match self.scale {
+            Some(s) => {
+                let factor = 10f64.powi(s);
+                self.clamp((v * factor).round() / factor)
+            }
+            None => v,
+        }
+    }
+}
+
+/// The distribution named by the first positional argument of `m`, with its
+/// `clamp` option. `None` when that argument is not a call, so a type can
+/// fall back to its other modifier forms. `scale` is left unset for the
+/// caller to fill in.
+pub(crate) fn numeric_spec(m: &Modifier) -> Result<Option<NumericSpec>, String> {
+    let Some(Value::Call(name, args)) = m.positional().first().copied() else {
+        return Ok(None);
+    };
+    if args.iter().any(|a| a.key.is_some()) {
+        return Err(format!("{name}(…) takes positional parameters only"));
+    }
+    let params = args
+        .iter()
+        .map(|a| {
+            a.value
+                .as_f64()
+                .ok_or_else(|| format!("{name}(…) parameter '{}' is not a number", a.value))
+        })
+        .collect::<Result<Vec<f64>, String>>()?;
+    let dist = dist(name, &params)?;
+    let pattern = match name.as_str() {
+        "walk" => Pattern::Walk(params[0]),
+        "seasonal" => Pattern::Seasonal {
+            amplitude: params[1],
+            period: params[2],
+        },
+        _ => Pattern::Independent,
+    };
+    let clamp = match m.option("clamp") {
+        None => None,
+        Some(v) => {
+            let (lo, hi) = v
+                .as_range()
+                .and_then(|(lo, hi)| Some((lo.as_f64()?, hi.as_f64()?)))
+                .ok_or_else(|| format!("clamp expects a numeric range like 0..100, got '{v}'"))?;
+            if lo > hi {
+                return Err(format!(
+                    "empty clamp {v}; lower bound must not exceed upper"
+                ));
+            }
+            Some((lo, hi))
+        }
+    };
+    Ok(Some(NumericSpec {
+        dist,
+        pattern,
+        clamp,
+        scale: None,
+    }))
+}
+
+fn dist(name: &str, params: &[f64]) -> Result<Dist, String> {
+    let arity = |n: usize, shape: &str| {
+        if params.len() == n {
+            Ok(())
+        } else {
+            Err(format!(
+                "expected {shape}, got {} parameter(s)",
+                params.len()
+            ))
+        }
+    };
+    let bad = |e: &dyn std::fmt::Display| format!("invalid {name} parameters: {e}");
+    match name {
+        "normal" => {
+            arity(2, "normal(mean, sd)")?;
+            if params[1] < 0.0 {
+                return Err(bad(&"sd must not be negative"));
+            }
+            Normal::new(params[0], params[1])
+                .map(Dist::Normal)
+                .map_err(|e| bad(&e))
+        }
+        "lognormal" => {
+            arity(2, "lognormal(mu, sigma)")?;
+            if params[1] < 0.0 {
+                return Err(bad(&"sigma must not be negative"));
+            }
+            LogNormal::new(params[0], params[1])
+                .map(Dist::LogNormal)
+                .map_err(|e| bad(&e))
+        }
+        "exponential" => {
+            arity(1, "exponential(rate)")?;
+            if params[0] <= 0.0 {
+                return Err(bad(&"rate must be positive"));
+            }
+            Exp::new(params[0])
+                .map(Dist::Exponential)
+                .map_err(|e| bad(&e))
+        }
+        "poisson" => {
+            arity(1, "poisson(mean)")?;
+            Poisson::new(params[0])
+                .map(Dist::Poisson)
+                .map_err(|e| bad(&e))
+        }
+        "zipf" => {
+            arity(2, "zipf(n, s)")?;
+            let n = params[0];
+            if n < 1.0 || n.fract() != 0.0 {
+                return Err(bad(&"n must be a whole number of at least 1"));
+            }
+            Zipf::new(n as u64, params[1])
+                .map(Dist::Zipf)
+                .map_err(|e| bad(&e))
+        }
+        "uniform" => {
+            arity(2, "uniform(lo, hi)")?;
+            if params[0] >= params[1] || !params.iter().all(|p| p.is_finite()) {
+                return Err(bad(&"lo must be below hi"));
+            }
+            Ok(Dist::Uniform(Uniform::new(params[0], params[1])))
+        }
+        "walk" => {
+            arity(2, "walk(start, sd)")?;
+            if params[1] < 0.0 {
+                return Err(bad(&"sd must not be negative"));
+            }
+            Normal::new(0.0, params[1])
+                .map(Dist::Normal)
+                .map_err(|e| bad(&e))
+        }
+        "seasonal" => {
+            arity(4, "seasonal(mean, amplitude, period, sd)")?;
+            if params[2] <= 0.0 {
+                return Err(bad(&"period must be positive"));
+            }
+            if params[3] < 0.0 {
+                return Err(bad(&"sd must not be negative"));
+            }
+            Normal::new(params[0], params[3])
+                .map(Dist::Normal)
+                .map_err(|e| bad(&e))
+        }
+        other => Err(format!(
+            "unknown distribution '{other}'; expected one of: {DISTRIBUTIONS}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::modifier::parse_modifier;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn spec(text: &str) -> Result<Option<NumericSpec>, String> {
+        numeric_spec(&parse_modifier(text).unwrap())
+    }
+
+    fn draws(spec: &NumericSpec, n: usize) -> Vec<f64> {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        spec.series((0..n).map(|_| spec.dist.sample(&mut rng)).collect())
+    }
+
+    #[test]
+    fn normal_draws_center_on_the_mean_and_respect_clamp() {
+        let mut s = spec("(normal(50, 10), clamp=0..60)").unwrap().unwrap();
+        s.scale = Some(0);
+        let values = draws(&s, 2000);
+        assert!(values
+            .iter()
+            .all(|v| (0.0..=60.0).contains(v) && v.fract() == 0.0));
+        let below_mean = values.iter().filter(|v| **v < 50.0).count();
+        assert!((850..1150).contains(&below_mean), "{below_mean}");
+        assert!(values.contains(&60.0));
+    }
+
+    #[test]
+    fn zipf_and_poisson_are_skewed_toward_small_values() {
+        let s = spec("(zipf(100, 1.2))").unwrap().unwrap();
+        let values = draws(&s, 2000);
+        assert!(values.iter().all(|v| (1.0..=100.0).contains(v)));
+        let ones = values.iter().filter(|v| **v == 1.0).count();
+        assert!(ones > 500, "{ones}");
+
+        let s = spec("(poisson(3))").unwrap().unwrap();
+        let mean = draws(&s, 2000).iter().sum::<f64>() / 2000.0;
+        assert!((2.7..3.3).contains(&mean), "{mean}");
+    }
+
+    #[test]
+    fn scale_rounds_draws() {
+        let mut s = spec("(uniform(0, 1))").unwrap().unwrap();
+        s.scale = Some(2);
+        for v in draws(&s, 100) {
+            assert_eq!(v, (v * 100.0).round() / 100.0);
+        }
+    }
+
+    #[test]
+    fn walks_move_in_small_steps_from_their_start() {
+        let mut s = spec("(walk(100, 1), clamp=0..200)").unwrap().unwrap();
+        s.scale = Some(2);
+        let values = draws(&s, 1000);
+        assert!((values[0] - 100.0).abs() < 5.0, "{}", values[0]);
+        assert!(values.windows(2).all(|w| (w[1] - w[0]).abs() < 5.0));
+        assert!(values.iter().all(|v| (0.0..=200.0).contains(v)));
+    }
+
+    #[test]
+    fn seasonal_values_follow_the_period() {
+        let s = spec("(seasonal(50, 20, 4, 0))").unwrap().unwrap();
+        let values = draws(&s, 8);
+        for (got, want) in values
+            .iter()
+            .zip([50.0, 70.0, 50.0, 30.0, 50.0, 70.0, 50.0, 30.0])
+        {
+            assert!((got - want).abs() < 1e-9, "{values:?}");
+        }
+    }
+
+    #[test]
+    fn non_calls_are_left_to_the_caller() {
+        assert!(spec("(0..500, scale=2)").unwrap().is_none());
+    }
+
+    #[test]
+    fn rejects_bad_distributions() {
+        for bad in [
+            "(gauss(1, 2))",
+            "(normal(1))",
+            "(normal(1, -2))",
+            "(normal(mean=1, sd=2))",
+            "(exponential(0))",
+            "(poisson(-1))",
+            "(zipf(0, 1))",
+            "(uniform(5, 1))",
+            "(walk(0))",
+            "(walk(0, -1))",
+            "(seasonal(50, 10, 0, 1))",
+            "(normal(1, 2), clamp=10..0)",
+            "(normal(1, 2), clamp='x')",
+        ] {
+            assert!(spec(bad).is_err(), "{bad} should fail");
+        }
+    }
+}
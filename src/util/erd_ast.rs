@@ -38,6 +38,9 @@ pub struct Attribute {
     pub name: String,
     pub data_type: String,
     pub key: Option<KeyKind>,
+    /// Mermaid attribute comment, read as a synthtab generator hint
+    /// (see `parser::attribute_schema`).
+    pub hint: Option<String>,
     pub line: usize,
 }
 
@@ -176,12 +179,14 @@ mod test {
                     name: "name".into(),
                     data_type: "string".into(),
                     key: None,
+                    hint: None,
                     line: 2,
                 },
                 Attribute {
                     name: "id".into(),
                     data_type: "int".into(),
                     key: Some(KeyKind::Pk),
+                    hint: None,
                     line: 3,
                 },
             ],
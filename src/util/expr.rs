@@ -0,0 +1,104 @@
+//! `EXPR` columns: values computed by a Polars expression over other columns
+//! of the same row.
+//!
+//! ```text
+//! total:EXPR(qty * unit_price)
+//! is_adult:EXPR(age >= 18)
+//! ```
+//!
+//! The expression is written in SQL syntax and compiled with
+//! `polars::sql::sql_expr`, so operators, `CASE WHEN`, `ROUND(x, 2)`,
+//! `UPPER(s)` and casts all work. The column's dtype is whatever the
+//! expression yields. Ordering against other columns is handled in `derived`.
+
+use crate::util::modifier::ModifierError;
+use crate::util::schema::Schema;
+use polars::prelude::*;
+use polars::sql::sql_expr;
+
+/// `EXPR(<sql expression>)`. The modifier is kept raw by `Schema::new`.
+pub(crate) fn expr_spec(element: &Schema) -> Result<Expr, ModifierError> {
+    let err = |msg: String| ModifierError {
+        message: format!("column '{}': EXPR modifier: {msg}", element.name),
+    };
+    let text = element
+        .modifier
+        .as_ref()
+        .map(|m| m.raw.as_str())
+        .filter(|raw| !raw.is_empty())
+        .ok_or_else(|| err("expected an expression like (qty * unit_price)".to_string()))?;
+    let expr = sql_expr(text).map_err(|e| err(format!("invalid expression '{text}': {e}")))?;
+    if references(&expr).is_empty() {
+        return Err(err(format!("'{text}' references no columns")));
+    }
+    Ok(expr)
+}
+
+/// Column names the expression reads.
+pub(crate) fn references(expr: &Expr) -> Vec<String> {
+    expr.clone()
+        .meta()
+        .root_names()
+        .iter()
+        .map(|n| n.to_string())
+        .collect()
+}
+
+/// Evaluate `expr` against `frame` as a `with_columns` pass, returning the
+/// new column named `name`.
+pub(crate) fn evaluate(expr: &Expr, name: &str, frame: &DataFrame) -> PolarsResult<Series> {
+    let out = frame
+        .clone()
+        .lazy()
+        .with_columns([expr.clone().alias(name)])
+        .collect()?;
+    Ok(out.column(name)?.clone())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn expr_column(name: &str, text: &str) -> Schema {
+        Schema::new(name, "EXPR", Some(text)).unwrap()
+    }
+
+    fn frame() -> DataFrame {
+        DataFrame::new(vec![
+            Series::new("qty", vec![2i32, 3]),
+            Series::new("unit_price", vec![1.5f64, 10.0]),
+            Series::new("age", vec![17i32, 30]),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn arithmetic_takes_the_expression_dtype() {
+        let expr = expr_spec(&expr_column("total", "(qty * unit_price)")).unwrap();
+        assert_eq!(references(&expr), vec!["qty", "unit_price"]);
+        let total = evaluate(&expr, "total", &frame()).unwrap();
+        assert_eq!(total.dtype(), &DataType::Float64);
+        let total: Vec<Option<f64>> = total.f64().unwrap().into_iter().collect();
+        assert_eq!(total, vec![Some(3.0), Some(30.0)]);
+    }
+
+    #[test]
+    fn comparisons_yield_booleans() {
+        let expr = expr_spec(&expr_column("is_adult", "(age >= 18)")).unwrap();
+        let adult = evaluate(&expr, "is_adult", &frame()).unwrap();
+        let adult: Vec<Option<bool>> = adult.bool().unwrap().into_iter().collect();
+        assert_eq!(adult, vec![Some(false), Some(true)]);
+    }
+
+    #[test]
+    fn rejects_bad_expressions() {
+        for bad in ["()", "(qty *)", "(1 + 2)"] {
+            let err = expr_spec(&expr_column("x", bad)).unwrap_err();
+            assert!(
+                err.message.starts_with("column 'x': EXPR modifier:"),
+                "{bad}: {}",
+                err.message
+            );
+        }
+    }
+}
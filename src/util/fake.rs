@@ -1,36 +1,67 @@
+use crate::util::distribution::{numeric_spec, NumericSpec};
+use crate::util::locale::{localized, Locale};
+use crate::util::modifier::{Modifier, ModifierError, Value};
+use crate::util::rng::{CellRng, SynthRng};
 use crate::util::schema::Schema;
+use crate::util::temporal::{date_spec, date_time_spec, default_date_spec, default_date_time_spec};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use fake::faker::address::raw::*;
 use fake::faker::chrono::raw::*;
+use fake::faker::company::raw::{CompanyName, Industry};
+use fake::faker::currency::raw::CurrencyCode;
+use fake::faker::finance::raw::Bic;
+use fake::faker::internet::raw::*;
+use fake::faker::job::raw as job;
 use fake::faker::lorem::raw::*;
 use fake::faker::name::raw::*;
 use fake::faker::number::raw::*;
 use fake::faker::phone_number::raw::*;
 use fake::locales::*;
+use fake::uuid::UUIDv4;
 use fake::{Fake, Faker};
-use fakeit::currency;
-use fakeit::name;
-use fakeit::person;
+use polars::chunked_array::builder::get_list_builder;
 use polars::prelude::*;
-use regex::Regex;
-use std::error::Error;
-use std::sync::OnceLock;
-use uuid::Uuid;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::Rng;
+use regex_syntax::hir::{Class, ClassUnicode, ClassUnicodeRange, Group, Hir, HirKind, Repetition};
+use std::net::{Ipv4Addr, Ipv6Addr};
 
-type RangeParseResult = Result<(i32, i32), Box<dyn Error>>;
+type ColumnResult = Result<Series, ModifierError>;
 
-fn range_regex() -> &'static Regex {
-    static RE: OnceLock<Regex> = OnceLock::new();
-    RE.get_or_init(|| Regex::new(r"\((-?\d+)\s*-\s*(-?\d+)\)").unwrap())
-}
-
-fn build_data_vector<T>(size: usize, generator: impl Fn() -> T) -> Vec<T> {
+fn build_data_vector<T, C: CellRng>(
+    size: usize,
+    rng: &mut C,
+    generator: impl Fn(&mut SynthRng) -> T,
+) -> Vec<T> {
     let mut data: Vec<T> = Vec::with_capacity(size);
-    for _ in 0..size {
-        data.push(generator());
+    for row in 0..size {
+        data.push(rng.cell(row, |r| generator(r)));
     }
     data
 }
 
+/// `size` values of `spec`, one draw per row, shaped by its pattern.
+fn numeric_vector<C: CellRng>(spec: &NumericSpec, size: usize, rng: &mut C) -> Vec<f64> {
+    spec.series(build_data_vector(size, rng, |r| spec.dist.sample(r)))
+}
+
+/// Null out about `ratio` of the cells in `series`, one draw per row.
+pub fn apply_null_ratio<C: CellRng>(
+    series: Series,
+    ratio: f64,
+    rng: &mut C,
+) -> PolarsResult<Series> {
+    if ratio <= 0.0 {
+        return Ok(series);
+    }
+    let keep: Vec<bool> = (0..series.len())
+        .map(|row| rng.cell(row, |r| !r.gen_bool(ratio)))
+        .collect();
+    let keep = BooleanChunked::from_slice("keep", &keep);
+    let nulls = Series::full_null(series.name(), series.len(), series.dtype());
+    series.zip_with(&keep, &nulls)
+}
+
 pub fn build_incremental_int(size: i32, start: i32, end: i32) -> Vec<i32> {
     let end = if start - end < 0 { start + size } else { end };
     (start..end).collect::<Vec<i32>>()
@@ -46,178 +77,1276 @@ pub(crate) fn capitalize_first(s: &str) -> String {
     }
 }
 
-fn parse_range_string(range_str: &str) -> RangeParseResult {
-    if let Some(caps) = range_regex().captures(range_str) {
-        let lower: i32 = caps.get(1).unwrap().as_str().parse()?;
-        let upper: i32 = caps.get(2).unwrap().as_str().parse()?;
-        Ok((lower, upper))
-    } else {
-        Err(format!("Error parsing {}", range_str).into())
+fn modifier_error(element: &Schema, message: impl std::fmt::Display) -> ModifierError {
+    ModifierError {
+        message: format!(
+            "column '{}': {} modifier: {message}",
+            element.name, element.datatype
+        ),
+    }
+}
+
+/// `INT_RNG(lo-hi)` / `INT_RNG(lo..hi)`. A missing modifier still falls back
+/// to `(0-rows)` with a warning; a malformed one is an error.
+fn int_rng_bounds(element: &Schema, size: usize) -> Result<(i32, i32), ModifierError> {
+    let Some(m) = &element.modifier else {
+        eprintln!(
+            "INT_RNG column '{}' has no (lo-hi) modifier; using default range",
+            element.name
+        );
+        return Ok((0, size as i32));
+    };
+    let err = |msg: &str| modifier_error(element, msg);
+    m.check_options(&[]).map_err(|e| err(&e))?;
+    m.check_positional(1).map_err(|e| err(&e))?;
+    let (lo, hi) = m
+        .positional()
+        .first()
+        .and_then(|v| v.as_range())
+        .ok_or_else(|| {
+            err(&format!(
+                "expected an integer range like (0-100), got '({})'",
+                m.raw
+            ))
+        })?;
+    let bound = |v: &Value| {
+        v.as_i64()
+            .and_then(|i| i32::try_from(i).ok())
+            .ok_or_else(|| err(&format!("range bound '{v}' is not a 32-bit integer")))
+    };
+    Ok((bound(lo)?, bound(hi)?))
+}
+
+/// `INT(lo..hi)` / `BIGINT(lo..hi)` with inclusive bounds, or a distribution
+/// such as `INT(normal(50, 10), clamp=0..100)`. Draws are kept inside the
+/// type's own range.
+enum IntSpec {
+    Range(i64, i64),
+    Dist(NumericSpec, i64, i64),
+}
+
+impl IntSpec {
+    fn column<C: CellRng>(&self, size: usize, rng: &mut C) -> Vec<i64> {
+        match self {
+            IntSpec::Range(lo, hi) => build_data_vector(size, rng, |r| r.gen_range(*lo..=*hi)),
+            IntSpec::Dist(spec, min, max) => numeric_vector(spec, size, rng)
+                .into_iter()
+                .map(|v| (v as i64).clamp(*min, *max))
+                .collect(),
+        }
+    }
+}
+
+fn int_spec(element: &Schema, m: &Modifier, min: i64, max: i64) -> Result<IntSpec, ModifierError> {
+    if matches!(m.positional().first(), Some(Value::Call(..))) {
+        let spec = distribution_spec(element, m, &["clamp"], Some(0))?;
+        return Ok(IntSpec::Dist(spec, min, max));
+    }
+    let err = |msg: &str| modifier_error(element, msg);
+    m.check_options(&[]).map_err(|e| err(&e))?;
+    m.check_positional(1).map_err(|e| err(&e))?;
+    let (lo, hi) = m
+        .positional()
+        .first()
+        .and_then(|v| v.as_range())
+        .ok_or_else(|| {
+            err(&format!(
+                "expected a range like (1..100) or a distribution like (normal(50, 10)), got '({})'",
+                m.raw
+            ))
+        })?;
+    let bound = |v: &Value| {
+        v.as_i64()
+            .filter(|i| (min..=max).contains(i))
+            .ok_or_else(|| {
+                err(&format!(
+                    "range bound '{v}' is not an integer in {min}..{max}"
+                ))
+            })
+    };
+    let (lo, hi) = (bound(lo)?, bound(hi)?);
+    if lo > hi {
+        return Err(err(&format!(
+            "empty range {lo}..{hi}; lower bound must not exceed upper"
+        )));
+    }
+    Ok(IntSpec::Range(lo, hi))
+}
+
+/// `DECIMAL(lo..hi, scale=n)`; both parts are optional.
+struct DecimalSpec {
+    lower: f64,
+    upper: f64,
+    scale: Option<i32>,
+}
+
+fn decimal_spec(element: &Schema, m: &Modifier) -> Result<DecimalSpec, ModifierError> {
+    let err = |msg: &str| modifier_error(element, msg);
+    m.check_options(&["scale", "precision"])
+        .map_err(|e| err(&e))?;
+    m.check_positional(1).map_err(|e| err(&e))?;
+    let digits = decimal_digits(element)?;
+    let bound = digits.map(|(p, s)| decimal_max(p, s));
+    let (lower, upper) = match m.positional().first() {
+        None => (0.0, bound.unwrap_or(100000.0)),
+        Some(v) => {
+            let (lo, hi) = v
+                .as_range()
+                .and_then(|(lo, hi)| Some((lo.as_f64()?, hi.as_f64()?)))
+                .ok_or_else(|| err(&format!("expected a numeric range like 0..500, got '{v}'")))?;
+            if lo >= hi {
+                return Err(err(&format!(
+                    "empty range {v}; lower bound must be below upper"
+                )));
+            }
+            // Draws below `hi` round to at most `hi`, so `hi` itself must fit.
+            if let (Some(bound), Some((p, s))) = (bound, digits) {
+                if lo < -bound || hi > bound {
+                    return Err(err(&format!(
+                        "range {v} does not fit {p} digits with {s} decimal places"
+                    )));
+                }
+            }
+            (lo, hi)
+        }
+    };
+    Ok(DecimalSpec {
+        lower,
+        upper,
+        scale: scale_option(element, m)?,
+    })
+}
+
+/// The `scale=n` option: decimal places to round to, at most 9, or at most
+/// the precision when `precision=` is set.
+fn scale_option(element: &Schema, m: &Modifier) -> Result<Option<i32>, ModifierError> {
+    let max = match m.option("precision") {
+        Some(_) => 38,
+        None => 9,
+    };
+    match m.option("scale") {
+        None => Ok(None),
+        Some(v) => v
+            .as_i64()
+            .filter(|s| (0..=max).contains(s))
+            .map(|s| Some(s as i32))
+            .ok_or_else(|| {
+                modifier_error(
+                    element,
+                    format!("scale must be an integer in 0..{max}, got '{v}'"),
+                )
+            }),
+    }
+}
+
+/// The largest magnitude `DECIMAL(p,s)` holds: `p` nines, `s` of them
+/// after the point.
+fn decimal_max(precision: usize, scale: usize) -> f64 {
+    (10f64.powi(precision as i32) - 1.0) / 10f64.powi(scale as i32)
+}
+
+/// A distribution has no upper end, so one written as an exact
+/// `DECIMAL(p,s)` needs a `clamp=` range that fits it.
+fn check_clamp_fits(
+    element: &Schema,
+    spec: &NumericSpec,
+    (precision, scale): (usize, usize),
+) -> Result<(), ModifierError> {
+    let max = decimal_max(precision, scale);
+    match spec.clamp {
+        Some((lo, hi)) if lo >= -max && hi <= max => Ok(()),
+        Some((lo, hi)) => Err(modifier_error(
+            element,
+            format!("clamp={lo}..{hi} does not fit {precision} digits with {scale} decimal places"),
+        )),
+        None => Err(modifier_error(
+            element,
+            format!("precision={precision} needs a clamp= range within -{max}..{max} so every draw fits"),
+        )),
+    }
+}
+
+/// `DECIMAL(precision=12, scale=4)`: the total digits and decimal places of
+/// an exact decimal column, when `precision=` is set. Scale defaults to 0
+/// and may not exceed the precision.
+pub(crate) fn decimal_digits(element: &Schema) -> Result<Option<(usize, usize)>, ModifierError> {
+    let Some(m) = &element.modifier else {
+        return Ok(None);
+    };
+    let Some(v) = m.option("precision") else {
+        return Ok(None);
+    };
+    let precision = v.as_i64().filter(|p| (1..=38).contains(p)).ok_or_else(|| {
+        modifier_error(
+            element,
+            format!("precision must be an integer in 1..38, got '{v}'"),
+        )
+    })?;
+    let scale = scale_option(element, m)?.unwrap_or(0) as i64;
+    if scale > precision {
+        return Err(modifier_error(
+            element,
+            format!("scale={scale} exceeds precision={precision}"),
+        ));
+    }
+    Ok(Some((precision as usize, scale as usize)))
+}
+
+/// `BOOLEAN(0.8)`: the share of cells that are `true`; half when omitted.
+fn true_ratio(element: &Schema) -> Result<f64, ModifierError> {
+    let Some(m) = &element.modifier else {
+        return Ok(0.5);
+    };
+    let err = |msg: &str| modifier_error(element, msg);
+    m.check_options(&[]).map_err(|e| err(&e))?;
+    m.check_positional(1).map_err(|e| err(&e))?;
+    m.positional()
+        .first()
+        .and_then(|v| v.as_f64())
+        .filter(|r| (0.0..=1.0).contains(r))
+        .ok_or_else(|| {
+            err(&format!(
+                "expected a true ratio between 0 and 1 like (0.8), got '({})'",
+                m.raw
+            ))
+        })
+}
+
+/// `LIST<TYPE>(lo..hi)`: how many items each row holds, both bounds
+/// inclusive; `0..5` when omitted.
+fn list_length(element: &Schema) -> Result<(usize, usize), ModifierError> {
+    let Some(m) = &element.modifier else {
+        return Ok((0, 5));
+    };
+    let err = |msg: &str| modifier_error(element, msg);
+    m.check_options(&[]).map_err(|e| err(&e))?;
+    m.check_positional(1).map_err(|e| err(&e))?;
+    m.positional()
+        .first()
+        .and_then(|v| v.as_range())
+        .and_then(|(lo, hi)| Some((lo.as_i64()?, hi.as_i64()?)))
+        .filter(|(lo, hi)| 0 <= *lo && lo <= hi && *hi <= 10_000)
+        .map(|(lo, hi)| (lo as usize, hi as usize))
+        .ok_or_else(|| {
+            err(&format!(
+                "expected an item count range like (0..5) within 0..10000, got '({})'",
+                m.raw
+            ))
+        })
+}
+
+/// One element type of a `LIST` or `STRUCT`, with its own nulls. Items pick
+/// up the locale of the column they sit in.
+fn nested_column<C: CellRng>(
+    parent: &Schema,
+    child: &Schema,
+    size: usize,
+    rng: &mut C,
+    now: NaiveDateTime,
+) -> ColumnResult {
+    let mut child = child.clone();
+    child.locale = child.locale.or(parent.locale);
+    let null_ratio = child.null_ratio;
+    let col = create_column(child, size, &mut rng.part("\0values"), now)?;
+    match null_ratio {
+        Some(ratio) => apply_null_ratio(col, ratio, &mut rng.part("\0nulls")).map_err(|e| {
+            modifier_error(
+                parent,
+                format!("failed to apply nulls to nested values: {e}"),
+            )
+        }),
+        None => Ok(col),
+    }
+}
+
+/// `INT_INC(start=1000, step=10)` or `INT_INC(prefix='ORD-', width=6)`: an
+/// arithmetic sequence, written as text when it has a prefix or a width.
+pub(crate) struct SequenceSpec {
+    start: i64,
+    step: i64,
+    /// `(prefix, width)`: numbers are zero-padded to `width` digits.
+    pub format: Option<(String, usize)>,
+}
+
+impl SequenceSpec {
+    /// The value of row `row`, wide enough that overflow can be checked.
+    fn nth(&self, row: usize) -> i128 {
+        i128::from(self.start) + i128::from(self.step) * row as i128
+    }
+
+    fn text(&self, row: usize) -> String {
+        let (prefix, width) = self.format.as_ref().expect("formatted sequence");
+        format!("{prefix}{:0width$}", self.nth(row))
+    }
+}
+
+/// `element`'s sequence, failing when numbering `size` rows would run past
+/// the largest value its type holds.
+pub(crate) fn sized_sequence(element: &Schema, size: usize) -> Result<SequenceSpec, ModifierError> {
+    let spec = sequence_spec(element)?;
+    let last = spec.nth(size.saturating_sub(1));
+    let fits = match element.datatype.as_str() {
+        "INT_INC" => i32::try_from(last).is_ok(),
+        _ => i64::try_from(last).is_ok(),
+    };
+    if !fits {
+        let hint = match element.datatype.as_str() {
+            "INT_INC" => "; use BIGINT_INC",
+            _ => "",
+        };
+        return Err(ModifierError {
+            message: format!(
+                "column '{}': {} cannot number {size} rows from start={} step={}{hint}",
+                element.name, element.datatype, spec.start, spec.step
+            ),
+        });
+    }
+    Ok(spec)
+}
+
+pub(crate) fn sequence_spec(element: &Schema) -> Result<SequenceSpec, ModifierError> {
+    let mut spec = SequenceSpec {
+        start: 0,
+        step: 1,
+        format: None,
+    };
+    let Some(m) = &element.modifier else {
+        return Ok(spec);
+    };
+    let err = |msg: &str| modifier_error(element, msg);
+    m.check_options(&["start", "step", "prefix", "width"])
+        .map_err(|e| err(&e))?;
+    m.check_positional(0).map_err(|e| err(&e))?;
+    if let Some(v) = m.option("start") {
+        spec.start = v
+            .as_i64()
+            .ok_or_else(|| err(&format!("start must be a whole number, got '{v}'")))?;
+    }
+    if let Some(v) = m.option("step") {
+        spec.step = v
+            .as_i64()
+            .filter(|s| *s != 0)
+            .ok_or_else(|| err(&format!("step must be a non-zero whole number, got '{v}'")))?;
+    }
+    let prefix = match m.option("prefix") {
+        Some(Value::Str(p)) => Some(p.clone()),
+        Some(v) => return Err(err(&format!("prefix must be a quoted string, got '{v}'"))),
+        None => None,
+    };
+    let width = match m.option("width") {
+        Some(v) => Some(
+            v.as_i64()
+                .filter(|w| (1..=20).contains(w))
+                .ok_or_else(|| err(&format!("width must be between 1 and 20, got '{v}'")))?
+                as usize,
+        ),
+        None => None,
+    };
+    if prefix.is_some() || width.is_some() {
+        spec.format = Some((prefix.unwrap_or_default(), width.unwrap_or(0)));
+    }
+    Ok(spec)
+}
+
+/// A distribution modifier such as `INT(normal(50, 10), clamp=0..100)`,
+/// taking `options` and rounding every draw to `scale` places.
+fn distribution_spec(
+    element: &Schema,
+    m: &Modifier,
+    options: &[&str],
+    scale: Option<i32>,
+) -> Result<NumericSpec, ModifierError> {
+    let err = |msg: &str| modifier_error(element, msg);
+    m.check_options(options).map_err(|e| err(&e))?;
+    m.check_positional(1).map_err(|e| err(&e))?;
+    let mut spec = numeric_spec(m).map_err(|e| err(&e))?.ok_or_else(|| {
+        err(&format!(
+            "expected a distribution like (normal(50, 10)), got '({})'",
+            m.raw
+        ))
+    })?;
+    spec.scale = scale;
+    Ok(spec)
+}
+
+/// `ENUM('a', 'b')` or `ENUM('a'=70, 'b'=30)`: the choices and their
+/// relative weights. An unweighted list draws every value equally often.
+pub(crate) struct EnumSpec {
+    pub values: Vec<String>,
+    pub weights: Vec<f64>,
+}
+
+pub(crate) fn enum_spec(element: &Schema) -> Result<EnumSpec, ModifierError> {
+    let err = |msg: &str| modifier_error(element, msg);
+    let Some(m) = &element.modifier else {
+        return Err(err(
+            "expected a value list like ('a', 'b') or ('a'=70, 'b'=30)",
+        ));
+    };
+    m.check_options(&[]).map_err(|e| err(&e))?;
+    let choices = m.positional();
+    if choices.is_empty() {
+        return Err(err("expected at least one value"));
+    }
+    let weighted = choices
+        .iter()
+        .filter(|v| matches!(v, Value::Weighted(..)))
+        .count();
+    if weighted != 0 && weighted != choices.len() {
+        return Err(err("give every value a weight, or none of them"));
+    }
+
+    let mut spec = EnumSpec {
+        values: Vec::with_capacity(choices.len()),
+        weights: Vec::with_capacity(choices.len()),
+    };
+    for choice in choices {
+        let (value, weight) = match choice {
+            Value::Weighted(v, w) => {
+                let weight = w
+                    .as_f64()
+                    .filter(|w| w.is_finite() && *w > 0.0)
+                    .ok_or_else(|| {
+                        err(&format!(
+                            "weight for {v} must be a positive number, got '{w}'"
+                        ))
+                    })?;
+                (v.as_ref(), weight)
+            }
+            v => (v, 1.0),
+        };
+        let value = match value {
+            Value::Str(s) | Value::Ident(s) => s.clone(),
+            Value::Int(_) | Value::Float(_) => value.to_string(),
+            other => return Err(err(&format!("expected a plain value, got '{other}'"))),
+        };
+        if spec.values.contains(&value) {
+            return Err(err(&format!("value '{value}' is listed more than once")));
+        }
+        spec.values.push(value);
+        spec.weights.push(weight);
+    }
+    Ok(spec)
+}
+
+/// `*`, `+` and `{n,}` in a `PATTERN` repeat at most this many extra times.
+const PATTERN_MAX_REPEAT: u32 = 10;
+
+/// `PATTERN('[A-Z]{3}-\d{6}')`: one quoted regex, compiled for sampling.
+pub(crate) fn pattern_spec(element: &Schema) -> Result<rand_regex::Regex, ModifierError> {
+    let err = |msg: &str| modifier_error(element, msg);
+    let Some(m) = &element.modifier else {
+        return Err(err("expected a quoted regex like ('[A-Z]{3}-\\d{6}')"));
+    };
+    m.check_options(&[]).map_err(|e| err(&e))?;
+    m.check_positional(1).map_err(|e| err(&e))?;
+    let pattern = m
+        .positional()
+        .first()
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| err(&format!("expected a quoted regex, got '({})'", m.raw)))?;
+    let hir = regex_syntax::Parser::new()
+        .parse(pattern)
+        .map_err(|e| err(&format!("invalid regex '{pattern}': {e}")))?;
+    let regex = rand_regex::Regex::with_hir(ascii_classes(hir), PATTERN_MAX_REPEAT)
+        .map_err(|e| err(&format!("invalid regex '{pattern}': {e}")))?;
+    if !regex.is_utf8() {
+        return Err(err(&format!(
+            "regex '{pattern}' can produce bytes that are not valid UTF-8"
+        )));
+    }
+    Ok(regex)
+}
+
+/// Narrow each character class in `hir` to printable ASCII where it has any,
+/// so `\d`, `\w` and `.` yield `0-9`, word characters and plain text rather
+/// than arbitrary scripts. Classes with no ASCII member (`[à-ÿ]`) are kept.
+fn ascii_classes(hir: Hir) -> Hir {
+    match hir.into_kind() {
+        HirKind::Class(Class::Unicode(class)) => {
+            let mut ascii = class.clone();
+            ascii.intersect(&ClassUnicode::new([ClassUnicodeRange::new(' ', '~')]));
+            let narrowed = if ascii.ranges().is_empty() {
+                class
+            } else {
+                ascii
+            };
+            Hir::class(Class::Unicode(narrowed))
+        }
+        HirKind::Repetition(rep) => Hir::repetition(Repetition {
+            hir: Box::new(ascii_classes(*rep.hir)),
+            ..rep
+        }),
+        HirKind::Group(group) => Hir::group(Group {
+            hir: Box::new(ascii_classes(*group.hir)),
+            ..group
+        }),
+        HirKind::Concat(hirs) => Hir::concat(hirs.into_iter().map(ascii_classes).collect()),
+        HirKind::Alternation(hirs) => {
+            Hir::alternation(hirs.into_iter().map(ascii_classes).collect())
+        }
+        HirKind::Class(class) => Hir::class(class),
+        HirKind::Literal(lit) => Hir::literal(lit),
+        HirKind::Anchor(anchor) => Hir::anchor(anchor),
+        HirKind::WordBoundary(wb) => Hir::word_boundary(wb),
+        HirKind::Empty => Hir::empty(),
+    }
+}
+
+/// A card network for `CREDIT_CARD`: its issuer prefixes and number length.
+pub(crate) struct CardBrand {
+    name: &'static str,
+    prefixes: &'static [&'static str],
+    length: usize,
+}
+
+const CARD_BRANDS: &[CardBrand] = &[
+    CardBrand {
+        name: "visa",
+        prefixes: &["4"],
+        length: 16,
+    },
+    CardBrand {
+        name: "mastercard",
+        prefixes: &["51", "52", "53", "54", "55"],
+        length: 16,
+    },
+    CardBrand {
+        name: "amex",
+        prefixes: &["34", "37"],
+        length: 15,
+    },
+    CardBrand {
+        name: "discover",
+        prefixes: &["6011", "65"],
+        length: 16,
+    },
+];
+
+/// A country's BBAN layout for `IBAN`, as runs of `n` digits, `a` upper-case
+/// letters or `c` either.
+pub(crate) struct IbanCountry {
+    code: &'static str,
+    layout: &'static [(char, usize)],
+}
+
+const IBAN_COUNTRIES: &[IbanCountry] = &[
+    IbanCountry {
+        code: "DE",
+        layout: &[('n', 18)],
+    },
+    IbanCountry {
+        code: "GB",
+        layout: &[('a', 4), ('n', 14)],
+    },
+    IbanCountry {
+        code: "FR",
+        layout: &[('n', 10), ('c', 11), ('n', 2)],
+    },
+    IbanCountry {
+        code: "NL",
+        layout: &[('a', 4), ('n', 10)],
+    },
+    IbanCountry {
+        code: "ES",
+        layout: &[('n', 20)],
+    },
+    IbanCountry {
+        code: "IT",
+        layout: &[('a', 1), ('n', 10), ('c', 12)],
+    },
+];
+
+/// `CREDIT_CARD('visa')` / `IBAN('DE')`: the entries of `table` a column may
+/// draw from, all of them when there is no modifier. `key` names an entry
+/// and is matched case-insensitively.
+fn table_choice<'a, T>(
+    element: &Schema,
+    table: &'a [T],
+    key: impl Fn(&T) -> &str,
+) -> Result<Vec<&'a T>, ModifierError> {
+    let Some(m) = &element.modifier else {
+        return Ok(table.iter().collect());
+    };
+    let err = |msg: &str| modifier_error(element, msg);
+    m.check_options(&[]).map_err(|e| err(&e))?;
+    m.check_positional(1).map_err(|e| err(&e))?;
+    let names: Vec<&str> = table.iter().map(&key).collect();
+    let expected = || format!("expected one of {}, got '({})'", names.join(", "), m.raw);
+    let wanted = match m.positional().first() {
+        Some(Value::Str(s) | Value::Ident(s)) => s,
+        _ => return Err(err(&expected())),
+    };
+    table
+        .iter()
+        .find(|t| key(t).eq_ignore_ascii_case(wanted))
+        .map(|t| vec![t])
+        .ok_or_else(|| err(&expected()))
+}
+
+/// Longest `STRING` in characters, or `BINARY`, `HEX` or `BASE64` value in
+/// bytes.
+const MAX_LENGTH: i64 = 1 << 20;
+
+/// `(16)` or `(8..32)`: an exact length or an inclusive range of them, read
+/// from the only argument of `m`. `unit` names what is counted in errors.
+fn length_range(
+    element: &Schema,
+    m: &Modifier,
+    unit: &str,
+) -> Result<(usize, usize), ModifierError> {
+    let err = |msg: &str| modifier_error(element, msg);
+    m.check_options(&[]).map_err(|e| err(&e))?;
+    m.check_positional(1).map_err(|e| err(&e))?;
+    m.positional()
+        .first()
+        .and_then(|v| match v.as_range() {
+            Some((lo, hi)) => Some((lo.as_i64()?, hi.as_i64()?)),
+            None => v.as_i64().map(|n| (n, n)),
+        })
+        .filter(|(lo, hi)| 0 <= *lo && lo <= hi && (1..=MAX_LENGTH).contains(hi))
+        .map(|(lo, hi)| (lo as usize, hi as usize))
+        .ok_or_else(|| {
+            err(&format!(
+                "expected a {unit} count like (16) or (8..32) within 1..{MAX_LENGTH}, got '({})'",
+                m.raw
+            ))
+        })
+}
+
+/// `STRING(8)` / `STRING(1..40)`: how many characters each value holds.
+/// `None` without a modifier, where lengths follow `fake_string`.
+pub(crate) fn string_length(element: &Schema) -> Result<Option<(usize, usize)>, ModifierError> {
+    element
+        .modifier
+        .as_ref()
+        .map(|m| length_range(element, m, "character"))
+        .transpose()
+}
+
+/// `BINARY(16)` / `HEX(8..32)`: how many random bytes each value holds, an
+/// exact count or an inclusive range. Without a modifier `BINARY` holds 16
+/// bytes and `HEX` and `BASE64` encode 32, the size of a SHA-256 digest.
+pub(crate) fn byte_length(element: &Schema) -> Result<(usize, usize), ModifierError> {
+    match &element.modifier {
+        Some(m) => length_range(element, m, "byte"),
+        None if element.datatype == "BINARY" => Ok((16, 16)),
+        None => Ok((32, 32)),
+    }
+}
+
+/// `TOKEN('sk_live_', 24)`: a literal prefix and how many random characters
+/// follow it, both optional (`TOKEN('ghp_')`, `TOKEN(40)`). The default is
+/// 32 characters with no prefix.
+pub(crate) fn token_spec(element: &Schema) -> Result<(String, usize), ModifierError> {
+    let Some(m) = &element.modifier else {
+        return Ok((String::new(), 32));
+    };
+    let err = |msg: &str| modifier_error(element, msg);
+    m.check_options(&[]).map_err(|e| err(&e))?;
+    m.check_positional(2).map_err(|e| err(&e))?;
+    let expected = || {
+        err(&format!(
+            "expected ('prefix', length) with a length within 1..1024, got '({})'",
+            m.raw
+        ))
+    };
+    let (prefix, length) = match m.positional().as_slice() {
+        [Value::Str(prefix)] => (prefix.clone(), 32),
+        [Value::Int(n)] => (String::new(), *n),
+        [Value::Str(prefix), Value::Int(n)] => (prefix.clone(), *n),
+        _ => return Err(expected()),
+    };
+    if !(1..=1024).contains(&length) {
+        return Err(expected());
     }
+    Ok((prefix, length as usize))
 }
 
-pub fn create_column(element: Schema, size: usize) -> Series {
+/// Every column type synthtab generates: the ones `create_column` handles
+/// plus the derived `TEMPLATE` and `EXPR`. Anything else is unknown.
+pub const TYPES: &[&str] = &[
+    "STRING",
+    "INT",
+    "BIGINT",
+    "INT_INC",
+    "BIGINT_INC",
+    "INT_RNG",
+    "VALUE",
+    "DIGIT",
+    "BOOLEAN",
+    "DECIMAL",
+    "ENUM",
+    "PATTERN",
+    "DATE",
+    "TIME",
+    "DATE_TIME",
+    "NAME",
+    "ZIP_CODE",
+    "COUNTRY_CODE",
+    "STATE_NAME",
+    "STATE_ABBR",
+    "LAT",
+    "LON",
+    "PHONE",
+    "PRICE",
+    "LOREM_WORD",
+    "LOREM_TITLE",
+    "LOREM_SENTENCE",
+    "LOREM_PARAGRAPH",
+    "UUID",
+    "LIST",
+    "STRUCT",
+    "FIRST_NAME",
+    "LAST_NAME",
+    "SSN",
+    "EMAIL",
+    "URL",
+    "DOMAIN",
+    "IPV4",
+    "IPV6",
+    "MAC_ADDRESS",
+    "USER_AGENT",
+    "COMPANY",
+    "JOB_TITLE",
+    "INDUSTRY",
+    "CREDIT_CARD",
+    "IBAN",
+    "BIC",
+    "CURRENCY_CODE",
+    "BINARY",
+    "HEX",
+    "BASE64",
+    "TOKEN",
+    "TEMPLATE",
+    "EXPR",
+];
+
+/// Build one column of `size` values for `element`. Every random draw comes
+/// from `rng`, so a seeded generator yields identical columns across runs;
+/// a `KeyedRng` additionally pins each row's value to its index. `now` and
+/// `today` bounds resolve against `now`.
+///
+/// Errors when the column carries a modifier its type does not understand.
+pub fn create_column<C: CellRng>(
+    element: Schema,
+    size: usize,
+    rng: &mut C,
+    now: NaiveDateTime,
+) -> ColumnResult {
+    if let Some(m) = &element.modifier {
+        if !matches!(
+            element.datatype.as_str(),
+            "STRING"
+                | "INT"
+                | "BIGINT"
+                | "INT_INC"
+                | "BIGINT_INC"
+                | "INT_RNG"
+                | "DECIMAL"
+                | "PRICE"
+                | "DATE"
+                | "DATE_TIME"
+                | "BOOLEAN"
+                | "ENUM"
+                | "PATTERN"
+                | "CREDIT_CARD"
+                | "IBAN"
+                | "LIST"
+                | "BINARY"
+                | "HEX"
+                | "BASE64"
+                | "TOKEN"
+        ) {
+            return Err(modifier_error(
+                &element,
+                format!("this type takes no modifier, got '({})'", m.raw),
+            ));
+        }
+    }
+    let locale = element.locale.unwrap_or_default();
     let col = match element.datatype.as_str() {
-        "STRING" => Series::new(element.name.as_str(), build_data_vector(size, fake_string)),
-        "INT" => Series::new(element.name.as_str(), build_data_vector(size, fake_int)),
-        "INT_INC" => Series::new(
+        "STRING" => match string_length(&element)? {
+            Some((lo, hi)) => Series::new(
+                element.name.as_str(),
+                build_data_vector(size, rng, |r| fake_string_between(r, lo, hi)),
+            ),
+            None => Series::new(
+                element.name.as_str(),
+                build_data_vector(size, rng, fake_string),
+            ),
+        },
+        "INT" => match &element.modifier {
+            Some(m) => {
+                let spec = int_spec(&element, m, i32::MIN.into(), i32::MAX.into())?;
+                Series::new(
+                    element.name.as_str(),
+                    spec.column(size, rng)
+                        .into_iter()
+                        .map(|v| v as i32)
+                        .collect::<Vec<i32>>(),
+                )
+            }
+            None => Series::new(
+                element.name.as_str(),
+                build_data_vector(size, rng, fake_int),
+            ),
+        },
+        "BIGINT" => match &element.modifier {
+            Some(m) => {
+                let spec = int_spec(&element, m, i64::MIN, i64::MAX)?;
+                Series::new(element.name.as_str(), spec.column(size, rng))
+            }
+            None => Series::new(
+                element.name.as_str(),
+                build_data_vector(size, rng, fake_bigint),
+            ),
+        },
+        "INT_INC" | "BIGINT_INC" => {
+            let spec = sized_sequence(&element, size)?;
+            let name = element.name.as_str();
+            match (&spec.format, element.datatype.as_str()) {
+                (Some(_), _) => Series::new(
+                    name,
+                    (0..size).map(|i| spec.text(i)).collect::<Vec<String>>(),
+                ),
+                (None, "INT_INC") => Series::new(
+                    name,
+                    (0..size).map(|i| spec.nth(i) as i32).collect::<Vec<i32>>(),
+                ),
+                (None, _) => Series::new(
+                    name,
+                    (0..size).map(|i| spec.nth(i) as i64).collect::<Vec<i64>>(),
+                ),
+            }
+        }
+        "INT_RNG" => {
+            let (lower, upper) = int_rng_bounds(&element, size)?;
+
+            Series::new(
+                element.name.as_str(),
+                build_incremental_int(size as i32, lower, upper),
+            )
+        }
+        "VALUE" => Series::new(
             element.name.as_str(),
-            build_incremental_int(size as i32, 0, size as i32),
+            build_data_vector(size, rng, |_| value_string()),
         ),
-        "INT_RNG" => {
-            let (lower, upper) = match element.modifier.as_deref() {
-                Some(m) => parse_range_string(m).unwrap_or_else(|e| {
-                    eprintln!("Error parsing INT_RNG modifier '{m}': {e}; using default range");
-                    (0, size as i32)
-                }),
-                None => {
-                    eprintln!(
-                        "INT_RNG column '{}' has no (lo-hi) modifier; using default range",
-                        element.name
-                    );
-                    (0, size as i32)
+        "DIGIT" => Series::new(
+            element.name.as_str(),
+            build_data_vector(size, rng, fake_digit),
+        ),
+        "BOOLEAN" => {
+            let ratio = true_ratio(&element)?;
+            Series::new(
+                element.name.as_str(),
+                build_data_vector(size, rng, |r| r.gen_bool(ratio)),
+            )
+        }
+        "DECIMAL" => {
+            let values = match &element.modifier {
+                Some(m) if matches!(m.positional().first(), Some(Value::Call(..))) => {
+                    let scale = scale_option(&element, m)?;
+                    let options = ["clamp", "scale", "precision"];
+                    let spec = distribution_spec(&element, m, &options, scale)?;
+                    if let Some(digits) = decimal_digits(&element)? {
+                        check_clamp_fits(&element, &spec, digits)?;
+                    }
+                    numeric_vector(&spec, size, rng)
                 }
+                Some(m) => {
+                    let spec = decimal_spec(&element, m)?;
+                    build_data_vector(size, rng, |r| {
+                        fake_decimal_between(r, spec.lower, spec.upper, spec.scale)
+                    })
+                }
+                None => build_data_vector(size, rng, fake_decimal),
             };
-
+            match (decimal_digits(&element)?, &element.modifier) {
+                (Some((precision, scale)), _) => {
+                    decimal_series(&element.name, values, precision, scale)
+                }
+                (None, None) => decimal_series(&element.name, values, 10, 2),
+                (None, Some(_)) => Series::new(element.name.as_str(), values),
+            }
+        }
+        "ENUM" => {
+            let spec = enum_spec(&element)?;
+            let dist = WeightedIndex::new(&spec.weights).expect("weights checked by enum_spec");
             Series::new(
                 element.name.as_str(),
-                build_incremental_int(size as i32, lower, upper),
+                build_data_vector(size, rng, |r| spec.values[dist.sample(r)].clone()),
+            )
+        }
+        "PATTERN" => {
+            let regex = pattern_spec(&element)?;
+            Series::new(
+                element.name.as_str(),
+                build_data_vector(size, rng, |r| -> String { regex.sample(r) }),
             )
         }
-        "VALUE" => Series::new(element.name.as_str(), build_data_vector(size, value_string)),
-        "DIGIT" => Series::new(element.name.as_str(), build_data_vector(size, fake_digit)),
-        "DECIMAL" => Series::new(element.name.as_str(), build_data_vector(size, fake_decimal)),
-        "DATE" => Series::new(element.name.as_str(), build_data_vector(size, fake_date)),
-        "TIME" => Series::new(element.name.as_str(), build_data_vector(size, fake_time)),
-        "DATE_TIME" => Series::new(
+        "DATE" => match &element.modifier {
+            Some(m) => {
+                let spec = date_spec(m, now).map_err(|e| modifier_error(&element, e))?;
+                date_series(
+                    &element.name,
+                    build_data_vector(size, rng, |r| spec.sample(r)),
+                )
+            }
+            None => date_series(&element.name, build_data_vector(size, rng, fake_date)),
+        },
+        "TIME" => TimeChunked::from_naive_time(
             element.name.as_str(),
-            build_data_vector(size, fake_date_time),
+            build_data_vector(size, rng, fake_time),
+        )
+        .into_series(),
+        "DATE_TIME" => match &element.modifier {
+            Some(m) => {
+                let spec = date_time_spec(m, now).map_err(|e| modifier_error(&element, e))?;
+                date_time_series(
+                    &element.name,
+                    build_data_vector(size, rng, |r| spec.sample(r)),
+                )
+            }
+            None => date_time_series(&element.name, build_data_vector(size, rng, fake_date_time)),
+        },
+        "NAME" => Series::new(
+            element.name.as_str(),
+            build_data_vector(size, rng, |r| fake_name(r, locale)),
+        ),
+        "ZIP_CODE" => Series::new(
+            element.name.as_str(),
+            build_data_vector(size, rng, |r| fake_zipcode(r, locale)),
         ),
-        "NAME" => Series::new(element.name.as_str(), build_data_vector(size, fake_name)),
-        "ZIP_CODE" => Series::new(element.name.as_str(), build_data_vector(size, fake_zipcode)),
         "COUNTRY_CODE" => Series::new(
             element.name.as_str(),
-            build_data_vector(size, fake_country_code),
+            build_data_vector(size, rng, fake_country_code),
         ),
         "STATE_NAME" => Series::new(
             element.name.as_str(),
-            build_data_vector(size, fake_state_name),
+            build_data_vector(size, rng, |r| fake_state_name(r, locale)),
         ),
         "STATE_ABBR" => Series::new(
             element.name.as_str(),
-            build_data_vector(size, fake_state_abbr),
+            build_data_vector(size, rng, |r| fake_state_abbr(r, locale)),
         ),
-        "LAT" => Series::new(element.name.as_str(), build_data_vector(size, fake_lat)),
-        "LON" => Series::new(element.name.as_str(), build_data_vector(size, fake_lon)),
-        "PHONE" => Series::new(element.name.as_str(), build_data_vector(size, fake_phone)),
-        "PRICE" => Series::new(element.name.as_str(), build_data_vector(size, fake_price)),
+        "LAT" => decimal_series(&element.name, build_data_vector(size, rng, fake_lat), 9, 6),
+        "LON" => decimal_series(&element.name, build_data_vector(size, rng, fake_lon), 9, 6),
+        "PHONE" => Series::new(
+            element.name.as_str(),
+            build_data_vector(size, rng, |r| fake_phone(r, locale)),
+        ),
+        "PRICE" => match &element.modifier {
+            Some(m) => {
+                let spec = distribution_spec(&element, m, &["clamp"], Some(2))?;
+                price_series(
+                    &element.name,
+                    numeric_vector(&spec, size, rng)
+                        .into_iter()
+                        .map(|v| v.clamp(0.0, decimal_max(10, 2)))
+                        .collect(),
+                )
+            }
+            None => price_series(&element.name, build_data_vector(size, rng, fake_price)),
+        },
         "LOREM_WORD" => Series::new(
             element.name.as_str(),
-            build_data_vector(size, fake_lorem_word),
+            build_data_vector(size, rng, fake_lorem_word),
         ),
         "LOREM_TITLE" => Series::new(
             element.name.as_str(),
-            build_data_vector(size, fake_lorem_title),
+            build_data_vector(size, rng, fake_lorem_title),
         ),
         "LOREM_SENTENCE" => Series::new(
             element.name.as_str(),
-            build_data_vector(size, fake_lorem_sentence),
+            build_data_vector(size, rng, fake_lorem_sentence),
         ),
         "LOREM_PARAGRAPH" => Series::new(
             element.name.as_str(),
-            build_data_vector(size, fake_lorem_paragraph),
+            build_data_vector(size, rng, fake_lorem_paragraph),
+        ),
+        "UUID" => Series::new(
+            element.name.as_str(),
+            build_data_vector(size, rng, fake_uuid),
         ),
-        "UUID" => Series::new(element.name.as_str(), build_data_vector(size, fake_uuid)),
+        "LIST" => {
+            let (lo, hi) = list_length(&element)?;
+            let lengths = build_data_vector(size, &mut rng.part("\0len"), |r| r.gen_range(lo..=hi));
+            let total = lengths.iter().sum();
+            let items = nested_column(&element, &element.children[0], total, rng, now)?;
+            let mut builder = get_list_builder(items.dtype(), total, size, element.name.as_str())
+                .map_err(|e| modifier_error(&element, e))?;
+            let mut offset = 0;
+            for len in lengths {
+                builder
+                    .append_series(&items.slice(offset as i64, len))
+                    .map_err(|e| modifier_error(&element, e))?;
+                offset += len;
+            }
+            builder.finish().into_series()
+        }
+        "STRUCT" => {
+            let fields = element
+                .children
+                .iter()
+                .map(|field| nested_column(&element, field, size, &mut rng.part(&field.name), now))
+                .collect::<Result<Vec<_>, _>>()?;
+            StructChunked::new(element.name.as_str(), &fields)
+                .map_err(|e| modifier_error(&element, e))?
+                .into_series()
+        }
         "FIRST_NAME" => Series::new(
             element.name.as_str(),
-            build_data_vector(size, fake_first_name),
+            build_data_vector(size, rng, |r| fake_first_name(r, locale)),
         ),
         "LAST_NAME" => Series::new(
             element.name.as_str(),
-            build_data_vector(size, fake_last_name),
+            build_data_vector(size, rng, |r| fake_last_name(r, locale)),
+        ),
+        "SSN" => Series::new(
+            element.name.as_str(),
+            build_data_vector(size, rng, fake_ssn),
+        ),
+        "EMAIL" => Series::new(
+            element.name.as_str(),
+            build_data_vector(size, rng, fake_email),
+        ),
+        "URL" => Series::new(
+            element.name.as_str(),
+            build_data_vector(size, rng, |r| fake_url(r, locale)),
+        ),
+        "DOMAIN" => Series::new(
+            element.name.as_str(),
+            build_data_vector(size, rng, |r| fake_domain(r, locale)),
+        ),
+        "IPV4" => Series::new(
+            element.name.as_str(),
+            build_data_vector(size, rng, fake_ipv4),
+        ),
+        "IPV6" => Series::new(
+            element.name.as_str(),
+            build_data_vector(size, rng, fake_ipv6),
+        ),
+        "MAC_ADDRESS" => Series::new(
+            element.name.as_str(),
+            build_data_vector(size, rng, fake_mac_address),
+        ),
+        "USER_AGENT" => Series::new(
+            element.name.as_str(),
+            build_data_vector(size, rng, fake_user_agent),
+        ),
+        "COMPANY" => Series::new(
+            element.name.as_str(),
+            build_data_vector(size, rng, |r| fake_company(r, locale)),
+        ),
+        "JOB_TITLE" => Series::new(
+            element.name.as_str(),
+            build_data_vector(size, rng, |r| fake_job_title(r, locale)),
+        ),
+        "INDUSTRY" => Series::new(
+            element.name.as_str(),
+            build_data_vector(size, rng, fake_industry),
+        ),
+        "CREDIT_CARD" => {
+            let brands = table_choice(&element, CARD_BRANDS, |b| b.name)?;
+            Series::new(
+                element.name.as_str(),
+                build_data_vector(size, rng, |r| {
+                    let brand = brands[r.gen_range(0..brands.len())];
+                    fake_credit_card(r, brand)
+                }),
+            )
+        }
+        "IBAN" => {
+            let countries = table_choice(&element, IBAN_COUNTRIES, |c| c.code)?;
+            Series::new(
+                element.name.as_str(),
+                build_data_vector(size, rng, |r| {
+                    let country = countries[r.gen_range(0..countries.len())];
+                    fake_iban(r, country)
+                }),
+            )
+        }
+        "BIC" => Series::new(
+            element.name.as_str(),
+            build_data_vector(size, rng, fake_bic),
         ),
-        "SSN" => Series::new(element.name.as_str(), build_data_vector(size, fake_ssn)),
+        "CURRENCY_CODE" => Series::new(
+            element.name.as_str(),
+            build_data_vector(size, rng, fake_currency_code),
+        ),
+        "BINARY" => {
+            let (lo, hi) = byte_length(&element)?;
+            Series::new(
+                element.name.as_str(),
+                build_data_vector(size, rng, |r| fake_bytes(r, lo, hi)),
+            )
+        }
+        "HEX" => {
+            let (lo, hi) = byte_length(&element)?;
+            Series::new(
+                element.name.as_str(),
+                build_data_vector(size, rng, |r| hex(&fake_bytes(r, lo, hi))),
+            )
+        }
+        "BASE64" => {
+            let (lo, hi) = byte_length(&element)?;
+            Series::new(
+                element.name.as_str(),
+                build_data_vector(size, rng, |r| base64(&fake_bytes(r, lo, hi))),
+            )
+        }
+        "TOKEN" => {
+            let (prefix, length) = token_spec(&element)?;
+            Series::new(
+                element.name.as_str(),
+                build_data_vector(size, rng, |r| fake_token(r, &prefix, length)),
+            )
+        }
+        // Only reachable in `--lenient` runs; strict ones reject the type up
+        // front (see `validate::check_columns`).
         _ => Series::new(
             element.name.as_str(),
-            build_data_vector(size, unknown_string),
+            build_data_vector(size, rng, |_| unknown_string()),
         ),
     };
-    col
+    Ok(col)
+}
+
+fn date_series(name: &str, days: Vec<NaiveDate>) -> Series {
+    DateChunked::from_naive_date(name, days).into_series()
+}
+
+/// UTC timestamps with microsecond precision, which every target dialect and
+/// Parquet reader understands.
+pub(crate) fn date_time_series(name: &str, times: Vec<NaiveDateTime>) -> Series {
+    let mut ca = DatetimeChunked::from_naive_datetime(name, times, TimeUnit::Microseconds);
+    ca.set_time_zone("UTC".to_string())
+        .expect("UTC is a valid time zone");
+    ca.into_series()
+}
+
+/// Exact `Decimal(10, 2)` amounts, matching the `DECIMAL(10,2)` in the DDL.
+fn price_series(name: &str, amounts: Vec<f64>) -> Series {
+    decimal_series(name, amounts, 10, 2)
+}
+
+/// Exact `Decimal(precision, scale)` cells from `values`, rounded to `scale`
+/// places. Callers keep `values` within `precision` digits.
+fn decimal_series(name: &str, values: Vec<f64>, precision: usize, scale: usize) -> Series {
+    let factor = 10f64.powi(scale as i32);
+    let unscaled: Vec<i128> = values
+        .iter()
+        .map(|v| (v * factor).round() as i128)
+        .collect();
+    Int128Chunked::from_vec(name, unscaled)
+        .into_decimal_unchecked(Some(precision), scale)
+        .into_series()
 }
 
 //STRING
-pub fn fake_string() -> String {
-    Faker.fake::<String>()
+pub fn fake_string<R: Rng + ?Sized>(rng: &mut R) -> String {
+    Faker.fake_with_rng::<String, _>(rng)
+}
+/// Letters and digits, between `lo` and `hi` of them, both inclusive.
+pub fn fake_string_between<R: Rng + ?Sized>(rng: &mut R, lo: usize, hi: usize) -> String {
+    (lo..=hi).fake_with_rng::<String, _>(rng)
 }
 //INT
 //Digit
 
-pub fn fake_int() -> i32 {
-    (0..2147483647).fake::<i32>()
+pub fn fake_int<R: Rng + ?Sized>(rng: &mut R) -> i32 {
+    (0..2147483647).fake_with_rng::<i32, _>(rng)
+}
+
+pub fn fake_bigint<R: Rng + ?Sized>(rng: &mut R) -> i64 {
+    rng.gen_range(0..i64::MAX)
 }
 
-pub fn fake_digit() -> String {
-    Digit(EN).fake()
+pub fn fake_digit<R: Rng + ?Sized>(rng: &mut R) -> String {
+    Digit(EN).fake_with_rng(rng)
 }
 // DECIMAL
-pub fn fake_decimal() -> f32 {
-    (0.0..100000.0).fake::<f32>()
+pub fn fake_decimal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    (0.0..100000.0).fake_with_rng::<f64, _>(rng)
+}
+
+/// Uniform in `[lower, upper)`, rounded to `scale` decimal places if given.
+pub fn fake_decimal_between<R: Rng + ?Sized>(
+    rng: &mut R,
+    lower: f64,
+    upper: f64,
+    scale: Option<i32>,
+) -> f64 {
+    let v: f64 = rng.gen_range(lower..upper);
+    match scale {
+        Some(s) => {
+            let factor = 10f64.powi(s);
+            ((v * factor).round() / factor).clamp(lower, upper)
+        }
+        None => v,
+    }
 }
 //DATE
-pub fn fake_date() -> String {
-    Date(EN).fake()
+pub fn fake_date<R: Rng + ?Sized>(rng: &mut R) -> NaiveDate {
+    default_date_spec().sample(rng)
 }
 //TIME
-pub fn fake_time() -> String {
-    Time(EN).fake()
+pub fn fake_time<R: Rng + ?Sized>(rng: &mut R) -> NaiveTime {
+    Time(EN).fake_with_rng(rng)
 }
 
 //DATE_TIME
-pub fn fake_date_time() -> String {
-    DateTime(EN).fake()
+pub fn fake_date_time<R: Rng + ?Sized>(rng: &mut R) -> NaiveDateTime {
+    default_date_time_spec().sample(rng)
 }
 //NAME
-pub fn fake_name() -> String {
-    Name(EN).fake()
+pub fn fake_name<R: Rng + ?Sized>(rng: &mut R, locale: Locale) -> String {
+    localized!(locale, Name, rng)
 }
 
 //ZIP_CODE
-pub fn fake_zipcode() -> String {
-    PostCode(EN).fake()
+pub fn fake_zipcode<R: Rng + ?Sized>(rng: &mut R, locale: Locale) -> String {
+    localized!(locale, PostCode, rng)
 }
 //COUNTRY_CODE
-pub fn fake_country_code() -> String {
-    CountryCode(EN).fake()
+pub fn fake_country_code<R: Rng + ?Sized>(rng: &mut R) -> String {
+    CountryCode(EN).fake_with_rng(rng)
 }
 //STATE_NAME
-pub fn fake_state_name() -> String {
-    StateName(EN).fake()
+pub fn fake_state_name<R: Rng + ?Sized>(rng: &mut R, locale: Locale) -> String {
+    localized!(locale, StateName, rng)
 }
 //STATE_ABBR
-pub fn fake_state_abbr() -> String {
-    StateAbbr(EN).fake()
+pub fn fake_state_abbr<R: Rng + ?Sized>(rng: &mut R, locale: Locale) -> String {
+    localized!(locale, StateAbbr, rng)
 }
 //LAT
-pub fn fake_lat() -> String {
-    Latitude(EN).fake()
+pub fn fake_lat<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    degrees(rng, 90.0)
 }
 //LON
-pub fn fake_lon() -> String {
-    Longitude(EN).fake()
+pub fn fake_lon<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    degrees(rng, 180.0)
+}
+
+/// Uniform in `[-max, max]` at the six decimals of `DECIMAL(9,6)`.
+fn degrees<R: Rng + ?Sized>(rng: &mut R, max: f64) -> f64 {
+    (rng.gen_range(-max..=max) * 1e6).round() / 1e6
 }
 //PHONE
-pub fn fake_phone() -> String {
-    CellNumber(EN).fake()
+pub fn fake_phone<R: Rng + ?Sized>(rng: &mut R, locale: Locale) -> String {
+    localized!(locale, CellNumber, rng)
 }
 
 //LOREM_WORD
-pub fn fake_lorem_word() -> String {
-    Word(EN).fake()
+pub fn fake_lorem_word<R: Rng + ?Sized>(rng: &mut R) -> String {
+    Word(EN).fake_with_rng(rng)
 }
 
 //LOREM_TITLE
-pub fn fake_lorem_title() -> String {
-    let title: Vec<String> = Words(EN, 1..4).fake();
+pub fn fake_lorem_title<R: Rng + ?Sized>(rng: &mut R) -> String {
+    let title: Vec<String> = Words(EN, 1..4).fake_with_rng(rng);
     title
         .iter()
         .map(|s| capitalize_first(s))
@@ -226,38 +1355,200 @@ pub fn fake_lorem_title() -> String {
 }
 
 //LOREM_SENTENCE
-pub fn fake_lorem_sentence() -> String {
-    Sentence(EN, 1..15).fake()
+pub fn fake_lorem_sentence<R: Rng + ?Sized>(rng: &mut R) -> String {
+    Sentence(EN, 1..15).fake_with_rng(rng)
 }
 
 //LOREM_PARAGRAPH
-pub fn fake_lorem_paragraph() -> String {
-    Paragraph(EN, 1..100).fake()
+pub fn fake_lorem_paragraph<R: Rng + ?Sized>(rng: &mut R) -> String {
+    Paragraph(EN, 1..100).fake_with_rng(rng)
 }
 
 //UUID
-pub fn fake_uuid() -> String {
-    let uuid = Uuid::new_v4();
-    uuid.to_string()
+pub fn fake_uuid<R: Rng + ?Sized>(rng: &mut R) -> String {
+    UUIDv4.fake_with_rng(rng)
 }
 
 //FIRST_NAME
-pub fn fake_first_name() -> String {
-    name::first()
+pub fn fake_first_name<R: Rng + ?Sized>(rng: &mut R, locale: Locale) -> String {
+    localized!(locale, FirstName, rng)
 }
 //LAST_NAME
-pub fn fake_last_name() -> String {
-    name::last()
+pub fn fake_last_name<R: Rng + ?Sized>(rng: &mut R, locale: Locale) -> String {
+    localized!(locale, LastName, rng)
 }
 
 //SSN
-pub fn fake_ssn() -> String {
-    person::ssn()
+pub fn fake_ssn<R: Rng + ?Sized>(rng: &mut R) -> String {
+    rng.gen_range(100000000..999999999).to_string()
+}
+
+//EMAIL
+/// Addresses on the reserved `example.*` domains, so fixtures never point
+/// at a real mailbox.
+pub fn fake_email<R: Rng + ?Sized>(rng: &mut R) -> String {
+    let user: String = Username(EN).fake_with_rng(rng);
+    let tld = ["com", "net", "org"][rng.gen_range(0..3)];
+    format!("{user}@example.{tld}")
+}
+//DOMAIN
+pub fn fake_domain<R: Rng + ?Sized>(rng: &mut R, locale: Locale) -> String {
+    let word: &str = Word(EN).fake_with_rng(rng);
+    let suffix: &str = localized!(locale, DomainSuffix, rng);
+    format!("{word}.{suffix}")
+}
+//URL
+pub fn fake_url<R: Rng + ?Sized>(rng: &mut R, locale: Locale) -> String {
+    let domain = fake_domain(rng, locale);
+    let depth = rng.gen_range(0..=2);
+    let path: Vec<&str> = (0..depth).map(|_| Word(EN).fake_with_rng(rng)).collect();
+    format!("https://www.{domain}/{}", path.join("/"))
+}
+//IPV4
+pub fn fake_ipv4<R: Rng + ?Sized>(rng: &mut R) -> String {
+    Faker.fake_with_rng::<Ipv4Addr, _>(rng).to_string()
+}
+//IPV6
+pub fn fake_ipv6<R: Rng + ?Sized>(rng: &mut R) -> String {
+    Faker.fake_with_rng::<Ipv6Addr, _>(rng).to_string()
+}
+//MAC_ADDRESS
+pub fn fake_mac_address<R: Rng + ?Sized>(rng: &mut R) -> String {
+    MACAddress(EN).fake_with_rng(rng)
+}
+//USER_AGENT
+pub fn fake_user_agent<R: Rng + ?Sized>(rng: &mut R) -> String {
+    UserAgent(EN).fake_with_rng(rng)
+}
+
+//COMPANY
+pub fn fake_company<R: Rng + ?Sized>(rng: &mut R, locale: Locale) -> String {
+    localized!(locale, CompanyName, rng)
+}
+//JOB_TITLE
+pub fn fake_job_title<R: Rng + ?Sized>(rng: &mut R, locale: Locale) -> String {
+    localized!(locale, job::Title, rng)
+}
+//INDUSTRY
+pub fn fake_industry<R: Rng + ?Sized>(rng: &mut R) -> String {
+    Industry(EN).fake_with_rng(rng)
+}
+//CREDIT_CARD
+/// A `brand` card number ending in its Luhn check digit.
+pub(crate) fn fake_credit_card<R: Rng + ?Sized>(rng: &mut R, brand: &CardBrand) -> String {
+    let mut number = brand.prefixes[rng.gen_range(0..brand.prefixes.len())].to_string();
+    while number.len() < brand.length - 1 {
+        number.push(char::from(b'0' + rng.gen_range(0..10)));
+    }
+    let check = luhn_check_digit(&number);
+    number.push(check);
+    number
+}
+
+/// The digit that makes `payload` followed by it pass the Luhn check.
+fn luhn_check_digit(payload: &str) -> char {
+    let sum: u32 = payload
+        .bytes()
+        .rev()
+        .enumerate()
+        .map(|(i, b)| {
+            let d = u32::from(b - b'0');
+            match (i % 2 == 0, d * 2) {
+                (true, doubled) if doubled > 9 => doubled - 9,
+                (true, doubled) => doubled,
+                (false, _) => d,
+            }
+        })
+        .sum();
+    char::from(b'0' + ((10 - sum % 10) % 10) as u8)
+}
+//IBAN
+/// A `country` IBAN with ISO 13616 mod-97 check digits.
+pub(crate) fn fake_iban<R: Rng + ?Sized>(rng: &mut R, country: &IbanCountry) -> String {
+    const DIGITS: &[u8] = b"0123456789";
+    const LETTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    const ALPHANUMERIC: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    let mut bban = String::new();
+    for &(kind, count) in country.layout {
+        let charset = match kind {
+            'n' => DIGITS,
+            'a' => LETTERS,
+            _ => ALPHANUMERIC,
+        };
+        for _ in 0..count {
+            bban.push(char::from(charset[rng.gen_range(0..charset.len())]));
+        }
+    }
+    let check = 98 - mod97(&format!("{bban}{}00", country.code));
+    format!("{}{check:02}{bban}", country.code)
+}
+
+/// `s` read as one big number, letters counting as 10 to 35, modulo 97.
+fn mod97(s: &str) -> u32 {
+    s.chars().fold(0, |acc, c| {
+        let v = c.to_digit(36).expect("IBAN characters are alphanumeric");
+        let shift = if v < 10 { 10 } else { 100 };
+        (acc * shift + v) % 97
+    })
+}
+//BIC
+pub fn fake_bic<R: Rng + ?Sized>(rng: &mut R) -> String {
+    Bic(EN).fake_with_rng(rng)
+}
+//CURRENCY_CODE
+pub fn fake_currency_code<R: Rng + ?Sized>(rng: &mut R) -> String {
+    CurrencyCode(EN).fake_with_rng(rng)
+}
+
+//BINARY
+/// Between `lo` and `hi` random bytes, both inclusive.
+pub fn fake_bytes<R: Rng + ?Sized>(rng: &mut R, lo: usize, hi: usize) -> Vec<u8> {
+    let len = rng.gen_range(lo..=hi);
+    (0..len).map(|_| rng.gen()).collect()
+}
+
+//HEX
+/// `bytes` as lower-case hex, two digits per byte.
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+//BASE64
+/// `bytes` in standard, padded base64 (RFC 4648).
+pub(crate) fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (i, &b)| acc | u32::from(b) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(char::from(ALPHABET[(n >> (18 - 6 * i)) as usize & 0x3f]));
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+//TOKEN
+/// `prefix` followed by `length` random letters and digits, like an API key.
+pub fn fake_token<R: Rng + ?Sized>(rng: &mut R, prefix: &str, length: usize) -> String {
+    const ALPHANUMERIC: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+    let mut token = prefix.to_string();
+    token.extend(
+        (0..length).map(|_| char::from(ALPHANUMERIC[rng.gen_range(0..ALPHANUMERIC.len())])),
+    );
+    token
 }
 
 //PRICE
-pub fn fake_price() -> String {
-    currency::price(0.0, 9999.0).to_string()
+pub fn fake_price<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    let price: f64 = rng.gen_range(0.0..9999.0);
+    (price * 100.0).floor() / 100.0
 }
 
 //default
@@ -270,6 +1561,20 @@ pub fn unknown_string() -> String {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::util::modifier::parse_modifier;
+    use crate::util::rng::seeded_rng;
+
+    /// `create_column` at a fixed `now`, so no test depends on the clock.
+    fn create_column<C: CellRng>(element: Schema, size: usize, rng: &mut C) -> ColumnResult {
+        super::create_column(element, size, rng, noon())
+    }
+
+    fn noon() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 6, 12)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+    }
 
     #[test]
     fn test_build_incremental_int() {
@@ -293,16 +1598,29 @@ mod test {
         assert_eq!(data, vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
     }
 
+    fn with_modifier(name: &str, datatype: &str, modifier: &str) -> Schema {
+        Schema {
+            name: name.to_string(),
+            datatype: datatype.to_string(),
+            modifier: Some(parse_modifier(modifier).unwrap()),
+            null_ratio: None,
+            locale: None,
+            children: Vec::new(),
+        }
+    }
+
     #[test]
-    fn test_parse_range_string() {
-        let data = parse_range_string("(0-10)");
-        assert_eq!(data.unwrap(), (0, 10));
+    fn test_int_rng_bounds_from_legacy_range() {
+        let element = with_modifier("id", "INT_RNG", "(0-10)");
+        assert_eq!(int_rng_bounds(&element, 5).unwrap(), (0, 10));
     }
 
     #[test]
-    fn test_parse_negative_range_string() {
-        let data = parse_range_string("(-10-10)");
-        assert_eq!(data.unwrap(), (-10, 10));
+    fn test_int_rng_bounds_from_negative_range() {
+        let element = with_modifier("id", "INT_RNG", "(-10-10)");
+        assert_eq!(int_rng_bounds(&element, 5).unwrap(), (-10, 10));
+        let element = with_modifier("id", "INT_RNG", "(-10..10)");
+        assert_eq!(int_rng_bounds(&element, 5).unwrap(), (-10, 10));
     }
 
     #[test]
@@ -330,30 +1648,432 @@ mod test {
             name: "id".to_string(),
             datatype: "INT_RNG".to_string(),
             modifier: None,
+            null_ratio: None,
+            locale: None,
+            children: Vec::new(),
         };
-        let series = create_column(element, 5);
+        let series = create_column(element, 5, &mut seeded_rng(Some(1))).unwrap();
         assert_eq!(series.len(), 5);
     }
 
     #[test]
-    fn test_create_column_int_rng_with_bad_modifier_falls_back() {
+    fn test_create_column_int_rng_with_bad_modifier_is_an_error() {
+        for bad in ["(garbage)", "(1.5..3)", "(0-10, step=2)", "(0-1, 2-3)"] {
+            let element = with_modifier("id", "INT_RNG", bad);
+            let err = create_column(element, 4, &mut seeded_rng(Some(1))).unwrap_err();
+            assert!(
+                err.message.starts_with("column 'id': INT_RNG modifier:"),
+                "{bad}: {}",
+                err.message
+            );
+        }
+    }
+
+    #[test]
+    fn test_create_column_rejects_modifier_on_plain_type() {
+        let element = with_modifier("who", "NAME", "(3)");
+        let err = create_column(element, 4, &mut seeded_rng(Some(1))).unwrap_err();
+        assert!(err.message.contains("column 'who'"), "{}", err.message);
+        assert!(err.message.contains("takes no modifier"), "{}", err.message);
+    }
+
+    #[test]
+    fn test_create_column_decimal_range_and_scale() {
+        let element = with_modifier("price", "DECIMAL", "(0..500, scale=2)");
+        let series = create_column(element, 50, &mut seeded_rng(Some(1))).unwrap();
+        for v in series.f64().unwrap().into_iter().flatten() {
+            assert!((0.0..=500.0).contains(&v), "{v} out of range");
+            let cents = (v * 100.0).round();
+            assert!(
+                (v * 100.0 - cents).abs() < 1e-6,
+                "{v} has more than 2 decimals"
+            );
+        }
+    }
+
+    #[test]
+    fn test_create_column_decimal_rejects_unknown_option() {
+        let element = with_modifier("price", "DECIMAL", "(0..500, sacle=2)");
+        let err = create_column(element, 3, &mut seeded_rng(Some(1))).unwrap_err();
+        assert!(
+            err.message.contains("unknown option 'sacle'"),
+            "{}",
+            err.message
+        );
+
+        let element = with_modifier("price", "DECIMAL", "(500..0)");
+        assert!(create_column(element, 3, &mut seeded_rng(Some(1))).is_err());
+    }
+
+    #[test]
+    fn test_create_column_distributions_clamp_and_round() {
+        let element = with_modifier("score", "INT", "(normal(50, 10), clamp=0..100)");
+        let series = create_column(element, 500, &mut seeded_rng(Some(1))).unwrap();
+        let scores: Vec<i32> = series.i32().unwrap().into_iter().flatten().collect();
+        assert!(scores.iter().all(|v| (0..=100).contains(v)));
+        let mean = scores.iter().sum::<i32>() as f64 / 500.0;
+        assert!((47.0..53.0).contains(&mean), "{mean}");
+
+        let element = with_modifier("amount", "DECIMAL", "(lognormal(3, 1), scale=2)");
+        let series = create_column(element, 50, &mut seeded_rng(Some(1))).unwrap();
+        assert!(series.f64().unwrap().into_iter().flatten().all(|v| v > 0.0));
+
+        let element = with_modifier("price", "PRICE", "(normal(0, 50))");
+        let series = create_column(element, 50, &mut seeded_rng(Some(1))).unwrap();
+        assert_eq!(series.dtype(), &DataType::Decimal(Some(10), Some(2)));
+        let cents = series.decimal().unwrap();
+        assert!(cents.into_iter().flatten().all(|c| c >= 0));
+    }
+
+    #[test]
+    fn test_create_column_distribution_errors_name_the_type() {
+        for (ty, bad) in [
+            ("INT", "(normal(50))"),
+            ("INT", "(normal(50, 10), scale=2)"),
+            ("PRICE", "(0..5)"),
+            ("DECIMAL", "(zipf(10, 1), clamp=5)"),
+        ] {
+            let element = with_modifier("x", ty, bad);
+            let err = create_column(element, 3, &mut seeded_rng(Some(1))).unwrap_err();
+            assert!(
+                err.message
+                    .starts_with(&format!("column 'x': {ty} modifier:")),
+                "{bad}: {}",
+                err.message
+            );
+        }
+    }
+
+    #[test]
+    fn test_create_column_int_and_bigint_ranges() {
+        let element = with_modifier("die", "INT", "(1..6)");
+        let series = create_column(element, 300, &mut seeded_rng(Some(1))).unwrap();
+        let rolls: Vec<i32> = series.i32().unwrap().into_iter().flatten().collect();
+        assert!(rolls.iter().all(|v| (1..=6).contains(v)));
+        assert!(rolls.contains(&1) && rolls.contains(&6));
+
+        let element = with_modifier("id", "BIGINT", "(5000000000..5000000009)");
+        let series = create_column(element, 50, &mut seeded_rng(Some(1))).unwrap();
+        assert_eq!(series.dtype(), &DataType::Int64);
+        assert!(series
+            .i64()
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .all(|v| (5_000_000_000..=5_000_000_009).contains(&v)));
+
+        let element = with_modifier("n", "BIGINT", "(normal(0, 10))");
+        assert!(create_column(element, 5, &mut seeded_rng(Some(1))).is_ok());
+
+        for bad in ["(1..5000000000)", "(9..1)", "(garbage)", "(1..6, scale=2)"] {
+            let element = with_modifier("die", "INT", bad);
+            let err = create_column(element, 3, &mut seeded_rng(Some(1))).unwrap_err();
+            assert!(
+                err.message.starts_with("column 'die': INT modifier:"),
+                "{bad}: {}",
+                err.message
+            );
+        }
+    }
+
+    #[test]
+    fn test_create_column_bigint_inc_is_sequential_i64() {
         let element = Schema {
             name: "id".to_string(),
-            datatype: "INT_RNG".to_string(),
-            modifier: Some("garbage".to_string()),
+            datatype: "BIGINT_INC".to_string(),
+            modifier: None,
+            null_ratio: None,
+            locale: None,
+            children: Vec::new(),
         };
-        let series = create_column(element, 4);
-        assert_eq!(series.len(), 4);
+        let series = create_column(element, 4, &mut seeded_rng(None)).unwrap();
+        let ids: Vec<i64> = series.i64().unwrap().into_iter().flatten().collect();
+        assert_eq!(ids, vec![0, 1, 2, 3]);
     }
 
     #[test]
-    fn test_create_column_int_rng_with_valid_modifier() {
+    fn test_create_column_sequences_take_start_step_and_format() {
+        let element = with_modifier("n", "INT_INC", "(start=1000, step=-10)");
+        let series = create_column(element, 3, &mut seeded_rng(Some(1))).unwrap();
+        let values: Vec<i32> = series.i32().unwrap().into_no_null_iter().collect();
+        assert_eq!(values, vec![1000, 990, 980]);
+
+        let element = with_modifier("id", "BIGINT_INC", "(start=1, prefix='ORD-', width=6)");
+        let series = create_column(element, 3, &mut seeded_rng(Some(1))).unwrap();
+        let values: Vec<&str> = series.str().unwrap().into_no_null_iter().collect();
+        assert_eq!(values, vec!["ORD-000001", "ORD-000002", "ORD-000003"]);
+
+        let element = with_modifier("id", "INT_INC", "(start=2147483646)");
+        let err = create_column(element, 3, &mut seeded_rng(Some(1))).unwrap_err();
+        assert!(err.message.ends_with("; use BIGINT_INC"), "{}", err.message);
+
+        for bad in ["(step=0)", "(prefix=ORD)", "(width=0)", "(5)", "(begin=1)"] {
+            let element = with_modifier("id", "INT_INC", bad);
+            let err = create_column(element, 3, &mut seeded_rng(Some(1))).unwrap_err();
+            assert!(
+                err.message.starts_with("column 'id': INT_INC modifier:"),
+                "{bad}: {}",
+                err.message
+            );
+        }
+    }
+
+    #[test]
+    fn test_create_column_boolean_follows_true_ratio() {
+        let element = with_modifier("active", "BOOLEAN", "(0.8)");
+        let series = create_column(element, 1000, &mut seeded_rng(Some(1))).unwrap();
+        assert_eq!(series.dtype(), &DataType::Boolean);
+        let trues = series.bool().unwrap().sum().unwrap();
+        assert!((750..850).contains(&trues), "{trues}");
+
+        for bad in ["(1.5)", "(yes)", "(0.5, 0.5)", "(ratio=0.5)"] {
+            let element = with_modifier("active", "BOOLEAN", bad);
+            let err = create_column(element, 3, &mut seeded_rng(Some(1))).unwrap_err();
+            assert!(
+                err.message
+                    .starts_with("column 'active': BOOLEAN modifier:"),
+                "{bad}: {}",
+                err.message
+            );
+        }
+    }
+
+    /// Standard Luhn validation, independent of the generator's check digit.
+    fn luhn_valid(number: &str) -> bool {
+        let sum: u32 = number
+            .chars()
+            .rev()
+            .enumerate()
+            .map(|(i, c)| {
+                let d = c.to_digit(10).unwrap();
+                if i % 2 == 1 {
+                    (d * 2) / 10 + (d * 2) % 10
+                } else {
+                    d
+                }
+            })
+            .sum();
+        sum.is_multiple_of(10)
+    }
+
+    #[test]
+    fn test_create_column_credit_cards_pass_luhn() {
+        let series = create_column(
+            with_modifier("card", "CREDIT_CARD", "('amex')"),
+            200,
+            &mut seeded_rng(Some(4)),
+        )
+        .unwrap();
+        for card in series.str().unwrap().into_no_null_iter() {
+            assert_eq!(card.len(), 15, "{card}");
+            assert!(card.starts_with("34") || card.starts_with("37"), "{card}");
+            assert!(luhn_valid(card), "{card}");
+        }
+
         let element = Schema {
-            name: "score".to_string(),
-            datatype: "INT_RNG".to_string(),
-            modifier: Some("(10-20)".to_string()),
+            name: "card".to_string(),
+            datatype: "CREDIT_CARD".to_string(),
+            modifier: None,
+            null_ratio: None,
+            locale: None,
+            children: Vec::new(),
+        };
+        let series = create_column(element, 200, &mut seeded_rng(Some(4))).unwrap();
+        for card in series.str().unwrap().into_no_null_iter() {
+            assert!(luhn_valid(card), "{card}");
+        }
+
+        for bad in ["('diners')", "(4)", "('visa', 'amex')", "(brand='visa')"] {
+            let element = with_modifier("card", "CREDIT_CARD", bad);
+            let err = create_column(element, 3, &mut seeded_rng(Some(1))).unwrap_err();
+            assert!(
+                err.message
+                    .starts_with("column 'card': CREDIT_CARD modifier:"),
+                "{bad}: {}",
+                err.message
+            );
+        }
+    }
+
+    #[test]
+    fn test_create_column_ibans_pass_mod_97() {
+        let series = create_column(
+            with_modifier("iban", "IBAN", "('gb')"),
+            200,
+            &mut seeded_rng(Some(5)),
+        )
+        .unwrap();
+        for iban in series.str().unwrap().into_no_null_iter() {
+            assert_eq!(iban.len(), 22, "{iban}");
+            assert!(iban.starts_with("GB"), "{iban}");
+            let rearranged = format!("{}{}", &iban[4..], &iban[..4]);
+            assert_eq!(mod97(&rearranged), 1, "{iban}");
+        }
+        // A published example IBAN keeps its check digits.
+        assert_eq!(mod97(&format!("{}GB00", "WEST12345698765432")), 98 - 82);
+
+        let err = create_column(
+            with_modifier("iban", "IBAN", "('XX')"),
+            3,
+            &mut seeded_rng(Some(1)),
+        )
+        .unwrap_err();
+        assert!(
+            err.message
+                .contains("expected one of DE, GB, FR, NL, ES, IT"),
+            "{}",
+            err.message
+        );
+    }
+
+    #[test]
+    fn test_create_column_date_ranges() {
+        let element = with_modifier("d", "DATE", "(2024-02-01..2024-02-29)");
+        let series = create_column(element, 50, &mut seeded_rng(Some(1))).unwrap();
+        assert_eq!(series.dtype(), &DataType::Date);
+        let (lo, hi) = (
+            NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 2, 29).unwrap(),
+        );
+        assert!(series
+            .date()
+            .unwrap()
+            .as_date_iter()
+            .flatten()
+            .all(|d| lo <= d && d <= hi));
+
+        let element = with_modifier("ts", "DATE_TIME", "(2024-02-01..2024-02-01T00:10)");
+        let series = create_column(element, 50, &mut seeded_rng(Some(1))).unwrap();
+        assert_eq!(
+            series.dtype(),
+            &DataType::Datetime(TimeUnit::Microseconds, Some("UTC".to_string()))
+        );
+        let hi = lo.and_hms_opt(0, 10, 0).unwrap();
+        assert!(series
+            .datetime()
+            .unwrap()
+            .as_datetime_iter()
+            .flatten()
+            .all(|t| lo.and_time(NaiveTime::MIN) <= t && t <= hi));
+
+        let element = with_modifier("ts", "DATE_TIME", "(now..now-1d)");
+        let err = create_column(element, 3, &mut seeded_rng(Some(1))).unwrap_err();
+        assert!(
+            err.message.starts_with("column 'ts': DATE_TIME modifier:"),
+            "{}",
+            err.message
+        );
+    }
+
+    #[test]
+    fn test_create_column_enum_follows_weights() {
+        let element = with_modifier(
+            "status",
+            "ENUM",
+            "('active'=70, 'suspended'=20, 'closed'=10)",
+        );
+        let series = create_column(element, 2000, &mut seeded_rng(Some(1))).unwrap();
+        let count = |want: &str| {
+            series
+                .str()
+                .unwrap()
+                .into_iter()
+                .filter(|v| *v == Some(want))
+                .count()
         };
-        let series = create_column(element, 5);
+        assert_eq!(count("active") + count("suspended") + count("closed"), 2000);
+        assert!(
+            (1300..1500).contains(&count("active")),
+            "{}",
+            count("active")
+        );
+        assert!((100..300).contains(&count("closed")), "{}", count("closed"));
+    }
+
+    #[test]
+    fn test_create_column_enum_without_weights_is_uniform() {
+        let element = with_modifier("tier", "ENUM", "('gold', 'silver', 3)");
+        let series = create_column(element, 300, &mut seeded_rng(Some(1))).unwrap();
+        let values: std::collections::HashSet<&str> =
+            series.str().unwrap().into_iter().flatten().collect();
+        assert_eq!(values, ["gold", "silver", "3"].into_iter().collect());
+    }
+
+    #[test]
+    fn test_enum_spec_rejects_bad_lists() {
+        for bad in [
+            "()",
+            "('a'=1, 'b')",
+            "('a'=0)",
+            "('a', 'a')",
+            "('a'=x)",
+            "(0..5)",
+        ] {
+            let element = with_modifier("s", "ENUM", bad);
+            let err = enum_spec(&element).err().expect(bad);
+            assert!(
+                err.message.starts_with("column 's': ENUM modifier:"),
+                "{bad}: {}",
+                err.message
+            );
+        }
+        let element = Schema {
+            name: "s".to_string(),
+            datatype: "ENUM".to_string(),
+            modifier: None,
+            null_ratio: None,
+            locale: None,
+            children: Vec::new(),
+        };
+        assert!(create_column(element, 3, &mut seeded_rng(Some(1))).is_err());
+    }
+
+    #[test]
+    fn test_create_column_pattern_matches_regex() {
+        let element = with_modifier("sku", "PATTERN", r"('[A-Z]{3}-\d{6}')");
+        let series = create_column(element, 50, &mut seeded_rng(Some(1))).unwrap();
+        let re = regex::Regex::new(r"^[A-Z]{3}-\d{6}$").unwrap();
+        for v in series.str().unwrap().into_iter().flatten() {
+            assert!(re.is_match(v), "{v} does not match");
+        }
+    }
+
+    #[test]
+    fn test_pattern_classes_stay_ascii_where_possible() {
+        let element = with_modifier("code", "PATTERN", r"('\w{4}.\d|[à-ÿ]')");
+        let series = create_column(element, 50, &mut seeded_rng(Some(1))).unwrap();
+        for v in series.str().unwrap().into_iter().flatten() {
+            let ascii = v.chars().all(|c| c.is_ascii_graphic() || c == ' ');
+            let accented =
+                v.chars().count() == 1 && ('à'..='ÿ').contains(&v.chars().next().unwrap());
+            assert!(ascii || accented, "{v:?}");
+        }
+    }
+
+    #[test]
+    fn test_pattern_spec_rejects_bad_patterns() {
+        for bad in [
+            "()",
+            "([A-Z]+)",
+            "('[A-Z')",
+            "('^abc$')",
+            "('a', 'b')",
+            "('a', x=1)",
+        ] {
+            let element = with_modifier("sku", "PATTERN", bad);
+            let err = pattern_spec(&element).expect_err(bad);
+            assert!(
+                err.message.starts_with("column 'sku': PATTERN modifier:"),
+                "{bad}: {}",
+                err.message
+            );
+        }
+    }
+
+    #[test]
+    fn test_create_column_int_rng_with_valid_modifier() {
+        let element = with_modifier("score", "INT_RNG", "(10-20)");
+        let series = create_column(element, 5, &mut seeded_rng(Some(1))).unwrap();
         assert_eq!(series.len(), 5);
         let vals: Vec<i32> = series.i32().unwrap().into_iter().flatten().collect();
         assert_eq!(vals[0], 10);
@@ -365,8 +2085,11 @@ mod test {
             "STRING",
             "INT",
             "INT_INC",
+            "BIGINT",
+            "BIGINT_INC",
             "VALUE",
             "DIGIT",
+            "BOOLEAN",
             "DECIMAL",
             "DATE",
             "TIME",
@@ -388,6 +2111,24 @@ mod test {
             "FIRST_NAME",
             "LAST_NAME",
             "SSN",
+            "EMAIL",
+            "URL",
+            "DOMAIN",
+            "IPV4",
+            "IPV6",
+            "MAC_ADDRESS",
+            "USER_AGENT",
+            "COMPANY",
+            "JOB_TITLE",
+            "INDUSTRY",
+            "CREDIT_CARD",
+            "IBAN",
+            "BIC",
+            "CURRENCY_CODE",
+            "BINARY",
+            "HEX",
+            "BASE64",
+            "TOKEN",
             "UNKNOWN_TYPE_FALLTHROUGH",
         ];
         for type_name in &types {
@@ -395,56 +2136,425 @@ mod test {
                 name: format!("col_{type_name}"),
                 datatype: type_name.to_string(),
                 modifier: None,
+                null_ratio: None,
+                locale: None,
+                children: Vec::new(),
             };
-            let series = create_column(element, 3);
+            let series = create_column(element, 3, &mut seeded_rng(Some(1))).unwrap();
             assert_eq!(series.len(), 3, "type {type_name} produced wrong length");
         }
     }
 
     #[test]
     fn test_generator_functions_return_nonempty_strings() {
-        assert!(!fake_string().is_empty());
-        assert!(!fake_digit().is_empty());
-        assert!(!fake_date().is_empty());
-        assert!(!fake_time().is_empty());
-        assert!(!fake_date_time().is_empty());
-        assert!(!fake_name().is_empty());
-        assert!(!fake_zipcode().is_empty());
-        assert!(!fake_country_code().is_empty());
-        assert!(!fake_state_name().is_empty());
-        assert!(!fake_state_abbr().is_empty());
-        assert!(!fake_lat().is_empty());
-        assert!(!fake_lon().is_empty());
-        assert!(!fake_phone().is_empty());
-        assert!(!fake_price().is_empty());
-        assert!(!fake_lorem_word().is_empty());
-        assert!(!fake_lorem_title().is_empty());
-        assert!(!fake_lorem_sentence().is_empty());
-        assert!(!fake_lorem_paragraph().is_empty());
-        assert!(!fake_uuid().is_empty());
-        assert!(!fake_first_name().is_empty());
-        assert!(!fake_last_name().is_empty());
-        assert!(!fake_ssn().is_empty());
+        let mut rng = seeded_rng(None);
+        assert!(!fake_string(&mut rng).is_empty());
+        assert!(!fake_digit(&mut rng).is_empty());
+        assert!(!fake_name(&mut rng, Locale::En).is_empty());
+        assert!(!fake_zipcode(&mut rng, Locale::En).is_empty());
+        assert!(!fake_country_code(&mut rng).is_empty());
+        assert!(!fake_state_name(&mut rng, Locale::En).is_empty());
+        assert!(!fake_state_abbr(&mut rng, Locale::En).is_empty());
+        assert!(!fake_phone(&mut rng, Locale::En).is_empty());
+        assert!(!fake_lorem_word(&mut rng).is_empty());
+        assert!(!fake_lorem_title(&mut rng).is_empty());
+        assert!(!fake_lorem_sentence(&mut rng).is_empty());
+        assert!(!fake_lorem_paragraph(&mut rng).is_empty());
+        assert!(!fake_uuid(&mut rng).is_empty());
+        assert!(!fake_first_name(&mut rng, Locale::En).is_empty());
+        assert!(!fake_last_name(&mut rng, Locale::En).is_empty());
+        assert!(!fake_ssn(&mut rng).is_empty());
         assert_eq!(value_string(), "value");
         assert_eq!(unknown_string(), "unknown");
     }
 
+    #[test]
+    fn test_localized_columns_use_their_locale() {
+        let element = Schema::new("n", "NAME", Some("(locale=ja_JP)")).unwrap();
+        let series = create_column(element, 20, &mut seeded_rng(Some(2))).unwrap();
+        for name in series.str().unwrap().into_no_null_iter() {
+            assert!(!name.is_ascii(), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_code_like_localized_types_stay_ascii_in_every_locale() {
+        // Their DDL types are VARCHAR/CHAR on SQL Server, not NVARCHAR.
+        let mut rng = seeded_rng(Some(6));
+        for locale in [
+            Locale::En,
+            Locale::FrFr,
+            Locale::PtBr,
+            Locale::JaJp,
+            Locale::ZhCn,
+            Locale::ZhTw,
+            Locale::ArSa,
+        ] {
+            for _ in 0..50 {
+                for value in [
+                    fake_phone(&mut rng, locale),
+                    fake_zipcode(&mut rng, locale),
+                    fake_state_abbr(&mut rng, locale),
+                ] {
+                    assert!(value.is_ascii(), "{locale}: {value}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_internet_generators_are_well_formed() {
+        let mut rng = seeded_rng(Some(8));
+        for _ in 0..50 {
+            let email = fake_email(&mut rng);
+            let (user, host) = email.split_once('@').unwrap();
+            assert!(!user.is_empty() && host.starts_with("example."), "{email}");
+            let url = fake_url(&mut rng, Locale::En);
+            assert!(
+                url.starts_with("https://www.") && !url.contains(' '),
+                "{url}"
+            );
+            assert!(fake_domain(&mut rng, Locale::En).contains('.'));
+            assert!(fake_ipv4(&mut rng).parse::<Ipv4Addr>().is_ok());
+            assert!(fake_ipv6(&mut rng).parse::<Ipv6Addr>().is_ok());
+            let mac = fake_mac_address(&mut rng);
+            assert_eq!(mac.len(), 17, "{mac}");
+            assert_eq!(mac.matches(':').count(), 5, "{mac}");
+            assert!(!fake_user_agent(&mut rng).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_company_generators_are_well_formed() {
+        let mut rng = seeded_rng(Some(9));
+        for _ in 0..50 {
+            assert!(!fake_company(&mut rng, Locale::En).is_empty());
+            assert!(!fake_job_title(&mut rng, Locale::En).is_empty());
+            assert!(!fake_industry(&mut rng).is_empty());
+            let code = fake_currency_code(&mut rng);
+            assert!(
+                code.len() == 3 && code.chars().all(|c| c.is_ascii_uppercase()),
+                "{code}"
+            );
+            let bic = fake_bic(&mut rng);
+            assert!(bic.len() == 8 || bic.len() == 11, "{bic}");
+        }
+    }
+
+    #[test]
+    fn test_create_column_strings_take_their_length() {
+        let mut rng = seeded_rng(Some(4));
+        let series = create_column(with_modifier("s", "STRING", "(2..4)"), 200, &mut rng).unwrap();
+        let lengths: Vec<usize> = series
+            .str()
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .map(str::len)
+            .collect();
+        assert!(lengths.iter().all(|n| (2..=4).contains(n)));
+        assert!(lengths.contains(&2) && lengths.contains(&4));
+
+        let series = create_column(with_modifier("s", "STRING", "(12)"), 20, &mut rng).unwrap();
+        assert!(series
+            .str()
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .all(|v| v.len() == 12));
+
+        let err = create_column(with_modifier("s", "STRING", "(0)"), 3, &mut rng).unwrap_err();
+        assert!(
+            err.message.starts_with("column 's': STRING modifier:"),
+            "{}",
+            err.message
+        );
+    }
+
+    #[test]
+    fn test_create_column_decimal_precision_is_exact() {
+        let mut rng = seeded_rng(Some(4));
+        let element = with_modifier("b", "DECIMAL", "(precision=6, scale=2)");
+        let series = create_column(element, 100, &mut rng).unwrap();
+        assert_eq!(series.dtype(), &DataType::Decimal(Some(6), Some(2)));
+        assert!(series
+            .decimal()
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .all(|v| (0..1_000_000).contains(&v)));
+
+        let element = with_modifier(
+            "b",
+            "DECIMAL",
+            "(normal(50, 10), clamp=0..9999, precision=4)",
+        );
+        let series = create_column(element, 10, &mut rng).unwrap();
+        assert_eq!(series.dtype(), &DataType::Decimal(Some(4), Some(0)));
+
+        let element = with_modifier("b", "DECIMAL", "(99.99..99.999, precision=4, scale=2)");
+        assert!(create_column(element, 10, &mut rng).is_err());
+        let element = with_modifier("b", "DECIMAL", "(99..99.99, precision=4, scale=2)");
+        let series = create_column(element, 100, &mut rng).unwrap();
+        assert!(series
+            .decimal()
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .all(|v| (9900..=9999).contains(&v)));
+
+        for bad in [
+            "(precision=4, scale=6)",
+            "(precision=0)",
+            "(0..1000, precision=4, scale=2)",
+            "(0..100, precision=4, scale=2)",
+            "(scale=12)",
+            "(normal(50, 10), precision=4)",
+            "(normal(50, 10), clamp=0..10000, precision=4)",
+        ] {
+            let element = with_modifier("b", "DECIMAL", bad);
+            let err = create_column(element, 3, &mut rng).unwrap_err();
+            assert!(
+                err.message.starts_with("column 'b': DECIMAL modifier:"),
+                "{bad}: {}",
+                err.message
+            );
+        }
+    }
+
+    #[test]
+    fn test_create_column_binary_and_encoded_bytes_take_their_length() {
+        let mut rng = seeded_rng(Some(3));
+        let series = create_column(with_modifier("b", "BINARY", "(4..8)"), 50, &mut rng).unwrap();
+        assert_eq!(series.dtype(), &DataType::Binary);
+        assert!(series
+            .binary()
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .all(|b| (4..=8).contains(&b.len())));
+
+        let element = Schema::from_string("digest:HEX").unwrap().unwrap();
+        let series = create_column(element, 20, &mut rng).unwrap();
+        for digest in series.str().unwrap().into_iter().flatten() {
+            assert_eq!(digest.len(), 64, "{digest}");
+            assert!(digest
+                .chars()
+                .all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase()));
+        }
+
+        let series = create_column(with_modifier("k", "BASE64", "(16)"), 20, &mut rng).unwrap();
+        assert!(series
+            .str()
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .all(|k| k.len() == 24 && k.ends_with("==")));
+
+        let element = with_modifier("key", "TOKEN", "('sk_live_', 24)");
+        let series = create_column(element, 20, &mut rng).unwrap();
+        for key in series.str().unwrap().into_iter().flatten() {
+            let rest = key.strip_prefix("sk_live_").unwrap();
+            assert_eq!(rest.len(), 24, "{key}");
+            assert!(rest.chars().all(|c| c.is_ascii_alphanumeric()), "{key}");
+        }
+    }
+
+    #[test]
+    fn test_byte_and_token_modifiers_are_checked() {
+        for (ty, bad) in [
+            ("BINARY", "(0)"),
+            ("HEX", "(8..4)"),
+            ("BASE64", "('16')"),
+            ("BINARY", "(2000000)"),
+            ("TOKEN", "(24, 'sk_')"),
+            ("TOKEN", "('sk_', 0)"),
+            ("TOKEN", "('sk_', 24, 1)"),
+        ] {
+            let element = with_modifier("x", ty, bad);
+            let err = create_column(element, 3, &mut seeded_rng(Some(1))).unwrap_err();
+            assert!(
+                err.message
+                    .starts_with(&format!("column 'x': {ty} modifier:")),
+                "{bad}: {}",
+                err.message
+            );
+        }
+    }
+
+    #[test]
+    fn test_hex_and_base64_encode_known_bytes() {
+        assert_eq!(hex(&[0x00, 0x0f, 0xab]), "000fab");
+        for (plain, encoded) in [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(base64(plain.as_bytes()), encoded);
+        }
+    }
+
     #[test]
     fn test_fake_int_is_non_negative() {
-        let v = fake_int();
+        let mut rng = seeded_rng(None);
+        let v = fake_int(&mut rng);
         assert!(v >= 0);
     }
 
+    #[test]
+    fn test_coordinates_and_prices_stay_in_range() {
+        let mut rng = seeded_rng(Some(3));
+        for _ in 0..200 {
+            assert!((-90.0..=90.0).contains(&fake_lat(&mut rng)));
+            assert!((-180.0..=180.0).contains(&fake_lon(&mut rng)));
+            assert!((0.0..9999.0).contains(&fake_price(&mut rng)));
+        }
+    }
+
     #[test]
     fn test_fake_decimal_is_in_range() {
-        let v = fake_decimal();
+        let mut rng = seeded_rng(None);
+        let v = fake_decimal(&mut rng);
         assert!((0.0..100000.0).contains(&v));
     }
 
     #[test]
     fn test_fake_uuid_has_correct_format() {
-        let u = fake_uuid();
+        let mut rng = seeded_rng(None);
+        let u = fake_uuid(&mut rng);
         assert_eq!(u.len(), 36);
         assert_eq!(u.chars().filter(|&c| c == '-').count(), 4);
     }
+
+    #[test]
+    fn test_create_column_same_seed_is_reproducible() {
+        for type_name in ["STRING", "INT", "NAME", "UUID", "PRICE", "DATE_TIME"] {
+            let element = Schema {
+                name: "c".to_string(),
+                datatype: type_name.to_string(),
+                modifier: None,
+                null_ratio: None,
+                locale: None,
+                children: Vec::new(),
+            };
+            let a = create_column(element.clone(), 20, &mut seeded_rng(Some(42))).unwrap();
+            let b = create_column(element, 20, &mut seeded_rng(Some(42))).unwrap();
+            assert!(
+                a.equals(&b),
+                "type {type_name} differed under the same seed"
+            );
+        }
+    }
+
+    #[test]
+    fn test_create_column_keyed_rows_do_not_depend_on_size() {
+        use crate::util::rng::KeyedRng;
+        let element = Schema {
+            name: "email".to_string(),
+            datatype: "NAME".to_string(),
+            modifier: None,
+            null_ratio: None,
+            locale: None,
+            children: Vec::new(),
+        };
+        let short = create_column(element.clone(), 5, &mut KeyedRng::new(1, "t", "email")).unwrap();
+        let long = create_column(element, 50, &mut KeyedRng::new(1, "t", "email")).unwrap();
+        assert!(short.equals(&long.head(Some(5))));
+    }
+
+    #[test]
+    fn test_create_column_lists_take_their_length_range() {
+        let element = Schema::from_string("tags:LIST<INT(1..9)>(1..3)")
+            .unwrap()
+            .unwrap();
+        let series = create_column(element, 100, &mut seeded_rng(Some(5))).unwrap();
+        assert_eq!(series.dtype(), &DataType::List(Box::new(DataType::Int32)));
+        for row in series.list().unwrap().into_iter() {
+            let items = row.unwrap();
+            assert!((1..=3).contains(&items.len()), "{}", items.len());
+            assert!(items
+                .i32()
+                .unwrap()
+                .into_iter()
+                .flatten()
+                .all(|v| (1..=9).contains(&v)));
+        }
+
+        let element = Schema::from_string("tags:LIST<INT>(4..2)")
+            .unwrap()
+            .unwrap();
+        let err = create_column(element, 3, &mut seeded_rng(Some(5))).unwrap_err();
+        assert!(
+            err.message.starts_with("column 'tags': LIST modifier:"),
+            "{}",
+            err.message
+        );
+    }
+
+    #[test]
+    fn test_create_column_structs_generate_each_field() {
+        use crate::util::rng::KeyedRng;
+        let element = Schema::from_string("address:STRUCT<street:STRING, zip:ZIP_CODE?(null=1)>")
+            .unwrap()
+            .unwrap();
+        let series =
+            create_column(element.clone(), 20, &mut KeyedRng::new(1, "t", "address")).unwrap();
+        let fields = series.struct_().unwrap().fields().to_vec();
+        assert_eq!(fields.len(), 2);
+        assert_eq!((fields[0].name(), fields[0].len()), ("street", 20));
+        assert_eq!(fields[1].null_count(), 20);
+
+        // Adding a field leaves the existing ones untouched under stable rows.
+        let wider = Schema::from_string("address:STRUCT<street:STRING, zip:ZIP_CODE, city:STRING>")
+            .unwrap()
+            .unwrap();
+        let series = create_column(wider, 20, &mut KeyedRng::new(1, "t", "address")).unwrap();
+        assert!(series.struct_().unwrap().fields()[0].equals(&fields[0]));
+    }
+
+    #[test]
+    fn test_apply_null_ratio_nulls_about_that_fraction() {
+        let element = Schema {
+            name: "n".to_string(),
+            datatype: "NAME".to_string(),
+            modifier: None,
+            null_ratio: None,
+            locale: None,
+            children: Vec::new(),
+        };
+        let series = create_column(element, 1000, &mut seeded_rng(Some(1))).unwrap();
+        let series = apply_null_ratio(series, 0.25, &mut seeded_rng(Some(2))).unwrap();
+        assert_eq!(series.len(), 1000);
+        let nulls = series.null_count();
+        assert!((150..350).contains(&nulls), "got {nulls} nulls");
+    }
+
+    #[test]
+    fn test_apply_null_ratio_zero_and_one() {
+        let series = Series::new("x", vec![1i32, 2, 3]);
+        let kept = apply_null_ratio(series.clone(), 0.0, &mut seeded_rng(Some(1))).unwrap();
+        assert_eq!(kept.null_count(), 0);
+        let all = apply_null_ratio(series, 1.0, &mut seeded_rng(Some(1))).unwrap();
+        assert_eq!(all.null_count(), 3);
+        assert_eq!(all.dtype(), &DataType::Int32);
+    }
+
+    #[test]
+    fn test_create_column_different_seeds_differ() {
+        let element = Schema {
+            name: "c".to_string(),
+            datatype: "UUID".to_string(),
+            modifier: None,
+            null_ratio: None,
+            locale: None,
+            children: Vec::new(),
+        };
+        let a = create_column(element.clone(), 5, &mut seeded_rng(Some(1))).unwrap();
+        let b = create_column(element, 5, &mut seeded_rng(Some(2))).unwrap();
+        assert!(!a.equals(&b));
+    }
 }
@@ -9,13 +9,15 @@
 //! - M:N relationships emit a junction `<Left>_<Right>` DataFrame with two FK
 //!   columns; `}|--|{` enforces ≥1 coverage on both sides
 
+use crate::util::derived::{derivation_of, render_pending, Pending};
 use crate::util::erd_ast::{Cardinality, Entity, ErdAst, Relationship};
-use crate::util::fake::create_column;
-use crate::util::parser::mermaid_type_to_synthtab;
-use crate::util::schema::Schema;
+use crate::util::fake::{apply_null_ratio, create_column};
+use crate::util::locale::Locale;
+use crate::util::parser::{attribute_schema, mermaid_type_to_synthtab};
+use crate::util::rng::{CellRng, RunRng};
 use polars::prelude::*;
 use rand::seq::SliceRandom;
-use rand::{thread_rng, Rng};
+use rand::Rng;
 use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::fmt;
@@ -37,6 +39,8 @@ pub fn generate(
     ast: &ErdAst,
     default_rows: usize,
     rows_per: &HashMap<String, usize>,
+    rng: &mut RunRng,
+    locale: Locale,
 ) -> Result<Vec<(String, DataFrame)>, GenError> {
     validate_row_counts(ast, default_rows, rows_per)?;
 
@@ -49,7 +53,7 @@ pub fn generate(
             .entity(entity_name)
             .expect("entity in topo order exists");
         let n = resolve_rows(entity_name, default_rows, rows_per);
-        let df = build_entity_frame(entity, ast, &frames, n)?;
+        let df = build_entity_frame(entity, ast, &frames, n, rng, locale)?;
         frames.insert(entity_name.clone(), df.clone());
         ordered.push((entity_name.clone(), df));
     }
@@ -64,7 +68,7 @@ pub fn generate(
             let b = resolve_rows(&r.right, default_rows, rows_per);
             a.max(b)
         });
-        let df = build_junction_frame(r, &frames, junction_rows)?;
+        let df = build_junction_frame(r, &frames, junction_rows, rng)?;
         ordered.push((junction_name, df));
     }
 
@@ -162,44 +166,75 @@ fn build_entity_frame(
     ast: &ErdAst,
     parents: &HashMap<String, DataFrame>,
     n: usize,
+    rng: &mut RunRng,
+    locale: Locale,
 ) -> Result<DataFrame, GenError> {
     let fk_targets = fk_targets_for(&entity.name, ast);
     let fk_names: HashSet<&str> = fk_targets.iter().map(|(_, n)| n.as_str()).collect();
 
-    let mut columns: Vec<Series> = Vec::new();
+    let entity_err = |message: String| GenError {
+        message: format!("entity '{}': {message}", entity.name),
+    };
+    let mut columns: Vec<Option<Series>> = Vec::new();
+    let mut derived: Vec<Pending> = Vec::new();
 
     for attr in &entity.attributes {
-        if fk_names.contains(attr.name.as_str()) {
+        let mut schema = attribute_schema(attr).map_err(|e| entity_err(e.message))?;
+        schema.locale = schema.locale.or(Some(locale));
+        let null_ratio = schema.null_ratio;
+        let is_fk = fk_names.contains(attr.name.as_str());
+        if !is_fk {
+            if let Some(derivation) = derivation_of(&schema).map_err(|e| entity_err(e.message))? {
+                derived.push(Pending {
+                    slot: columns.len(),
+                    column: schema,
+                    derivation,
+                });
+                columns.push(None);
+                continue;
+            }
+        }
+
+        let col = if is_fk {
+            let mapped = mermaid_type_to_synthtab(&attr.data_type).unwrap_or("STRING");
+            if schema.modifier.is_some() || schema.datatype != mapped {
+                return Err(GenError {
+                    message: format!(
+                        "entity '{}': FK attribute '{}' takes its values from the parent key; only '?' or null= hints apply",
+                        entity.name, attr.name
+                    ),
+                });
+            }
             let parent_name = fk_targets
                 .iter()
                 .find(|(_, n)| n == &attr.name)
                 .map(|(p, _)| p.clone())
                 .expect("fk_name appeared in set above");
-            let series = sample_fk_column(
+            sample_fk_column(
                 &attr.name,
                 &parent_name,
                 parents,
                 n,
                 requires_coverage(&entity.name, &parent_name, ast),
-            )?;
-            columns.push(series);
-            continue;
-        }
-
-        let synthtab_type = mermaid_type_to_synthtab(&attr.data_type).ok_or_else(|| GenError {
-            message: format!(
-                "entity '{}': attribute '{}' has unknown type '{}' (parser should have caught this)",
-                entity.name, attr.name, attr.data_type
-            ),
-        })?;
+                &mut rng.column(&entity.name, &attr.name),
+            )?
+        } else {
+            let now = rng.now();
+            create_column(schema, n, &mut rng.column(&entity.name, &attr.name), now)
+                .map_err(|e| entity_err(e.message))?
+        };
 
-        let schema = Schema {
-            name: attr.name.clone(),
-            datatype: synthtab_type.to_string(),
-            modifier: None,
+        let col = match null_ratio {
+            Some(ratio) => apply_null_ratio(col, ratio, &mut rng.nulls(&entity.name, &attr.name))
+                .map_err(|e| GenError {
+                message: format!(
+                    "entity '{}': failed to apply nulls to '{}': {e}",
+                    entity.name, attr.name
+                ),
+            })?,
+            None => col,
         };
-        let col = create_column(schema, n);
-        columns.push(col);
+        columns.push(Some(col));
     }
 
     let attr_names: HashSet<&str> = entity.attributes.iter().map(|a| a.name.as_str()).collect();
@@ -213,9 +248,12 @@ fn build_entity_frame(
             parents,
             n,
             requires_coverage(&entity.name, parent_name, ast),
+            &mut rng.column(&entity.name, fk_name),
         )?;
-        columns.push(series);
+        columns.push(Some(series));
     }
+    render_pending(derived, &mut columns, rng, &entity.name).map_err(|e| entity_err(e.message))?;
+    let columns: Vec<Series> = columns.into_iter().flatten().collect();
 
     DataFrame::new(columns).map_err(|e| GenError {
         message: format!(
@@ -236,12 +274,13 @@ fn requires_coverage(child: &str, parent: &str, ast: &ErdAst) -> bool {
     false
 }
 
-fn sample_fk_column(
+fn sample_fk_column<C: CellRng>(
     column_name: &str,
     parent_name: &str,
     parents: &HashMap<String, DataFrame>,
     n: usize,
     require_full_coverage: bool,
+    rng: &mut C,
 ) -> Result<Series, GenError> {
     let parent_df = parents.get(parent_name).ok_or_else(|| GenError {
         message: format!(
@@ -252,14 +291,18 @@ fn sample_fk_column(
         message: format!("parent '{parent_name}' has no columns"),
     })?;
 
-    sample_series_with_replacement(column_name, parent_pk_series, n, require_full_coverage)
+    sample_series_with_replacement(column_name, parent_pk_series, n, require_full_coverage, rng)
 }
 
-fn sample_series_with_replacement(
+/// Row `i`'s pick draws from `rng.cell(i)`, so under `--stable-rows` it
+/// survives a change in `n`. The `||--|{` coverage shuffle necessarily
+/// depends on `n` and reorders the whole column.
+fn sample_series_with_replacement<C: CellRng>(
     column_name: &str,
     source: &Series,
     n: usize,
     require_full_coverage: bool,
+    rng: &mut C,
 ) -> Result<Series, GenError> {
     let parent_len = source.len();
     if parent_len == 0 {
@@ -268,16 +311,19 @@ fn sample_series_with_replacement(
         });
     }
 
-    let mut rng = thread_rng();
-    let mut indices: Vec<usize> = (0..n).map(|_| rng.gen_range(0..parent_len)).collect();
+    let mut indices: Vec<usize> = (0..n)
+        .map(|row| rng.cell(row, |r| r.gen_range(0..parent_len)))
+        .collect();
 
     if require_full_coverage && n >= parent_len {
-        let mut perm: Vec<usize> = (0..parent_len).collect();
-        perm.shuffle(&mut rng);
-        for (i, &p) in perm.iter().enumerate() {
-            indices[i] = p;
-        }
-        indices.shuffle(&mut rng);
+        rng.column(|r| {
+            let mut perm: Vec<usize> = (0..parent_len).collect();
+            perm.shuffle(r);
+            for (i, &p) in perm.iter().enumerate() {
+                indices[i] = p;
+            }
+            indices.shuffle(r);
+        });
     }
 
     take_by_indices(column_name, source, &indices)
@@ -325,7 +371,9 @@ fn build_junction_frame(
     r: &Relationship,
     parents: &HashMap<String, DataFrame>,
     n: usize,
+    rng: &mut RunRng,
 ) -> Result<DataFrame, GenError> {
+    let junction_name = format!("{}_{}", r.left, r.right);
     let left_fk = format!("{}_id", r.left.to_lowercase());
     let right_fk = format!("{}_id", r.right.to_lowercase());
 
@@ -335,6 +383,7 @@ fn build_junction_frame(
         parents,
         n,
         matches!(r.cardinality, Cardinality::MandatoryManyToMany),
+        &mut rng.column(&junction_name, &left_fk),
     )?;
     let right_series = sample_fk_column(
         &right_fk,
@@ -342,13 +391,11 @@ fn build_junction_frame(
         parents,
         n,
         matches!(r.cardinality, Cardinality::MandatoryManyToMany),
+        &mut rng.column(&junction_name, &right_fk),
     )?;
 
     DataFrame::new(vec![left_series, right_series]).map_err(|e| GenError {
-        message: format!(
-            "junction {}_{}: failed to assemble DataFrame: {e}",
-            r.left, r.right
-        ),
+        message: format!("junction {junction_name}: failed to assemble DataFrame: {e}"),
     })
 }
 
@@ -356,6 +403,7 @@ mod test {
     #![allow(unused_imports, dead_code)]
     use super::*;
     use crate::util::parser::parse;
+    use crate::util::rng::RunRng;
     use crate::util::scanner::scan;
 
     fn ast_from(src: &str) -> ErdAst {
@@ -372,7 +420,14 @@ erDiagram
   PERSON ||--o{ CAR : owns
 ";
         let ast = ast_from(src);
-        let frames = generate(&ast, 5, &HashMap::new()).unwrap();
+        let frames = generate(
+            &ast,
+            5,
+            &HashMap::new(),
+            &mut RunRng::new(None, false),
+            Locale::En,
+        )
+        .unwrap();
         let names: Vec<&str> = frames.iter().map(|(n, _)| n.as_str()).collect();
         let person_idx = names.iter().position(|&n| n == "PERSON").unwrap();
         let car_idx = names.iter().position(|&n| n == "CAR").unwrap();
@@ -391,7 +446,14 @@ erDiagram
   PARENT ||--o{ CHILD : has
 ";
         let ast = ast_from(src);
-        let frames = generate(&ast, 10, &HashMap::new()).unwrap();
+        let frames = generate(
+            &ast,
+            10,
+            &HashMap::new(),
+            &mut RunRng::new(None, false),
+            Locale::En,
+        )
+        .unwrap();
         let parent = frames.iter().find(|(n, _)| n == "PARENT").unwrap();
         let child = frames.iter().find(|(n, _)| n == "CHILD").unwrap();
 
@@ -432,7 +494,14 @@ erDiagram
   STUDENT }o--o{ COURSE : enrolled
 ";
         let ast = ast_from(src);
-        let frames = generate(&ast, 5, &HashMap::new()).unwrap();
+        let frames = generate(
+            &ast,
+            5,
+            &HashMap::new(),
+            &mut RunRng::new(None, false),
+            Locale::En,
+        )
+        .unwrap();
         let names: Vec<&str> = frames.iter().map(|(n, _)| n.as_str()).collect();
         assert!(names.contains(&"STUDENT_COURSE"), "got: {names:?}");
         let junction = frames.iter().find(|(n, _)| n == "STUDENT_COURSE").unwrap();
@@ -452,7 +521,7 @@ erDiagram
         let mut rows = HashMap::new();
         rows.insert("PARENT".to_string(), 4);
         rows.insert("CHILD".to_string(), 12);
-        let frames = generate(&ast, 10, &rows).unwrap();
+        let frames = generate(&ast, 10, &rows, &mut RunRng::new(None, false), Locale::En).unwrap();
         let child = frames.iter().find(|(n, _)| n == "CHILD").unwrap();
         let child_fks: HashSet<i32> = child
             .1
@@ -482,7 +551,7 @@ erDiagram
         let mut rows = HashMap::new();
         rows.insert("USER".to_string(), 5);
         rows.insert("PROFILE".to_string(), 10);
-        let err = generate(&ast, 5, &rows).unwrap_err();
+        let err = generate(&ast, 5, &rows, &mut RunRng::new(None, false), Locale::En).unwrap_err();
         assert!(
             err.message.contains("requires count"),
             "got: {}",
@@ -499,7 +568,14 @@ erDiagram
   AUTHOR ||--o{ BOOK : writes
 ";
         let ast = ast_from(src);
-        let frames = generate(&ast, 5, &HashMap::new()).unwrap();
+        let frames = generate(
+            &ast,
+            5,
+            &HashMap::new(),
+            &mut RunRng::new(None, false),
+            Locale::En,
+        )
+        .unwrap();
         let book = frames.iter().find(|(n, _)| n == "BOOK").unwrap();
         let cols: Vec<&str> = book.1.get_column_names();
         assert!(cols.contains(&"author_id"), "got cols: {cols:?}");
@@ -515,7 +591,14 @@ erDiagram
   AUTHOR ||--o{ BOOK : writes
 ";
         let ast = ast_from(src);
-        let frames = generate(&ast, 5, &HashMap::new()).unwrap();
+        let frames = generate(
+            &ast,
+            5,
+            &HashMap::new(),
+            &mut RunRng::new(None, false),
+            Locale::En,
+        )
+        .unwrap();
         let book = frames.iter().find(|(n, _)| n == "BOOK").unwrap();
         let cols: Vec<&str> = book.1.get_column_names();
         let count = cols.iter().filter(|&&c| c == "author_id").count();
@@ -524,4 +607,73 @@ erDiagram
             "expected author_id exactly once, got cols: {cols:?}"
         );
     }
+
+    #[test]
+    fn same_seed_produces_identical_frames() {
+        let src = "\
+erDiagram
+  PARENT { int id PK
+           string name }
+  CHILD { int id PK
+          uuid token }
+  PARENT ||--|{ CHILD : has
+";
+        let ast = ast_from(src);
+        let a = generate(
+            &ast,
+            8,
+            &HashMap::new(),
+            &mut RunRng::new(Some(11), false),
+            Locale::En,
+        )
+        .unwrap();
+        let b = generate(
+            &ast,
+            8,
+            &HashMap::new(),
+            &mut RunRng::new(Some(11), false),
+            Locale::En,
+        )
+        .unwrap();
+        assert_eq!(a.len(), b.len());
+        for ((na, da), (nb, db)) in a.iter().zip(b.iter()) {
+            assert_eq!(na, nb);
+            assert!(da.equals(db), "frame {na} differed under the same seed");
+        }
+    }
+
+    #[test]
+    fn stable_rows_keep_child_values_when_row_count_grows() {
+        let src = "\
+erDiagram
+  PARENT { int id PK }
+  CHILD { int id PK
+          string name }
+  PARENT ||--o{ CHILD : has
+";
+        let ast = ast_from(src);
+        let mut small_rows = HashMap::new();
+        small_rows.insert("CHILD".to_string(), 5);
+        let mut large_rows = HashMap::new();
+        large_rows.insert("CHILD".to_string(), 50);
+        let small = generate(
+            &ast,
+            4,
+            &small_rows,
+            &mut RunRng::new(Some(2), true),
+            Locale::En,
+        )
+        .unwrap();
+        let large = generate(
+            &ast,
+            4,
+            &large_rows,
+            &mut RunRng::new(Some(2), true),
+            Locale::En,
+        )
+        .unwrap();
+        let small_child = &small.iter().find(|(n, _)| n == "CHILD").unwrap().1;
+        let large_child = &large.iter().find(|(n, _)| n == "CHILD").unwrap().1;
+        assert!(small_child.equals(&large_child.head(Some(5))));
+    }
 }
@@ -0,0 +1,817 @@
+//! Schema inference for the `synthtab infer` subcommand.
+//!
+//! ```text
+//! synthtab infer users.parquet
+//! # id:INT_INC(start=1),email:EMAIL,plan:ENUM('free'=61, 'pro'=39),signup:DATE(2021-03-02..2024-06-30)
+//! ```
+//!
+//! Each column of a sample frame gets the most specific synthtab type its
+//! values support. Value shapes come first (UUIDs, emails, IPs, dates held as
+//! text), then the column name (`first_name`, `phone`, `zip`, ...), then
+//! low-cardinality text becomes an `ENUM` of the observed values weighted by
+//! frequency. Everything else keeps its type with the observed range: `INT`
+//! bounds, `DECIMAL` bounds and scale, `STRING` lengths, `DATE` spans.
+//!
+//! Only `ENUM` repeats sample values; name-like columns never become one, so
+//! personal data in the sample does not reach the schema.
+//!
+//! `synthtab mimic` profiles the same way with `Profiling::mimic`: numeric
+//! columns follow a distribution fitted to the sample, clamped to its range,
+//! and `ENUM` values are relabelled unless categories may be reused, keeping
+//! their frequencies either way.
+
+use crate::util::modifier::ModifierError;
+use crate::util::schema::Schema;
+use crate::util::schema_file::ColumnSpec;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+use polars::prelude::*;
+use regex::Regex;
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::sync::OnceLock;
+use uuid::Uuid;
+
+/// Text columns with at most this many distinct values become an `ENUM`.
+pub const DEFAULT_MAX_ENUM: usize = 20;
+
+/// `DEFAULT_MAX_ENUM` for `synthtab mimic`, which keeps category
+/// frequencies without printing them.
+pub const DEFAULT_MIMIC_MAX_ENUM: usize = 1000;
+
+/// Fractional digits looked for in float columns; more are rounded away.
+const MAX_SCALE: usize = 6;
+
+/// One inferred column, ready to print as a `--schema` token or a schema
+/// file entry.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InferredColumn {
+    pub name: String,
+    /// The type, with its element types for `LIST` and `STRUCT`.
+    pub datatype: String,
+    /// Modifier arguments, without the surrounding parentheses.
+    pub args: Vec<String>,
+    /// Observed share of nulls, when there were any.
+    pub null_ratio: Option<f64>,
+}
+
+impl InferredColumn {
+    fn new(name: &str, datatype: impl Into<String>, args: Vec<String>) -> Self {
+        InferredColumn {
+            name: name.to_string(),
+            datatype: datatype.into(),
+            args,
+            null_ratio: None,
+        }
+    }
+
+    /// `TYPE(args)`, with the null ratio as a `null=` option.
+    pub fn type_with_modifier(&self) -> String {
+        let mut args = self.args.clone();
+        if let Some(ratio) = self.null_ratio {
+            args.push(format!("null={ratio}"));
+        }
+        if args.is_empty() {
+            self.datatype.clone()
+        } else {
+            format!("{}({})", self.datatype, args.join(", "))
+        }
+    }
+
+    /// `name:TYPE(args)` as accepted by `--schema`.
+    pub fn to_schema_string(&self) -> String {
+        format!("{}:{}", self.name, self.type_with_modifier())
+    }
+
+    /// The column as a generator schema.
+    pub(crate) fn to_schema(&self) -> Result<Schema, ModifierError> {
+        let modifier = (!self.args.is_empty()).then(|| format!("({})", self.args.join(", ")));
+        let mut column = Schema::new(&self.name, &self.datatype, modifier.as_deref())?;
+        column.null_ratio = self.null_ratio;
+        Ok(column)
+    }
+
+    /// The column as a schema file entry.
+    pub(crate) fn to_column_spec(&self) -> ColumnSpec {
+        ColumnSpec {
+            name: self.name.clone(),
+            datatype: self.datatype.clone(),
+            modifier: (!self.args.is_empty()).then(|| format!("({})", self.args.join(", "))),
+            null: self.null_ratio,
+        }
+    }
+}
+
+/// What a profile keeps of the sample.
+#[derive(Clone, Copy, Debug)]
+pub struct Profiling {
+    /// Text columns with more distinct values than this are never an `ENUM`.
+    pub max_enum: usize,
+    /// Match the sample's statistics: numbers follow a distribution fitted
+    /// to the sample, and ratios keep four decimal places instead of two.
+    pub statistical: bool,
+    /// `ENUM`s list the sample's values; otherwise each value is replaced by
+    /// `<column>_<rank>`, most frequent first, with the same weights.
+    pub reuse_categories: bool,
+}
+
+impl Profiling {
+    /// A readable schema: observed ranges and values.
+    pub fn infer(max_enum: usize) -> Self {
+        Profiling {
+            max_enum,
+            statistical: false,
+            reuse_categories: true,
+        }
+    }
+
+    /// A lookalike of the sample that only repeats its values when
+    /// `reuse_categories` allows.
+    pub fn mimic(max_enum: usize, reuse_categories: bool) -> Self {
+        Profiling {
+            max_enum,
+            statistical: true,
+            reuse_categories,
+        }
+    }
+
+    fn places(&self) -> i32 {
+        if self.statistical {
+            4
+        } else {
+            2
+        }
+    }
+}
+
+/// Infer one column per column of `df`.
+pub fn infer_columns(df: &DataFrame, p: &Profiling) -> PolarsResult<Vec<InferredColumn>> {
+    df.get_columns()
+        .iter()
+        .map(|s| infer_column(s, p))
+        .collect()
+}
+
+fn infer_column(s: &Series, p: &Profiling) -> PolarsResult<InferredColumn> {
+    let name = s.name();
+    let values = s.drop_nulls();
+    let mut column = if values.is_empty() {
+        InferredColumn::new(name, "STRING", Vec::new())
+    } else {
+        match s.dtype() {
+            DataType::Boolean => infer_boolean(name, &values, p)?,
+            dt if dt.is_integer() => infer_integer(name, &values, s.null_count() == 0, p)?,
+            DataType::Float32 | DataType::Float64 => infer_float(name, &values, p)?,
+            DataType::Decimal(precision, scale) => {
+                let (lo, hi) = float_bounds(&values)?;
+                let scale = scale.unwrap_or(0);
+                let precision = precision.unwrap_or(38).max(scale.max(1));
+                let mut args = spread(&values, decimal_range(lo, hi, scale), p)?;
+                args.push(format!("precision={precision}"));
+                args.push(format!("scale={scale}"));
+                InferredColumn::new(name, "DECIMAL", args)
+            }
+            DataType::Date => {
+                let (lo, hi) = int_bounds(&values.to_physical_repr())?;
+                let day = |d: i64| date_from_days(d).format("%Y-%m-%d").to_string();
+                InferredColumn::new(name, "DATE", vec![format!("{}..{}", day(lo), day(hi))])
+            }
+            DataType::Datetime(unit, _) => {
+                let (lo, hi) = int_bounds(&values.to_physical_repr())?;
+                let at = |t: i64| {
+                    date_time_from(t, *unit)
+                        .format("%Y-%m-%dT%H:%M:%S")
+                        .to_string()
+                };
+                InferredColumn::new(name, "DATE_TIME", vec![format!("{}..{}", at(lo), at(hi))])
+            }
+            DataType::Time => InferredColumn::new(name, "TIME", Vec::new()),
+            DataType::Binary => {
+                let lengths = values.binary()?.into_iter().flatten().map(<[u8]>::len);
+                InferredColumn::new(name, "BINARY", vec![length_range(lengths, 1)])
+            }
+            DataType::List(_) => infer_list(name, &values, p)?,
+            DataType::Struct(_) => {
+                let fields = values
+                    .struct_()?
+                    .fields()
+                    .iter()
+                    .map(|f| infer_column(f, p).map(|c| c.to_schema_string()))
+                    .collect::<PolarsResult<Vec<_>>>()?;
+                InferredColumn::new(name, format!("STRUCT<{}>", fields.join(", ")), Vec::new())
+            }
+            DataType::String => infer_text(name, &values, p)?,
+            _ => infer_text(name, &values.cast(&DataType::String)?, p)?,
+        }
+    };
+    if s.null_count() > 0 {
+        column.null_ratio = Some(ratio(s.null_count(), s.len(), p.places()));
+    }
+    Ok(column)
+}
+
+fn infer_boolean(name: &str, values: &Series, p: &Profiling) -> PolarsResult<InferredColumn> {
+    let trues = values.bool()?.sum().unwrap_or(0) as usize;
+    let share = ratio(trues, values.len(), p.places());
+    Ok(InferredColumn::new(
+        name,
+        "BOOLEAN",
+        vec![share.to_string()],
+    ))
+}
+
+/// A gap-free run counting up by one is a sequence; anything else a range.
+fn infer_integer(
+    name: &str,
+    values: &Series,
+    no_nulls: bool,
+    p: &Profiling,
+) -> PolarsResult<InferredColumn> {
+    let (lo, hi) = int_bounds(values)?;
+    let wide = lo < i32::MIN as i64 || hi > i32::MAX as i64;
+    let ints = values.cast(&DataType::Int64)?;
+    let sequential = no_nulls
+        && values.len() > 1
+        && ints
+            .i64()?
+            .into_no_null_iter()
+            .enumerate()
+            .all(|(i, v)| v == lo + i as i64);
+    Ok(match (sequential, wide) {
+        (true, _) => {
+            let ty = if wide { "BIGINT_INC" } else { "INT_INC" };
+            let args = match lo {
+                0 => Vec::new(),
+                start => vec![format!("start={start}")],
+            };
+            InferredColumn::new(name, ty, args)
+        }
+        (false, true) => {
+            InferredColumn::new(name, "BIGINT", spread(values, format!("{lo}..{hi}"), p)?)
+        }
+        (false, false) => {
+            InferredColumn::new(name, "INT", spread(values, format!("{lo}..{hi}"), p)?)
+        }
+    })
+}
+
+fn infer_float(name: &str, values: &Series, p: &Profiling) -> PolarsResult<InferredColumn> {
+    if let Some(ty @ ("LAT" | "LON")) = name_hint(name) {
+        return Ok(InferredColumn::new(name, ty, Vec::new()));
+    }
+    let (lo, hi) = float_bounds(values)?;
+    let scale = values
+        .cast(&DataType::Float64)?
+        .f64()?
+        .into_iter()
+        .flatten()
+        .map(decimal_places)
+        .max()
+        .unwrap_or(0);
+    let mut args = spread(values, decimal_range(lo, hi, scale), p)?;
+    args.push(format!("scale={scale}"));
+    Ok(InferredColumn::new(name, "DECIMAL", args))
+}
+
+/// `lo..hi` as is, or in a statistical profile a fitted distribution
+/// clamped to it.
+fn spread(values: &Series, range: String, p: &Profiling) -> PolarsResult<Vec<String>> {
+    if p.statistical {
+        let floats = values.cast(&DataType::Float64)?;
+        let floats: Vec<f64> = floats.f64()?.into_iter().flatten().collect();
+        if let Some(distribution) = fitted_distribution(&floats) {
+            return Ok(vec![distribution, format!("clamp={range}")]);
+        }
+    }
+    Ok(vec![range])
+}
+
+/// The distribution `values` look drawn from, or `None` when a uniform
+/// range describes them as well: a flat spread, a constant, or too few
+/// values to tell. Right-skewed positive values are log-normal, the rest
+/// normal.
+fn fitted_distribution(values: &[f64]) -> Option<String> {
+    if values.len() < 3 {
+        return None;
+    }
+    let (mean, sd) = mean_and_sd(values);
+    if sd == 0.0 {
+        return None;
+    }
+    let moment = |k: i32| {
+        values
+            .iter()
+            .map(|v| ((v - mean) / sd).powi(k))
+            .sum::<f64>()
+            / values.len() as f64
+    };
+    let (skew, excess_kurtosis) = (moment(3), moment(4) - 3.0);
+    // A uniform spread has an excess kurtosis of -1.2, a normal one 0.
+    if excess_kurtosis < -0.9 && skew.abs() < 0.5 {
+        return None;
+    }
+    if skew > 1.0 && values.iter().all(|v| *v > 0.0) {
+        let logs: Vec<f64> = values.iter().map(|v| v.ln()).collect();
+        let (mu, sigma) = mean_and_sd(&logs);
+        return Some(format!("lognormal({}, {})", short(mu), short(sigma)));
+    }
+    Some(format!("normal({}, {})", short(mean), short(sd)))
+}
+
+/// Mean and population standard deviation.
+fn mean_and_sd(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    (mean, variance.sqrt())
+}
+
+/// `v` to four decimal places, without trailing zeros.
+fn short(v: f64) -> String {
+    ((v * 1e4).round() / 1e4).to_string()
+}
+
+fn infer_list(name: &str, values: &Series, p: &Profiling) -> PolarsResult<InferredColumn> {
+    let lists = values.list()?;
+    let mut lengths = Vec::with_capacity(lists.len());
+    let mut items: Option<Series> = None;
+    for list in lists.into_iter().flatten() {
+        lengths.push(list.len());
+        match &mut items {
+            Some(all) => {
+                all.append(&list)?;
+            }
+            None => items = Some(list),
+        }
+    }
+    let items = items.unwrap_or_else(|| Series::new_null(name, 0));
+    let item = infer_column(&items.with_name(name), p)?;
+    Ok(InferredColumn::new(
+        name,
+        format!("LIST<{}>", item.type_with_modifier()),
+        vec![length_range(lengths.into_iter(), 0)],
+    ))
+}
+
+/// Text: value shapes, then the column name, then an `ENUM` of few distinct
+/// values, then a `STRING` of the observed lengths.
+fn infer_text(name: &str, values: &Series, p: &Profiling) -> PolarsResult<InferredColumn> {
+    let texts: Vec<&str> = values.str()?.into_iter().flatten().collect();
+    if let Some(column) = infer_shape(name, &texts) {
+        return Ok(column);
+    }
+    match name_hint(name) {
+        Some("STATE") => {
+            let ty = if texts.iter().all(|t| t.len() == 2) {
+                "STATE_ABBR"
+            } else {
+                "STATE_NAME"
+            };
+            return Ok(InferredColumn::new(name, ty, Vec::new()));
+        }
+        Some("LAT" | "LON") | None => {}
+        Some(ty) => return Ok(InferredColumn::new(name, ty, Vec::new())),
+    }
+    if let Some(column) = infer_enum(name, &texts, p) {
+        return Ok(column);
+    }
+    let lengths = texts.iter().map(|t| t.chars().count());
+    Ok(InferredColumn::new(
+        name,
+        "STRING",
+        vec![length_range(lengths, 1)],
+    ))
+}
+
+/// A type every value has the shape of.
+fn infer_shape(name: &str, texts: &[&str]) -> Option<InferredColumn> {
+    let all = |f: fn(&str) -> bool| texts.iter().all(|t| f(t));
+    let plain = |ty: &str| Some(InferredColumn::new(name, ty, Vec::new()));
+    if all(|t| t.len() == 36 && Uuid::parse_str(t).is_ok()) {
+        return plain("UUID");
+    }
+    if all(|t| email_re().is_match(t)) {
+        return plain("EMAIL");
+    }
+    if all(|t| t.starts_with("http://") || t.starts_with("https://")) {
+        return plain("URL");
+    }
+    if all(|t| t.parse::<Ipv4Addr>().is_ok()) {
+        return plain("IPV4");
+    }
+    if all(|t| t.contains(':') && t.parse::<Ipv6Addr>().is_ok()) {
+        return plain("IPV6");
+    }
+    if all(|t| mac_re().is_match(t)) {
+        return plain("MAC_ADDRESS");
+    }
+    if let Some(days) = texts
+        .iter()
+        .map(|t| parse_date(t))
+        .collect::<Option<Vec<_>>>()
+    {
+        let (lo, hi) = (days.iter().min()?, days.iter().max()?);
+        return Some(InferredColumn::new(
+            name,
+            "DATE",
+            vec![format!(
+                "{}..{}",
+                lo.format("%Y-%m-%d"),
+                hi.format("%Y-%m-%d")
+            )],
+        ));
+    }
+    if let Some(times) = texts
+        .iter()
+        .map(|t| parse_date_time(t))
+        .collect::<Option<Vec<_>>>()
+    {
+        let at = |t: &NaiveDateTime| t.format("%Y-%m-%dT%H:%M:%S").to_string();
+        let (lo, hi) = (times.iter().min()?, times.iter().max()?);
+        return Some(InferredColumn::new(
+            name,
+            "DATE_TIME",
+            vec![format!("{}..{}", at(lo), at(hi))],
+        ));
+    }
+    if all(|t| NaiveTime::parse_from_str(t, "%H:%M:%S%.f").is_ok()) {
+        return plain("TIME");
+    }
+    // Digests and keys: long, even-length runs of hex digits.
+    if all(|t| t.len() >= 16 && t.len() % 2 == 0 && t.bytes().all(|b| b.is_ascii_hexdigit())) {
+        let bytes = texts.iter().map(|t| t.len() / 2);
+        return Some(InferredColumn::new(
+            name,
+            "HEX",
+            vec![length_range(bytes, 1)],
+        ));
+    }
+    None
+}
+
+/// An `ENUM` of the observed values, most frequent first, when there are at
+/// most `max_enum` of them and each shows up twice on average. Without
+/// `reuse_categories` the values are relabelled.
+fn infer_enum(name: &str, texts: &[&str], p: &Profiling) -> Option<InferredColumn> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for t in texts {
+        *counts.entry(t).or_default() += 1;
+        if counts.len() > p.max_enum {
+            return None;
+        }
+    }
+    if counts.len() * 2 > texts.len() {
+        return None;
+    }
+    // A trailing backslash would escape the closing quote.
+    if p.reuse_categories && counts.keys().any(|t| t.contains('\\')) {
+        return None;
+    }
+    let mut counts: Vec<(&str, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    let args = counts
+        .into_iter()
+        .enumerate()
+        .map(|(rank, (t, n))| {
+            let value = if p.reuse_categories {
+                t.to_string()
+            } else {
+                format!("{name}_{}", rank + 1)
+            };
+            format!("'{}'={n}", value.replace('\'', "\\'"))
+        })
+        .collect();
+    Some(InferredColumn::new(name, "ENUM", args))
+}
+
+/// The type a column name suggests. `STATE` stands for `STATE_ABBR` or
+/// `STATE_NAME`, picked from the values.
+fn name_hint(name: &str) -> Option<&'static str> {
+    let words = name_words(name);
+    let has = |w: &str| words.iter().any(|x| x == w);
+    let joined = words.concat();
+    let hint = if has("lat") || has("latitude") {
+        "LAT"
+    } else if has("lon") || has("lng") || has("longitude") {
+        "LON"
+    } else if joined.contains("firstname") || joined == "fname" || joined == "givenname" {
+        "FIRST_NAME"
+    } else if joined.contains("lastname")
+        || has("surname")
+        || joined == "lname"
+        || joined.contains("familyname")
+    {
+        "LAST_NAME"
+    } else if has("company") || has("employer") || has("organization") {
+        "COMPANY"
+    } else if has("industry") {
+        "INDUSTRY"
+    } else if has("job") || joined == "jobtitle" {
+        "JOB_TITLE"
+    } else if has("phone") || has("mobile") {
+        "PHONE"
+    } else if has("zip") || has("zipcode") || has("postal") || has("postcode") {
+        "ZIP_CODE"
+    } else if has("country") {
+        "COUNTRY_CODE"
+    } else if has("state") {
+        "STATE"
+    } else if has("email") {
+        "EMAIL"
+    } else if has("ssn") {
+        "SSN"
+    } else if has("iban") {
+        "IBAN"
+    } else if has("bic") || has("swift") {
+        "BIC"
+    } else if has("currency") {
+        "CURRENCY_CODE"
+    } else if joined.contains("creditcard") || joined.contains("cardnumber") {
+        "CREDIT_CARD"
+    } else if joined.contains("useragent") {
+        "USER_AGENT"
+    } else if has("domain") {
+        "DOMAIN"
+    } else if has("name") || joined == "fullname" {
+        "NAME"
+    } else {
+        return None;
+    };
+    Some(hint)
+}
+
+/// Lower-case words of a `snake_case`, `kebab-case` or `camelCase` name.
+fn name_words(name: &str) -> Vec<String> {
+    let mut words = vec![String::new()];
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if !c.is_ascii_alphanumeric() {
+            words.push(String::new());
+            prev_lower = false;
+            continue;
+        }
+        if c.is_ascii_uppercase() && prev_lower {
+            words.push(String::new());
+        }
+        prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        words
+            .last_mut()
+            .expect("never empty")
+            .push(c.to_ascii_lowercase());
+    }
+    words.retain(|w| !w.is_empty());
+    words
+}
+
+fn email_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^[^@\s]+@[^@\s]+\.[^@\s]+$").unwrap())
+}
+
+fn mac_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^([0-9A-Fa-f]{2}[:-]){5}[0-9A-Fa-f]{2}$").unwrap())
+}
+
+fn parse_date(text: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(text, "%Y-%m-%d").ok()
+}
+
+/// RFC 3339 timestamps in UTC, or ISO ones without an offset.
+fn parse_date_time(text: &str) -> Option<NaiveDateTime> {
+    DateTime::parse_from_rfc3339(text)
+        .map(|t| t.naive_utc())
+        .or_else(|_| NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M:%S%.f"))
+        .or_else(|_| NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f"))
+        .ok()
+}
+
+fn date_from_days(days: i64) -> NaiveDate {
+    NaiveDate::default() + chrono::Duration::days(days)
+}
+
+fn date_time_from(t: i64, unit: TimeUnit) -> NaiveDateTime {
+    let at = match unit {
+        TimeUnit::Nanoseconds => Some(DateTime::from_timestamp_nanos(t)),
+        TimeUnit::Microseconds => DateTime::from_timestamp_micros(t),
+        TimeUnit::Milliseconds => DateTime::from_timestamp_millis(t),
+    };
+    at.unwrap_or_default().naive_utc()
+}
+
+fn int_bounds(values: &Series) -> PolarsResult<(i64, i64)> {
+    let ints = values.cast(&DataType::Int64)?;
+    let ints = ints.i64()?;
+    Ok((ints.min().unwrap_or(0), ints.max().unwrap_or(0)))
+}
+
+fn float_bounds(values: &Series) -> PolarsResult<(f64, f64)> {
+    let floats = values.cast(&DataType::Float64)?;
+    let floats = floats.f64()?;
+    Ok((floats.min().unwrap_or(0.0), floats.max().unwrap_or(0.0)))
+}
+
+/// `lo..hi` for a `DECIMAL`, whose range must not be empty: a constant
+/// column widens to the next step of its scale.
+fn decimal_range(lo: f64, hi: f64, scale: usize) -> String {
+    let hi = if hi > lo {
+        hi
+    } else {
+        lo + 10f64.powi(-(scale as i32))
+    };
+    let round = |v: f64| {
+        let factor = 10f64.powi(scale as i32);
+        (v * factor).round() / factor
+    };
+    format!("{}..{}", round(lo), round(hi))
+}
+
+/// Digits after the point in the shortest rendering of `v`, capped.
+fn decimal_places(v: f64) -> usize {
+    let text = v.to_string();
+    text.split_once('.')
+        .map_or(0, |(_, frac)| frac.len())
+        .min(MAX_SCALE)
+}
+
+/// `n` when every length is `n`, else `lo..hi`; never below `floor`.
+fn length_range(lengths: impl Iterator<Item = usize>, floor: usize) -> String {
+    let (lo, hi) = lengths.fold((usize::MAX, 0), |(lo, hi), n| (lo.min(n), hi.max(n)));
+    let (lo, hi) = (lo.min(hi).max(floor), hi.max(floor));
+    if lo == hi {
+        lo.to_string()
+    } else {
+        format!("{lo}..{hi}")
+    }
+}
+
+/// `part / whole` to `places` decimal places, never rounded down to zero.
+fn ratio(part: usize, whole: usize, places: i32) -> f64 {
+    if part == 0 {
+        return 0.0;
+    }
+    let factor = 10f64.powi(places);
+    ((part as f64 / whole as f64 * factor).round() / factor).max(1.0 / factor)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::schema::parse_schema;
+    use crate::util::validate::check_columns;
+
+    fn infer(df: &DataFrame) -> Vec<String> {
+        let columns = infer_columns(df, &Profiling::infer(DEFAULT_MAX_ENUM)).unwrap();
+        let schema: Vec<String> = columns.iter().map(|c| c.to_schema_string()).collect();
+        // Whatever is inferred must be a schema synthtab accepts.
+        let columns = parse_schema(&schema.join(",")).unwrap();
+        check_columns(&columns, 10, true, chrono::NaiveDateTime::default()).unwrap();
+        schema
+    }
+
+    #[test]
+    fn numbers_keep_their_observed_ranges() {
+        let df = df! {
+            "id" => [1i64, 2, 3, 4],
+            "qty" => [5i32, -2, 9, 0],
+            "big" => [0i64, 1, 5_000_000_000, 7],
+            "amount" => [1.5f64, 20.25, 3.0, 9.75],
+            "flag" => [true, false, true, true],
+        }
+        .unwrap();
+        assert_eq!(
+            infer(&df),
+            [
+                "id:INT_INC(start=1)",
+                "qty:INT(-2..9)",
+                "big:BIGINT(0..5000000000)",
+                "amount:DECIMAL(1.5..20.25, scale=2)",
+                "flag:BOOLEAN(0.75)",
+            ]
+        );
+    }
+
+    #[test]
+    fn text_shapes_and_names_pick_semantic_types() {
+        let df = df! {
+            "user_id" => ["0b7f1a9e-3c55-4d7b-9d2e-8f4a6c1b2d3e", "5a1c8e2f-7b4d-4e6a-8c9f-1d2e3f4a5b6c"],
+            "contact" => ["ann@example.com", "bo@example.org"],
+            "seen" => ["2024-01-05", "2023-11-30"],
+            "at" => ["2024-01-05T10:00:00+00:00", "2024-01-06T11:30:00+00:00"],
+            "firstName" => ["Ann", "Bo"],
+            "state" => ["CA", "NY"],
+            "zip" => ["02134", "94105"],
+            "note" => ["a", "hello"],
+        }
+        .unwrap();
+        assert_eq!(
+            infer(&df),
+            [
+                "user_id:UUID",
+                "contact:EMAIL",
+                "seen:DATE(2023-11-30..2024-01-05)",
+                "at:DATE_TIME(2024-01-05T10:00:00..2024-01-06T11:30:00)",
+                "firstName:FIRST_NAME",
+                "state:STATE_ABBR",
+                "zip:ZIP_CODE",
+                "note:STRING(1..5)",
+            ]
+        );
+    }
+
+    #[test]
+    fn repeated_text_becomes_a_weighted_enum() {
+        let df = df! {
+            "plan" => ["pro", "free", "free", "it's", "free", "pro"],
+        }
+        .unwrap();
+        assert_eq!(infer(&df), ["plan:ENUM('free'=3, 'pro'=2, 'it\\'s'=1)"]);
+        let columns = infer_columns(&df, &Profiling::infer(2)).unwrap();
+        assert_eq!(columns[0].to_schema_string(), "plan:STRING(3..4)");
+    }
+
+    #[test]
+    fn nulls_become_a_null_ratio() {
+        let df = df! {
+            "score" => [Some(3i32), None, Some(7), Some(1)],
+            "empty" => [None::<&str>, None, None, None],
+        }
+        .unwrap();
+        assert_eq!(
+            infer(&df),
+            ["score:INT(1..7, null=0.25)", "empty:STRING(null=1)"]
+        );
+    }
+
+    #[test]
+    fn typed_temporal_binary_and_nested_columns() {
+        let day = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let days = DateChunked::from_naive_date("day", [day, day + chrono::Duration::days(9)]);
+        let blobs = Series::new("blob", [&b"\x01\x02"[..], &b"\x03\x04\x05\x06"[..]]);
+        let tags = Series::new(
+            "tags",
+            [Series::new("", ["a", "b"]), Series::new("", ["a"])],
+        );
+        let df = DataFrame::new(vec![days.into_series(), blobs, tags]).unwrap();
+        assert_eq!(
+            infer(&df),
+            [
+                "day:DATE(2024-03-01..2024-03-10)",
+                "blob:BINARY(2..4)",
+                "tags:LIST<STRING(1)>(1..2)",
+            ]
+        );
+    }
+
+    #[test]
+    fn statistical_profiles_fit_a_distribution() {
+        let bell = [40.0, 45.0, 50.0, 50.0, 50.0, 55.0, 60.0];
+        assert_eq!(
+            fitted_distribution(&bell).as_deref(),
+            Some("normal(50, 5.9761)")
+        );
+        let skewed = [1.0, 1.0, 1.0, 2.0, 2.0, 3.0, 5.0, 8.0, 20.0, 50.0];
+        assert!(fitted_distribution(&skewed)
+            .unwrap()
+            .starts_with("lognormal("));
+        let flat: Vec<f64> = (1..=100).map(f64::from).collect();
+        assert_eq!(fitted_distribution(&flat), None);
+        assert_eq!(fitted_distribution(&[7.0, 7.0, 7.0]), None);
+
+        let df = df! { "score" => [40i32, 45, 50, 50, 50, 55, 60] }.unwrap();
+        let columns = infer_columns(&df, &Profiling::mimic(DEFAULT_MIMIC_MAX_ENUM, false)).unwrap();
+        assert_eq!(
+            columns[0].to_schema_string(),
+            "score:INT(normal(50, 5.9761), clamp=40..60)"
+        );
+    }
+
+    #[test]
+    fn mimic_profiles_relabel_categories_unless_reused() {
+        let df = df! {
+            "plan" => [Some("pro"), Some("free"), Some("free"), None, Some("free"), Some("pro")],
+        }
+        .unwrap();
+        let hidden = infer_columns(&df, &Profiling::mimic(DEFAULT_MIMIC_MAX_ENUM, false)).unwrap();
+        assert_eq!(
+            hidden[0].to_schema_string(),
+            "plan:ENUM('plan_1'=3, 'plan_2'=2, null=0.1667)"
+        );
+        let reused = infer_columns(&df, &Profiling::mimic(DEFAULT_MIMIC_MAX_ENUM, true)).unwrap();
+        assert_eq!(
+            reused[0].to_schema_string(),
+            "plan:ENUM('free'=3, 'pro'=2, null=0.1667)"
+        );
+        reused[0].to_schema().unwrap();
+    }
+
+    #[test]
+    fn name_words_split_every_naming_style() {
+        assert_eq!(name_words("firstName"), ["first", "name"]);
+        assert_eq!(name_words("postal-code"), ["postal", "code"]);
+        assert_eq!(name_words("HOME_PHONE2"), ["home", "phone2"]);
+        assert_eq!(name_hint("statement"), None);
+        assert_eq!(name_hint("company_name"), Some("COMPANY"));
+        assert_eq!(name_hint("customer_name"), Some("NAME"));
+        assert_eq!(name_hint("workEmail"), Some("EMAIL"));
+    }
+}
@@ -5,6 +5,8 @@
 //! match PRD §6.3 exactly.
 
 use crate::util::dialect::Dialect;
+use crate::util::output::CsvFormat;
+use polars::prelude::*;
 
 /// File extension for the load-command output file per dialect.
 pub fn load_cmd_ext(dialect: Dialect) -> &'static str {
@@ -31,24 +33,43 @@ pub fn load_cmd_path(data_path: &str, dialect: Dialect) -> String {
     )
 }
 
-/// Emit the load command for `table` loading from `file`.
+/// Emit the load command for `table` loading `df` from `file`.
 ///
+/// CSV files are UTF-8, so MySQL and SQL Server are told so explicitly;
+/// their defaults depend on server settings and mangle non-Latin text.
 /// `is_parquet` switches BigQuery and Spark templates between CSV and Parquet
-/// variants. MySQL, Postgres, and SQL Server always use CSV templates.
-pub fn emit_load_cmd(table: &str, file: &str, dialect: Dialect, is_parquet: bool) -> String {
+/// variants. MySQL, Postgres, and SQL Server always use CSV templates. Every
+/// CSV template reads the null token of `csv` as NULL; SQL Server can only
+/// read empty fields that way, which `CsvFormat::for_target` ensures.
+pub fn emit_load_cmd(
+    table: &str,
+    file: &str,
+    dialect: Dialect,
+    is_parquet: bool,
+    df: &DataFrame,
+    csv: &CsvFormat,
+) -> String {
     match dialect {
         Dialect::Mysql => format!(
-            "LOAD DATA LOCAL INFILE '{}' INTO TABLE {} \
+            "LOAD DATA LOCAL INFILE '{}' INTO TABLE {} CHARACTER SET utf8mb4 \
              FIELDS TERMINATED BY ',' ENCLOSED BY '\"' \
-             LINES TERMINATED BY '\\n' IGNORE 1 ROWS;\n",
-            file, table
+             LINES TERMINATED BY '\\n' IGNORE 1 ROWS{};\n",
+            file,
+            table,
+            mysql_conversions(df, &csv.null_value)
+        ),
+        Dialect::Postgres if !csv.null_value.is_empty() => format!(
+            "\\copy {} FROM '{}' WITH (FORMAT csv, HEADER true, NULL {});\n",
+            table,
+            file,
+            sql_string(&csv.null_value)
         ),
         Dialect::Postgres => format!(
             "\\copy {} FROM '{}' WITH (FORMAT csv, HEADER true);\n",
             table, file
         ),
         Dialect::Sqlserver => format!(
-            "BULK INSERT {} FROM '{}' WITH (FORMAT = 'CSV', FIRSTROW = 2, KEEPIDENTITY);\n",
+            "BULK INSERT {} FROM '{}' WITH (FORMAT = 'CSV', CODEPAGE = '65001', FIRSTROW = 2, KEEPIDENTITY);\n",
             table, file
         ),
         Dialect::Bigquery => {
@@ -59,8 +80,10 @@ pub fn emit_load_cmd(table: &str, file: &str, dialect: Dialect, is_parquet: bool
                 )
             } else {
                 format!(
-                    "bq load --source_format=CSV --skip_leading_rows=1 dataset.{} {}\n",
-                    table, file
+                    "bq load --source_format=CSV --skip_leading_rows=1{} dataset.{} {}\n",
+                    bq_null_marker(&csv.null_value),
+                    table,
+                    file
                 )
             }
         }
@@ -72,21 +95,98 @@ pub fn emit_load_cmd(table: &str, file: &str, dialect: Dialect, is_parquet: bool
                 )
             } else {
                 format!(
-                    "spark.read.option(\"header\", True).csv(\"{}\").write.saveAsTable(\"{}\")\n",
-                    file, table
+                    "spark.read.option(\"header\", True){}.csv(\"{}\").write.saveAsTable(\"{}\")\n",
+                    spark_null_value(&csv.null_value),
+                    file,
+                    table
                 )
             }
         }
     }
 }
 
+/// ` (a, @b) SET b = ...` for the columns `LOAD DATA` cannot store as
+/// read; empty when every column loads as is.
+fn mysql_conversions(df: &DataFrame, null_token: &str) -> String {
+    let mut fields = Vec::new();
+    let mut sets = Vec::new();
+    for s in df.get_columns() {
+        match mysql_value(s, null_token) {
+            Some(value) => {
+                fields.push(format!("@{}", s.name()));
+                sets.push(format!("{} = {value}", s.name()));
+            }
+            None => fields.push(s.name().to_string()),
+        }
+    }
+    if sets.is_empty() {
+        return String::new();
+    }
+    format!(" ({}) SET {}", fields.join(", "), sets.join(", "))
+}
+
+/// The value to store for `s`, read into `@name`, or `None` to store the
+/// field as read. `LOAD DATA` only reads `\N` as NULL; any other null
+/// token would load as `''`, or `0` in a number or date column. Nor does it
+/// read `true` as 1 for a `TINYINT(1)` column, or hex as bytes.
+fn mysql_value(s: &Series, null_token: &str) -> Option<String> {
+    let field = format!("@{}", s.name());
+    let read = match s.null_count() {
+        0 => field.clone(),
+        // MySQL strings treat a backslash as an escape.
+        _ => format!(
+            "NULLIF({field}, {})",
+            sql_string(&null_token.replace('\\', "\\\\"))
+        ),
+    };
+    let value = match s.dtype() {
+        DataType::Boolean => format!("({read} = 'true')"),
+        DataType::Binary => format!("UNHEX({read})"),
+        _ => read,
+    };
+    (value != field).then_some(value)
+}
+
+/// ` --null_marker='TOKEN'` for a custom null token; `bq` reads empty
+/// fields as NULL by default.
+fn bq_null_marker(null_token: &str) -> String {
+    match null_token {
+        "" => String::new(),
+        token => format!(" --null_marker='{}'", token.replace('\'', "'\\''")),
+    }
+}
+
+/// `.option("nullValue", "TOKEN")` for a custom null token; Spark reads
+/// empty fields as NULL by default.
+fn spark_null_value(null_token: &str) -> String {
+    match null_token {
+        "" => String::new(),
+        token => format!(
+            ".option(\"nullValue\", \"{}\")",
+            token.replace('\\', "\\\\").replace('"', "\\\"")
+        ),
+    }
+}
+
+/// `text` as a standard single-quoted SQL string literal.
+fn sql_string(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+#[cfg(test)]
 mod test {
     #![allow(unused_imports, dead_code)]
     use super::*;
 
+    /// The command for a frame without nulls, in the default CSV format.
+    fn load(table: &str, file: &str, dialect: Dialect, is_parquet: bool) -> String {
+        let df = df!("id" => [1, 2]).unwrap();
+        emit_load_cmd(table, file, dialect, is_parquet, &df, &CsvFormat::default())
+    }
+
     #[test]
     fn mysql_csv_load_command() {
-        let cmd = emit_load_cmd("users", "users.csv", Dialect::Mysql, false);
+        let cmd = load("users", "users.csv", Dialect::Mysql, false);
         assert!(
             cmd.contains("LOAD DATA LOCAL INFILE 'users.csv'"),
             "got: {cmd}"
@@ -95,23 +195,34 @@ mod test {
         assert!(cmd.contains("IGNORE 1 ROWS"), "got: {cmd}");
     }
 
+    #[test]
+    fn mysql_and_sqlserver_read_csv_as_utf8() {
+        let cmd = load("users", "users.csv", Dialect::Mysql, false);
+        assert!(
+            cmd.contains("INTO TABLE users CHARACTER SET utf8mb4 FIELDS"),
+            "got: {cmd}"
+        );
+        let cmd = load("users", "users.csv", Dialect::Sqlserver, false);
+        assert!(cmd.contains("CODEPAGE = '65001'"), "got: {cmd}");
+    }
+
     #[test]
     fn postgres_csv_load_command() {
-        let cmd = emit_load_cmd("orders", "orders.csv", Dialect::Postgres, false);
+        let cmd = load("orders", "orders.csv", Dialect::Postgres, false);
         assert!(cmd.contains("\\copy orders"), "got: {cmd}");
         assert!(cmd.contains("FORMAT csv, HEADER true"), "got: {cmd}");
     }
 
     #[test]
     fn sqlserver_csv_load_command() {
-        let cmd = emit_load_cmd("t", "t.csv", Dialect::Sqlserver, false);
+        let cmd = load("t", "t.csv", Dialect::Sqlserver, false);
         assert!(cmd.contains("BULK INSERT t"), "got: {cmd}");
         assert!(cmd.contains("FIRSTROW = 2"), "got: {cmd}");
     }
 
     #[test]
     fn bigquery_csv_load_command() {
-        let cmd = emit_load_cmd("events", "events.csv", Dialect::Bigquery, false);
+        let cmd = load("events", "events.csv", Dialect::Bigquery, false);
         assert!(cmd.contains("--source_format=CSV"), "got: {cmd}");
         assert!(cmd.contains("--skip_leading_rows=1"), "got: {cmd}");
         assert!(cmd.contains("dataset.events"), "got: {cmd}");
@@ -119,14 +230,14 @@ mod test {
 
     #[test]
     fn bigquery_parquet_load_command() {
-        let cmd = emit_load_cmd("events", "events.parquet", Dialect::Bigquery, true);
+        let cmd = load("events", "events.parquet", Dialect::Bigquery, true);
         assert!(cmd.contains("--source_format=PARQUET"), "got: {cmd}");
         assert!(!cmd.contains("skip_leading_rows"), "got: {cmd}");
     }
 
     #[test]
     fn spark_csv_load_command() {
-        let cmd = emit_load_cmd("logs", "logs.csv", Dialect::Spark, false);
+        let cmd = load("logs", "logs.csv", Dialect::Spark, false);
         assert!(
             cmd.contains("option(\"header\", True).csv(\"logs.csv\")"),
             "got: {cmd}"
@@ -134,9 +245,30 @@ mod test {
         assert!(cmd.contains("saveAsTable(\"logs\")"), "got: {cmd}");
     }
 
+    #[test]
+    fn bigquery_and_spark_read_a_custom_null_token() {
+        let df = df!("id" => [1, 2]).unwrap();
+        let csv = CsvFormat {
+            null_value: "it's \"NA\"".to_string(),
+            ..CsvFormat::default()
+        };
+        let cmd = emit_load_cmd("t", "t.csv", Dialect::Bigquery, false, &df, &csv);
+        assert!(
+            cmd.contains("--skip_leading_rows=1 --null_marker='it'\\''s \"NA\"' dataset.t"),
+            "got: {cmd}"
+        );
+        let cmd = emit_load_cmd("t", "t.csv", Dialect::Spark, false, &df, &csv);
+        assert!(
+            cmd.contains(".option(\"nullValue\", \"it's \\\"NA\\\"\").csv("),
+            "got: {cmd}"
+        );
+        let cmd = emit_load_cmd("t", "t.parquet", Dialect::Spark, true, &df, &csv);
+        assert!(!cmd.contains("nullValue"), "got: {cmd}");
+    }
+
     #[test]
     fn spark_parquet_load_command() {
-        let cmd = emit_load_cmd("logs", "logs.parquet", Dialect::Spark, true);
+        let cmd = load("logs", "logs.parquet", Dialect::Spark, true);
         assert!(cmd.contains("read.parquet(\"logs.parquet\")"), "got: {cmd}");
         assert!(cmd.contains("saveAsTable(\"logs\")"), "got: {cmd}");
     }
@@ -166,4 +298,72 @@ mod test {
         assert_eq!(load_cmd_ext(Dialect::Bigquery), "sh");
         assert_eq!(load_cmd_ext(Dialect::Spark), "py");
     }
+
+    #[test]
+    fn mysql_reads_booleans_as_one_or_zero() {
+        let df = df!("id" => [1, 2], "ok" => [true, false], "opt" => [Some(true), None]).unwrap();
+        let cmd = emit_load_cmd(
+            "u",
+            "u.csv",
+            Dialect::Mysql,
+            false,
+            &df,
+            &CsvFormat::default(),
+        );
+        assert!(
+            cmd.ends_with(
+                "(id, @ok, @opt) SET ok = (@ok = 'true'), opt = (NULLIF(@opt, '') = 'true');\n"
+            ),
+            "got: {cmd}"
+        );
+    }
+
+    #[test]
+    fn mysql_decodes_binary_hex() {
+        let blob = Series::new("blob", [Some(&b"\x01"[..]), None]);
+        let df = DataFrame::new(vec![blob]).unwrap();
+        let cmd = emit_load_cmd(
+            "u",
+            "u.csv",
+            Dialect::Mysql,
+            false,
+            &df,
+            &CsvFormat::default(),
+        );
+        assert!(
+            cmd.ends_with("(@blob) SET blob = UNHEX(NULLIF(@blob, ''));\n"),
+            "got: {cmd}"
+        );
+    }
+
+    #[test]
+    fn nulls_load_as_null_in_mysql_and_postgres() {
+        let df = df!("id" => [1, 2], "email" => [Some("a@b.c"), None], "age" => [None, Some(3)])
+            .unwrap();
+        let cmd = emit_load_cmd(
+            "u",
+            "u.csv",
+            Dialect::Mysql,
+            false,
+            &df,
+            &CsvFormat::default(),
+        );
+        assert!(
+            cmd.ends_with(
+                "IGNORE 1 ROWS (id, @email, @age) SET email = NULLIF(@email, ''), age = NULLIF(@age, '');\n"
+            ),
+            "got: {cmd}"
+        );
+        let csv = CsvFormat {
+            null_value: "it's \\N".to_string(),
+            ..CsvFormat::default()
+        };
+        let cmd = emit_load_cmd("u", "u.csv", Dialect::Mysql, false, &df, &csv);
+        assert!(cmd.contains("NULLIF(@email, 'it''s \\\\N')"), "got: {cmd}");
+        let cmd = emit_load_cmd("u", "u.csv", Dialect::Postgres, false, &df, &csv);
+        assert!(
+            cmd.contains("HEADER true, NULL 'it''s \\N');"),
+            "got: {cmd}"
+        );
+    }
 }
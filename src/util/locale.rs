@@ -0,0 +1,128 @@
+//! Locale selection for the text generators.
+//!
+//! `--locale` sets the run default and a `locale=` option overrides it per
+//! column (`name:NAME(locale=ja_JP)`). Only the types in `LOCALIZED_TYPES`
+//! read it; the rest produce the same data in every locale.
+
+use clap::ValueEnum;
+use std::fmt;
+
+/// A data set shipped with the `fake` crate. Fakers a locale does not
+/// translate fall back to the English data.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Locale {
+    #[default]
+    #[value(name = "en")]
+    En,
+    #[value(name = "fr_FR", alias = "fr-FR")]
+    FrFr,
+    #[value(name = "pt_BR", alias = "pt-BR")]
+    PtBr,
+    #[value(name = "ja_JP", alias = "ja-JP")]
+    JaJp,
+    #[value(name = "zh_CN", alias = "zh-CN")]
+    ZhCn,
+    #[value(name = "zh_TW", alias = "zh-TW")]
+    ZhTw,
+    #[value(name = "ar_SA", alias = "ar-SA")]
+    ArSa,
+}
+
+/// Types whose values follow the column's locale.
+pub const LOCALIZED_TYPES: &[&str] = &[
+    "NAME",
+    "FIRST_NAME",
+    "LAST_NAME",
+    "PHONE",
+    "ZIP_CODE",
+    "STATE_NAME",
+    "STATE_ABBR",
+    "DOMAIN",
+    "URL",
+    "COMPANY",
+    "JOB_TITLE",
+];
+
+const ALL: &[Locale] = &[
+    Locale::En,
+    Locale::FrFr,
+    Locale::PtBr,
+    Locale::JaJp,
+    Locale::ZhCn,
+    Locale::ZhTw,
+    Locale::ArSa,
+];
+
+impl Locale {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::FrFr => "fr_FR",
+            Locale::PtBr => "pt_BR",
+            Locale::JaJp => "ja_JP",
+            Locale::ZhCn => "zh_CN",
+            Locale::ZhTw => "zh_TW",
+            Locale::ArSa => "ar_SA",
+        }
+    }
+
+    /// Parse a `locale=` option. Case-insensitive, and `-` works in place
+    /// of `_`, so `fr-fr` and `fr_FR` are the same locale.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let wanted = s.replace('-', "_");
+        ALL.iter()
+            .copied()
+            .find(|l| l.as_str().eq_ignore_ascii_case(&wanted))
+            .ok_or_else(|| {
+                let names: Vec<&str> = ALL.iter().map(|l| l.as_str()).collect();
+                format!("unknown locale '{s}'; supported: {}", names.join(", "))
+            })
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Call the `fake` faker `$faker` with the data set of `$locale`, e.g.
+/// `localized!(locale, Name, rng)`. Extra arguments follow the locale.
+macro_rules! localized {
+    ($locale:expr, $faker:path, $rng:expr $(, $arg:expr)*) => {{
+        use fake::locales::{AR_SA, EN, FR_FR, JA_JP, PT_BR, ZH_CN, ZH_TW};
+        use $crate::util::locale::Locale;
+        match $locale {
+            Locale::En => $faker(EN $(, $arg)*).fake_with_rng($rng),
+            Locale::FrFr => $faker(FR_FR $(, $arg)*).fake_with_rng($rng),
+            Locale::PtBr => $faker(PT_BR $(, $arg)*).fake_with_rng($rng),
+            Locale::JaJp => $faker(JA_JP $(, $arg)*).fake_with_rng($rng),
+            Locale::ZhCn => $faker(ZH_CN $(, $arg)*).fake_with_rng($rng),
+            Locale::ZhTw => $faker(ZH_TW $(, $arg)*).fake_with_rng($rng),
+            Locale::ArSa => $faker(AR_SA $(, $arg)*).fake_with_rng($rng),
+        }
+    }};
+}
+pub(crate) use localized;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_accepts_any_case_and_either_separator() {
+        assert_eq!(Locale::parse("ja_JP").unwrap(), Locale::JaJp);
+        assert_eq!(Locale::parse("fr-fr").unwrap(), Locale::FrFr);
+        assert_eq!(Locale::parse("EN").unwrap(), Locale::En);
+        for locale in ALL {
+            assert_eq!(Locale::parse(locale.as_str()).unwrap(), *locale);
+        }
+    }
+
+    #[test]
+    fn parse_lists_the_supported_locales() {
+        let err = Locale::parse("de_DE").unwrap_err();
+        assert!(err.starts_with("unknown locale 'de_DE'"), "{err}");
+        assert!(err.contains("en, fr_FR, pt_BR, ja_JP"), "{err}");
+    }
+}
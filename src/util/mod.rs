@@ -1,12 +1,24 @@
 pub mod dataframe;
 pub mod ddl;
+pub mod derived;
 pub mod dialect;
+pub mod distribution;
 pub mod erd_ast;
+pub mod expr;
 pub mod fake;
 pub mod generator;
+pub mod infer;
 pub mod load_cmd;
+pub mod locale;
+pub mod modifier;
 pub mod multi_file_sink;
 pub mod output;
 pub mod parser;
+pub mod rng;
 pub mod scanner;
 pub mod schema;
+pub mod schema_file;
+pub mod template;
+pub mod temporal;
+pub mod timeseries;
+pub mod validate;
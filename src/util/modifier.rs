@@ -0,0 +1,634 @@
+//! Column modifier grammar shared by `-s` strings and `--schema-file`.
+//!
+//! A modifier is the parenthesised argument list after a type name:
+//!
+//! ```text
+//! price:DECIMAL(0..500, scale=2)
+//! status:ENUM('new', 'paid', 'shipped')
+//! score:INT_RNG:(-15-23)            # legacy form, still accepted
+//! ```
+//!
+//! Arguments are positional values or `key=value` options. A value is a
+//! number, a quoted string (`'…'` or `"…"`, which may contain commas, colons
+//! and parentheses), a bare word, a `lo..hi` range, a nested call such as
+//! `normal(50, 10)`, or a weighted choice such as `'active'=70`. This module
+//! only checks the syntax; which arguments a type accepts is decided by the
+//! generator that consumes them.
+
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModifierError {
+    pub message: String,
+}
+
+impl fmt::Display for ModifierError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for ModifierError {}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Int(i64),
+    Float(f64),
+    /// A quoted string, with the quotes removed.
+    Str(String),
+    /// An unquoted word that is not a number, e.g. `now` or `2020-01-01`.
+    Ident(String),
+    Range(Box<Value>, Box<Value>),
+    Call(String, Vec<Arg>),
+    /// `'value'=weight`: a quoted string or number paired with a weight.
+    Weighted(Box<Value>, Box<Value>),
+}
+
+impl Value {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Int(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// Integers widen to `f64`, so `0..500` and `0.0..500.0` both work.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Int(i) => Some(*i as f64),
+            Value::Float(f) => Some(*f),
+            _ => None,
+        }
+    }
+
+    pub fn as_range(&self) -> Option<(&Value, &Value)> {
+        match self {
+            Value::Range(lo, hi) => Some((lo, hi)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Int(i) => write!(f, "{i}"),
+            Value::Float(x) => write!(f, "{x}"),
+            // Read back by the lexer, which takes `\'` as a literal quote.
+            Value::Str(s) => write!(f, "'{}'", s.replace('\'', "\\'")),
+            Value::Ident(s) => write!(f, "{s}"),
+            Value::Range(lo, hi) => write!(f, "{lo}..{hi}"),
+            Value::Weighted(v, w) => write!(f, "{v}={w}"),
+            Value::Call(name, args) => {
+                write!(f, "{name}(")?;
+                for (i, a) in args.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{a}")?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Arg {
+    /// `Some` for `key=value` options, `None` for positional arguments.
+    pub key: Option<String>,
+    pub value: Value,
+}
+
+impl fmt::Display for Arg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.key {
+            Some(k) => write!(f, "{k}={}", self.value),
+            None => write!(f, "{}", self.value),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Modifier {
+    /// Source text between the outer parentheses, kept for error messages.
+    pub raw: String,
+    pub args: Vec<Arg>,
+}
+
+impl Modifier {
+    pub fn positional(&self) -> Vec<&Value> {
+        self.args
+            .iter()
+            .filter(|a| a.key.is_none())
+            .map(|a| &a.value)
+            .collect()
+    }
+
+    pub fn option(&self, key: &str) -> Option<&Value> {
+        self.args
+            .iter()
+            .find(|a| a.key.as_deref() == Some(key))
+            .map(|a| &a.value)
+    }
+
+    /// Reject any `key=value` option not in `allowed`, and repeated keys.
+    pub fn check_options(&self, allowed: &[&str]) -> Result<(), String> {
+        let mut seen: Vec<&str> = Vec::new();
+        for key in self.args.iter().filter_map(|a| a.key.as_deref()) {
+            if !allowed.contains(&key) {
+                return Err(if allowed.is_empty() {
+                    format!("unknown option '{key}'; this type takes no options")
+                } else {
+                    format!(
+                        "unknown option '{key}'; expected one of: {}",
+                        allowed.join(", ")
+                    )
+                });
+            }
+            if seen.contains(&key) {
+                return Err(format!("option '{key}' given more than once"));
+            }
+            seen.push(key);
+        }
+        Ok(())
+    }
+
+    /// Reject more than `max` positional arguments.
+    pub fn check_positional(&self, max: usize) -> Result<(), String> {
+        let n = self.positional().len();
+        if n > max {
+            return Err(format!(
+                "expected at most {max} positional argument{}, got {n}",
+                if max == 1 { "" } else { "s" }
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Parse a full modifier, parentheses included: `(0..500, scale=2)`.
+pub fn parse_modifier(text: &str) -> Result<Modifier, ModifierError> {
+    let text = text.trim();
+    let inner = text
+        .strip_prefix('(')
+        .and_then(|t| t.strip_suffix(')'))
+        .ok_or_else(|| ModifierError {
+            message: format!("malformed modifier '{text}': expected '(...)'"),
+        })?;
+
+    // `(lo - hi)` with spaces predates the grammar; keep accepting it.
+    if let Some((lo, hi)) = legacy_range(inner) {
+        return Ok(Modifier {
+            raw: inner.to_string(),
+            args: vec![Arg {
+                key: None,
+                value: Value::Range(Box::new(Value::Int(lo)), Box::new(Value::Int(hi))),
+            }],
+        });
+    }
+
+    let tokens = tokenize(text).map_err(|m| ModifierError {
+        message: format!("malformed modifier '{text}': {m}"),
+    })?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let args = parser
+        .parenthesised_args()
+        .and_then(|args| match parser.peek() {
+            None => Ok(args),
+            Some(t) => Err(format!("unexpected {} after closing ')'", t.describe())),
+        })
+        .map_err(|m| ModifierError {
+            message: format!("malformed modifier '{text}': {m}"),
+        })?;
+
+    Ok(Modifier {
+        raw: inner.to_string(),
+        args,
+    })
+}
+
+/// Keep the text between the outer parentheses as-is, with no arguments. For
+/// types whose argument is not in this grammar, such as `EXPR(qty * price)`.
+pub fn raw_modifier(text: &str) -> Result<Modifier, ModifierError> {
+    let text = text.trim();
+    let inner = text
+        .strip_prefix('(')
+        .and_then(|t| t.strip_suffix(')'))
+        .ok_or_else(|| ModifierError {
+            message: format!("malformed modifier '{text}': expected '(...)'"),
+        })?;
+    Ok(Modifier {
+        raw: inner.trim().to_string(),
+        args: Vec::new(),
+    })
+}
+
+fn legacy_range(inner: &str) -> Option<(i64, i64)> {
+    let inner = inner.trim();
+    let (sign, rest) = match inner.strip_prefix('-') {
+        Some(r) => (-1, r),
+        None => (1, inner),
+    };
+    let (lo, hi) = rest.split_once('-')?;
+    let lo = lo.trim();
+    let hi = hi.trim();
+    if lo.is_empty() || !lo.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let hi_digits = hi.strip_prefix('-').unwrap_or(hi);
+    if hi_digits.is_empty() || !hi_digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some((sign * lo.parse::<i64>().ok()?, hi.parse().ok()?))
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    LParen,
+    RParen,
+    Comma,
+    Eq,
+    Quoted(String),
+    Bare(String),
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::LParen => "'('".to_string(),
+            Token::RParen => "')'".to_string(),
+            Token::Comma => "','".to_string(),
+            Token::Eq => "'='".to_string(),
+            Token::Quoted(s) => format!("string '{s}'"),
+            Token::Bare(s) => format!("'{s}'"),
+        }
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' | ')' | ',' | '=' => {
+                chars.next();
+                tokens.push(match c {
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    ',' => Token::Comma,
+                    _ => Token::Eq,
+                });
+            }
+            '\'' | '"' => {
+                chars.next();
+                let mut s = String::new();
+                let mut closed = false;
+                while let Some(ch) = chars.next() {
+                    if ch == c {
+                        closed = true;
+                        break;
+                    }
+                    // `\'` inside '…' is a literal quote; any other backslash
+                    // is kept so regex escapes like `\d` survive untouched.
+                    if ch == '\\' && chars.peek() == Some(&c) {
+                        s.push(c);
+                        chars.next();
+                    } else {
+                        s.push(ch);
+                    }
+                }
+                if !closed {
+                    return Err(format!("unterminated string starting with {c}{s}"));
+                }
+                tokens.push(Token::Quoted(s));
+            }
+            _ => {
+                let mut s = String::new();
+                while let Some(&ch) = chars.peek() {
+                    if ch.is_whitespace() || "(),='\"".contains(ch) {
+                        break;
+                    }
+                    s.push(ch);
+                    chars.next();
+                }
+                tokens.push(Token::Bare(s));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let t = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        t
+    }
+
+    fn expect(&mut self, want: Token) -> Result<(), String> {
+        match self.next() {
+            Some(ref t) if *t == want => Ok(()),
+            Some(t) => Err(format!(
+                "expected {}, found {}",
+                want.describe(),
+                t.describe()
+            )),
+            None => Err(format!("expected {}, found end of input", want.describe())),
+        }
+    }
+
+    /// `'(' [arg (',' arg)*] ')'`
+    fn parenthesised_args(&mut self) -> Result<Vec<Arg>, String> {
+        self.expect(Token::LParen)?;
+        let mut args = Vec::new();
+        if self.peek() == Some(&Token::RParen) {
+            self.next();
+            return Ok(args);
+        }
+        loop {
+            args.push(self.arg()?);
+            match self.next() {
+                Some(Token::Comma) => continue,
+                Some(Token::RParen) => return Ok(args),
+                Some(t) => return Err(format!("expected ',' or ')', found {}", t.describe())),
+                None => return Err("missing closing ')'".to_string()),
+            }
+        }
+    }
+
+    fn arg(&mut self) -> Result<Arg, String> {
+        match (self.tokens.get(self.pos), self.tokens.get(self.pos + 1)) {
+            (Some(Token::Bare(key)), Some(Token::Eq)) if is_identifier(key) => {
+                let key = key.clone();
+                self.pos += 2;
+                return Ok(Arg {
+                    key: Some(key),
+                    value: self.value()?,
+                });
+            }
+            // A quoted string or number before '=' is a weighted choice.
+            (Some(Token::Quoted(_)), Some(Token::Eq)) => {
+                let choice = self.value()?;
+                self.pos += 1;
+                return Ok(Arg {
+                    key: None,
+                    value: Value::Weighted(Box::new(choice), Box::new(self.value()?)),
+                });
+            }
+            (Some(Token::Bare(word)), Some(Token::Eq)) => {
+                let choice = atom(word);
+                if !matches!(choice, Value::Int(_) | Value::Float(_)) {
+                    return Err(format!("invalid option name '{word}'"));
+                }
+                self.pos += 2;
+                return Ok(Arg {
+                    key: None,
+                    value: Value::Weighted(Box::new(choice), Box::new(self.value()?)),
+                });
+            }
+            _ => {}
+        }
+        Ok(Arg {
+            key: None,
+            value: self.value()?,
+        })
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        match self.next() {
+            Some(Token::Quoted(s)) => Ok(Value::Str(s)),
+            Some(Token::Bare(word)) => {
+                if self.peek() == Some(&Token::LParen) && is_identifier(&word) {
+                    let args = self.parenthesised_args()?;
+                    return Ok(Value::Call(word, args));
+                }
+                bare_value(&word)
+            }
+            Some(t) => Err(format!("expected a value, found {}", t.describe())),
+            None => Err("expected a value, found end of input".to_string()),
+        }
+    }
+}
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn bare_value(word: &str) -> Result<Value, String> {
+    if let Some((lo, hi)) = word.split_once("..") {
+        if lo.is_empty() || hi.is_empty() {
+            return Err(format!("range '{word}' needs both ends"));
+        }
+        return Ok(Value::Range(Box::new(atom(lo)), Box::new(atom(hi))));
+    }
+    if let Some((lo, hi)) = legacy_range(word) {
+        return Ok(Value::Range(
+            Box::new(Value::Int(lo)),
+            Box::new(Value::Int(hi)),
+        ));
+    }
+    Ok(atom(word))
+}
+
+fn atom(word: &str) -> Value {
+    if let Ok(i) = word.parse::<i64>() {
+        return Value::Int(i);
+    }
+    // Only numeric-looking words become floats; `inf`/`nan` stay words.
+    let numeric_start = word
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.'));
+    if numeric_start {
+        if let Ok(f) = word.parse::<f64>() {
+            return Value::Float(f);
+        }
+    }
+    Value::Ident(word.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn int_range(lo: i64, hi: i64) -> Value {
+        Value::Range(Box::new(Value::Int(lo)), Box::new(Value::Int(hi)))
+    }
+
+    #[test]
+    fn legacy_range_is_accepted() {
+        let m = parse_modifier("(0-10)").unwrap();
+        assert_eq!(m.positional(), vec![&int_range(0, 10)]);
+    }
+
+    #[test]
+    fn legacy_negative_range_is_accepted() {
+        let m = parse_modifier("(-10-10)").unwrap();
+        assert_eq!(m.positional(), vec![&int_range(-10, 10)]);
+        let m = parse_modifier("(-15--5)").unwrap();
+        assert_eq!(m.positional(), vec![&int_range(-15, -5)]);
+    }
+
+    #[test]
+    fn legacy_range_tolerates_spaces() {
+        let m = parse_modifier("(1-  100)").unwrap();
+        assert_eq!(m.positional(), vec![&int_range(1, 100)]);
+    }
+
+    #[test]
+    fn dotted_range_and_option() {
+        let m = parse_modifier("(0..500, scale=2)").unwrap();
+        assert_eq!(m.positional(), vec![&int_range(0, 500)]);
+        assert_eq!(m.option("scale"), Some(&Value::Int(2)));
+        assert_eq!(m.raw, "0..500, scale=2");
+    }
+
+    #[test]
+    fn float_and_word_range_ends() {
+        let m = parse_modifier("(0.5..9.75)").unwrap();
+        let (lo, hi) = m.positional()[0].as_range().unwrap();
+        assert_eq!(lo.as_f64(), Some(0.5));
+        assert_eq!(hi.as_f64(), Some(9.75));
+
+        let m = parse_modifier("(2020-01-01..now)").unwrap();
+        let (lo, hi) = m.positional()[0].as_range().unwrap();
+        assert_eq!(lo, &Value::Ident("2020-01-01".to_string()));
+        assert_eq!(hi, &Value::Ident("now".to_string()));
+    }
+
+    #[test]
+    fn quoted_strings_keep_commas_colons_and_parens() {
+        let m = parse_modifier(r#"('a,b', "c:d", 'e(f)')"#).unwrap();
+        let vals: Vec<&str> = m.positional().iter().filter_map(|v| v.as_str()).collect();
+        assert_eq!(vals, vec!["a,b", "c:d", "e(f)"]);
+    }
+
+    #[test]
+    fn quoted_strings_keep_regex_backslashes() {
+        let m = parse_modifier(r"('[A-Z]{3}-\d{6}', 'it\'s')").unwrap();
+        assert_eq!(m.positional()[0].as_str(), Some(r"[A-Z]{3}-\d{6}"));
+        assert_eq!(m.positional()[1].as_str(), Some("it's"));
+    }
+
+    #[test]
+    fn quoted_strings_display_as_they_parse() {
+        let m = parse_modifier(r#"("it's", 'a\'b', '\d{3}', "x\'")"#).unwrap();
+        let shown = m.args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        assert_eq!(shown, [r"'it\'s'", r"'a\'b'", r"'\d{3}'", r"'x\\''"]);
+        let again = parse_modifier(&format!("({})", shown.join(", "))).unwrap();
+        assert_eq!(again.args, m.args);
+    }
+
+    #[test]
+    fn nested_calls_parse() {
+        let m = parse_modifier("(normal(50, 10), clamp=0..100)").unwrap();
+        assert_eq!(
+            m.positional()[0],
+            &Value::Call(
+                "normal".to_string(),
+                vec![
+                    Arg {
+                        key: None,
+                        value: Value::Int(50)
+                    },
+                    Arg {
+                        key: None,
+                        value: Value::Int(10)
+                    },
+                ]
+            )
+        );
+        assert_eq!(m.option("clamp"), Some(&int_range(0, 100)));
+    }
+
+    #[test]
+    fn weighted_choices_parse() {
+        let m = parse_modifier("('active'=70, 'closed'=0.5, 3=1)").unwrap();
+        let weighted = |v: Value, w: Value| Value::Weighted(Box::new(v), Box::new(w));
+        assert_eq!(
+            m.positional(),
+            vec![
+                &weighted(Value::Str("active".to_string()), Value::Int(70)),
+                &weighted(Value::Str("closed".to_string()), Value::Float(0.5)),
+                &weighted(Value::Int(3), Value::Int(1)),
+            ]
+        );
+        assert_eq!(m.args[0].to_string(), "'active'=70");
+    }
+
+    #[test]
+    fn empty_parens_have_no_args() {
+        assert!(parse_modifier("()").unwrap().args.is_empty());
+    }
+
+    #[test]
+    fn malformed_modifiers_are_rejected() {
+        for bad in [
+            "0..5", "(0..5", "(0..5))", "(a b)", "(,)", "(x=)", "('open)", "(..5)", "('a'=)",
+            "(a-b=2)",
+        ] {
+            let err = parse_modifier(bad).unwrap_err();
+            assert!(
+                err.message.starts_with("malformed modifier"),
+                "{bad}: {}",
+                err.message
+            );
+        }
+    }
+
+    #[test]
+    fn raw_modifier_keeps_inner_text() {
+        let m = raw_modifier("( qty * unit_price )").unwrap();
+        assert_eq!(m.raw, "qty * unit_price");
+        assert!(m.args.is_empty());
+        assert!(raw_modifier("qty * unit_price").is_err());
+    }
+
+    #[test]
+    fn check_options_rejects_unknown_and_repeated_keys() {
+        let m = parse_modifier("(scale=2, sacle=3)").unwrap();
+        let err = m.check_options(&["scale"]).unwrap_err();
+        assert!(err.contains("unknown option 'sacle'"), "{err}");
+
+        let m = parse_modifier("(scale=2, scale=3)").unwrap();
+        let err = m.check_options(&["scale"]).unwrap_err();
+        assert!(err.contains("more than once"), "{err}");
+    }
+
+    #[test]
+    fn check_positional_limits_count() {
+        let m = parse_modifier("(1, 2)").unwrap();
+        assert!(m.check_positional(2).is_ok());
+        assert!(m.check_positional(1).is_err());
+    }
+
+    #[test]
+    fn display_round_trips_structure() {
+        let m = parse_modifier("(0..5, 'x', f(1), k=v)").unwrap();
+        let shown: Vec<String> = m.args.iter().map(|a| a.to_string()).collect();
+        assert_eq!(shown, vec!["0..5", "'x'", "f(1)", "k=v"]);
+    }
+}
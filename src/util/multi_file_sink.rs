@@ -1,7 +1,7 @@
 //! Per-entity output sink for ER mode. Writes one file per `(name, DataFrame)`
 //! pair under a single output directory.
 
-use crate::util::output::{CSVFile, Output, ParquetFile};
+use crate::util::output::{CSVFile, CsvFormat, Output, ParquetFile};
 use polars::frame::DataFrame;
 use std::error::Error;
 use std::path::PathBuf;
@@ -15,17 +15,27 @@ pub enum SinkFormat {
 pub struct MultiFileSink {
     pub out_dir: PathBuf,
     pub format: SinkFormat,
+    /// Null and temporal rendering for CSV output.
+    pub csv_format: CsvFormat,
 }
 
 impl MultiFileSink {
-    pub fn new(out_dir: PathBuf, format: SinkFormat) -> Result<Self, Box<dyn Error>> {
+    pub fn new(
+        out_dir: PathBuf,
+        format: SinkFormat,
+        csv_format: CsvFormat,
+    ) -> Result<Self, Box<dyn Error>> {
         std::fs::create_dir_all(&out_dir).map_err(|e| {
             format!(
                 "failed to create output directory '{}': {e}",
                 out_dir.display()
             )
         })?;
-        Ok(Self { out_dir, format })
+        Ok(Self {
+            out_dir,
+            format,
+            csv_format,
+        })
     }
 
     pub fn write(&self, name: &str, df: &mut DataFrame) -> Result<PathBuf, Box<dyn Error>> {
@@ -41,6 +51,7 @@ impl MultiFileSink {
         match self.format {
             SinkFormat::Csv => CSVFile {
                 file_name: path_str,
+                format: self.csv_format.clone(),
             }
             .write(df)?,
             SinkFormat::Parquet => ParquetFile {
@@ -66,7 +77,7 @@ mod test {
     fn creates_directory_and_writes_csv() {
         let tmp = std::env::temp_dir().join("synthtab_msink_csv_test");
         let _ = std::fs::remove_dir_all(&tmp);
-        let sink = MultiFileSink::new(tmp.clone(), SinkFormat::Csv).unwrap();
+        let sink = MultiFileSink::new(tmp.clone(), SinkFormat::Csv, CsvFormat::default()).unwrap();
         let mut df = sample_df();
         let path = sink.write("ENTITY", &mut df).unwrap();
         assert!(path.exists());
@@ -78,7 +89,8 @@ mod test {
     fn creates_directory_and_writes_parquet() {
         let tmp = std::env::temp_dir().join("synthtab_msink_parquet_test");
         let _ = std::fs::remove_dir_all(&tmp);
-        let sink = MultiFileSink::new(tmp.clone(), SinkFormat::Parquet).unwrap();
+        let sink =
+            MultiFileSink::new(tmp.clone(), SinkFormat::Parquet, CsvFormat::default()).unwrap();
         let mut df = sample_df();
         let path = sink.write("ENTITY", &mut df).unwrap();
         assert!(path.exists());
@@ -90,7 +102,7 @@ mod test {
     fn file_path_combines_out_dir_and_entity_name() {
         let tmp = std::env::temp_dir().join("synthtab_msink_path_test");
         let _ = std::fs::remove_dir_all(&tmp);
-        let sink = MultiFileSink::new(tmp.clone(), SinkFormat::Csv).unwrap();
+        let sink = MultiFileSink::new(tmp.clone(), SinkFormat::Csv, CsvFormat::default()).unwrap();
         let mut df = sample_df();
         let path = sink.write("CUSTOMER", &mut df).unwrap();
         assert_eq!(
@@ -4,6 +4,9 @@ use std::io::stdout;
 use polars::frame::DataFrame;
 use polars::prelude::*;
 
+use crate::util::dialect::Dialect;
+use crate::util::fake::{base64, hex};
+
 pub trait Output {
     fn write(&mut self, df: &mut DataFrame) -> Result<(), Box<dyn Error>>;
 }
@@ -23,26 +26,275 @@ impl Output for ParquetFile {
     }
 }
 
+/// `df` with its `TIME` cells, nested ones included, as `HH:MM:SS[.f]` text.
+/// Spark reads no Parquet `TIME` type and declares `TIME` columns `STRING`.
+pub(crate) fn times_as_text(df: &DataFrame) -> PolarsResult<DataFrame> {
+    let columns = df
+        .get_columns()
+        .iter()
+        .map(|s| {
+            let dtype = without_time(s.dtype());
+            match &dtype == s.dtype() {
+                true => Ok(s.clone()),
+                false => s.cast(&dtype),
+            }
+        })
+        .collect::<PolarsResult<Vec<Series>>>()?;
+    DataFrame::new(columns)
+}
+
+fn without_time(dtype: &DataType) -> DataType {
+    match dtype {
+        DataType::Time => DataType::String,
+        DataType::List(inner) => DataType::List(Box::new(without_time(inner))),
+        DataType::Struct(fields) => DataType::Struct(
+            fields
+                .iter()
+                .map(|f| Field::new(f.name(), without_time(f.data_type())))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// How CSV output renders nulls and temporal cells. Patterns use chrono's
+/// `strftime` syntax.
+#[derive(Clone, Debug)]
+pub struct CsvFormat {
+    /// Text written for null cells; empty by default.
+    pub null_value: String,
+    pub date_format: String,
+    pub time_format: String,
+    pub datetime_format: String,
+    /// How `BINARY` cells are written; `\x`-prefixed hex by default.
+    pub binary: BinaryText,
+}
+
+/// The text form of a `BINARY` cell in CSV output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinaryText {
+    /// `\x`-prefixed hex, Postgres' `bytea` input format.
+    PrefixedHex,
+    /// Plain hex.
+    Hex,
+    /// Padded base64, the form BigQuery reads into `BYTES`.
+    Base64,
+}
+
+impl Default for CsvFormat {
+    fn default() -> Self {
+        Self {
+            null_value: String::new(),
+            date_format: "%Y-%m-%d".to_string(),
+            time_format: "%H:%M:%S".to_string(),
+            datetime_format: "%Y-%m-%dT%H:%M:%S%:z".to_string(),
+            binary: BinaryText::PrefixedHex,
+        }
+    }
+}
+
+impl CsvFormat {
+    /// This format with `BINARY` cells as `target` reads them: plain hex for
+    /// MySQL, whose load command decodes it with `UNHEX`, and SQL Server,
+    /// base64 for BigQuery. Fails for a custom null token with SQL Server, whose `BULK INSERT`
+    /// reads only empty fields as NULL.
+    pub fn for_target(self, target: Option<Dialect>) -> Result<Self, String> {
+        match target {
+            Some(Dialect::Sqlserver) if !self.null_value.is_empty() => Err(format!(
+                "--null-token '{}' cannot be used with --target sqlserver; BULK INSERT reads only empty fields as NULL",
+                self.null_value
+            )),
+            Some(Dialect::Mysql | Dialect::Sqlserver) => Ok(Self {
+                binary: BinaryText::Hex,
+                ..self
+            }),
+            Some(Dialect::Bigquery) => Ok(Self {
+                binary: BinaryText::Base64,
+                ..self
+            }),
+            _ => Ok(self),
+        }
+    }
+
+    fn finish<W: std::io::Write>(&self, writer: W, df: &mut DataFrame) -> PolarsResult<()> {
+        let mut df = self.as_csv_columns(df)?;
+        CsvWriter::new(writer)
+            .with_null_value(self.null_value.clone())
+            .with_date_format(Some(self.date_format.clone()))
+            .with_time_format(Some(self.time_format.clone()))
+            .with_datetime_format(Some(self.datetime_format.clone()))
+            .finish(&mut df)
+    }
+
+    /// The CSV writer cannot serialize `Decimal`, `Binary`, `List` or
+    /// `Struct` columns, so render them as text first: decimals as numbers,
+    /// bytes in the `binary` form, nested cells as JSON.
+    fn as_csv_columns(&self, df: &DataFrame) -> PolarsResult<DataFrame> {
+        let columns = df
+            .get_columns()
+            .iter()
+            .map(|s| match s.dtype() {
+                DataType::Decimal(_, _) => decimal_as_text(s),
+                DataType::Binary => self.binary_as_text(s),
+                DataType::List(_) | DataType::Struct(_) => {
+                    let text: StringChunked = self.json_cells(s)?.into_iter().collect();
+                    Ok(text.with_name(s.name()).into_series())
+                }
+                _ => Ok(s.clone()),
+            })
+            .collect::<PolarsResult<Vec<Series>>>()?;
+        DataFrame::new(columns)
+    }
+
+    /// A `Binary` column as text in the `binary` form, one cell per value.
+    fn binary_as_text(&self, s: &Series) -> PolarsResult<Series> {
+        let text: StringChunked = s
+            .binary()?
+            .into_iter()
+            .map(|v| {
+                v.map(|bytes| match self.binary {
+                    BinaryText::PrefixedHex => format!("\\x{}", hex(bytes)),
+                    BinaryText::Hex => hex(bytes),
+                    BinaryText::Base64 => base64(bytes),
+                })
+            })
+            .collect();
+        Ok(text.with_name(s.name()).into_series())
+    }
+
+    /// Each cell of `s` as JSON; `None` for a null cell. Temporal values use
+    /// the CSV patterns, so they read the same inside and outside JSON.
+    fn json_cells(&self, s: &Series) -> PolarsResult<Vec<Option<String>>> {
+        let quoted = |text: StringChunked| {
+            text.into_iter()
+                .map(|v| v.map(json_string))
+                .collect::<Vec<_>>()
+        };
+        let cells = match s.dtype() {
+            DataType::List(_) => s
+                .list()?
+                .into_iter()
+                .map(|row| match row {
+                    Some(items) => {
+                        let items = self.json_cells(&items)?;
+                        Ok(Some(format!("[{}]", json_join(items))))
+                    }
+                    None => Ok(None),
+                })
+                .collect::<PolarsResult<Vec<_>>>()?,
+            DataType::Struct(_) => {
+                let fields = s
+                    .struct_()?
+                    .fields()
+                    .iter()
+                    .map(|f| Ok((json_string(f.name()), self.json_cells(f)?)))
+                    .collect::<PolarsResult<Vec<_>>>()?;
+                let nulls = s.is_null();
+                (0..s.len())
+                    .map(|row| {
+                        (nulls.get(row) != Some(true)).then(|| {
+                            let members = fields.iter().map(|(name, cells)| {
+                                let value = cells[row].as_deref().unwrap_or("null");
+                                format!("{name}:{value}")
+                            });
+                            format!("{{{}}}", members.collect::<Vec<_>>().join(","))
+                        })
+                    })
+                    .collect()
+            }
+            DataType::Decimal(_, _) => decimal_as_text(s)?
+                .str()?
+                .into_iter()
+                .map(|v| v.map(str::to_string))
+                .collect(),
+            DataType::Binary => quoted(self.binary_as_text(s)?.str()?.clone()),
+            DataType::Date => quoted(s.date()?.to_string(&self.date_format)),
+            DataType::Time => quoted(s.time()?.to_string(&self.time_format)),
+            DataType::Datetime(_, _) => quoted(s.datetime()?.to_string(&self.datetime_format)?),
+            dtype if dtype.is_numeric() || dtype == &DataType::Boolean => s
+                .cast(&DataType::String)?
+                .str()?
+                .into_iter()
+                .map(|v| v.map(str::to_string))
+                .collect(),
+            _ => quoted(s.cast(&DataType::String)?.str()?.clone()),
+        };
+        Ok(cells)
+    }
+}
+
+fn json_join(cells: Vec<Option<String>>) -> String {
+    cells
+        .into_iter()
+        .map(|c| c.unwrap_or_else(|| "null".to_string()))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn json_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// A `Decimal` column as text that keeps every digit of its scale (`5.00`,
+/// not the `5` a plain cast gives).
+pub(crate) fn decimal_as_text(s: &Series) -> PolarsResult<Series> {
+    let ca = s.decimal()?;
+    let scale = ca.scale();
+    let text: StringChunked = ca
+        .into_iter()
+        .map(|v| v.map(|v| decimal_text(v, scale)))
+        .collect();
+    Ok(text.with_name(s.name()).into_series())
+}
+
+fn decimal_text(value: i128, scale: usize) -> String {
+    let sign = if value < 0 { "-" } else { "" };
+    let factor = 10u128.pow(scale as u32);
+    let (whole, frac) = (value.unsigned_abs() / factor, value.unsigned_abs() % factor);
+    if scale == 0 {
+        format!("{sign}{whole}")
+    } else {
+        format!("{sign}{whole}.{frac:0scale$}")
+    }
+}
+
 pub struct CSVFile {
     pub file_name: String,
+    pub format: CsvFormat,
 }
 
 impl Output for CSVFile {
     fn write(&mut self, df: &mut DataFrame) -> Result<(), Box<dyn Error>> {
         let mut file = std::fs::File::create(self.file_name.as_str())
             .map_err(|e| format!("failed to create CSV file '{}': {e}", self.file_name))?;
-        CsvWriter::new(&mut file)
-            .finish(df)
+        self.format
+            .finish(&mut file, df)
             .map_err(|e| format!("failed to write CSV file '{}': {e}", self.file_name))?;
         Ok(())
     }
 }
 
-pub struct Console {}
+pub struct Console {
+    pub format: CsvFormat,
+}
 
 impl Output for Console {
     fn write(&mut self, df: &mut DataFrame) -> Result<(), Box<dyn Error>> {
-        CsvWriter::new(stdout()).finish(df)?;
+        self.format.finish(stdout(), df)?;
         Ok(())
     }
 }
@@ -62,6 +314,7 @@ mod test {
         let path = std::env::temp_dir().join("synthtab_test_csv_writer.csv");
         let mut writer = CSVFile {
             file_name: path.to_str().unwrap().to_string(),
+            format: CsvFormat::default(),
         };
         let mut df = sample_df();
         writer.write(&mut df).unwrap();
@@ -87,6 +340,7 @@ mod test {
     fn test_csv_file_writer_bad_path_returns_error() {
         let mut writer = CSVFile {
             file_name: "/nonexistent/dir/out.csv".to_string(),
+            format: CsvFormat::default(),
         };
         let mut df = sample_df();
         assert!(writer.write(&mut df).is_err());
@@ -100,4 +354,141 @@ mod test {
         let mut df = sample_df();
         assert!(writer.write(&mut df).is_err());
     }
+
+    #[test]
+    fn test_csv_file_writer_uses_null_value() {
+        let path = std::env::temp_dir().join("synthtab_test_csv_null_value.csv");
+        let mut writer = CSVFile {
+            file_name: path.to_str().unwrap().to_string(),
+            format: CsvFormat {
+                null_value: "NULL".to_string(),
+                ..CsvFormat::default()
+            },
+        };
+        let s = Series::new("name", vec![Some("a"), None, Some("c")]);
+        let mut df = DataFrame::new(vec![s]).unwrap();
+        writer.write(&mut df).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content, "name\na\nNULL\nc\n");
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_csv_file_writer_formats_temporal_and_decimal_cells() {
+        let path = std::env::temp_dir().join("synthtab_test_csv_formats.csv");
+        let mut writer = CSVFile {
+            file_name: path.to_str().unwrap().to_string(),
+            format: CsvFormat {
+                date_format: "%d/%m/%Y".to_string(),
+                ..CsvFormat::default()
+            },
+        };
+        let day = chrono::NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        let mut ts = DatetimeChunked::from_naive_datetime(
+            "ts",
+            [day.and_hms_opt(8, 30, 0).unwrap(); 2],
+            TimeUnit::Microseconds,
+        );
+        ts.set_time_zone("UTC".to_string()).unwrap();
+        let price = Int128Chunked::from_vec("price", vec![1999, -500])
+            .into_decimal_unchecked(Some(10), 2)
+            .into_series();
+        let mut df = DataFrame::new(vec![
+            DateChunked::from_naive_date("d", [day, day]).into_series(),
+            ts.into_series(),
+            price,
+        ])
+        .unwrap();
+        writer.write(&mut df).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            content,
+            "d,ts,price\n\
+             29/02/2024,2024-02-29T08:30:00+00:00,19.99\n\
+             29/02/2024,2024-02-29T08:30:00+00:00,-5.00\n"
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_for_target_rejects_null_token_for_sqlserver() {
+        let format = CsvFormat {
+            null_value: "NULL".to_string(),
+            ..CsvFormat::default()
+        };
+        let err = format
+            .clone()
+            .for_target(Some(Dialect::Sqlserver))
+            .unwrap_err();
+        assert!(err.contains("--null-token 'NULL'"), "got: {err}");
+        assert!(format.for_target(Some(Dialect::Bigquery)).is_ok());
+    }
+
+    #[test]
+    fn test_for_target_writes_binary_as_the_target_reads_it() {
+        let df = DataFrame::new(vec![Series::new("blob", [&b"\x01\xfe\x10"[..]])]).unwrap();
+        for (target, expected) in [
+            (None, "\\x01fe10"),
+            (Some(Dialect::Postgres), "\\x01fe10"),
+            (Some(Dialect::Mysql), "01fe10"),
+            (Some(Dialect::Sqlserver), "01fe10"),
+            (Some(Dialect::Bigquery), "Af4Q"),
+        ] {
+            let format = CsvFormat::default().for_target(target).unwrap();
+            let text = format.as_csv_columns(&df).unwrap();
+            let cell = text.column("blob").unwrap().str().unwrap().get(0);
+            assert_eq!(cell, Some(expected), "{target:?}");
+        }
+    }
+
+    #[test]
+    fn test_times_as_text_reaches_nested_columns() {
+        let times = Series::new("t", [chrono::NaiveTime::from_hms_opt(8, 30, 0).unwrap()]);
+        let list = Series::new("l", [times.clone()]);
+        let df = DataFrame::new(vec![times, list]).unwrap();
+        let df = times_as_text(&df).unwrap();
+        assert_eq!(
+            df.dtypes(),
+            [DataType::String, DataType::List(Box::new(DataType::String))]
+        );
+        assert_eq!(
+            df.column("t").unwrap().str().unwrap().get(0),
+            Some("08:30:00")
+        );
+    }
+
+    #[test]
+    fn test_csv_file_writer_renders_nested_cells_as_json() {
+        let path = std::env::temp_dir().join("synthtab_test_csv_nested.csv");
+        let mut writer = CSVFile {
+            file_name: path.to_str().unwrap().to_string(),
+            format: CsvFormat::default(),
+        };
+        let tags = Series::new(
+            "tags",
+            vec![
+                Series::new("", vec!["a", "say \"hi\""]),
+                Series::new("", Vec::<&str>::new()),
+            ],
+        );
+        let address = StructChunked::new(
+            "address",
+            &[
+                Series::new("street", vec![Some("Main St"), None]),
+                Series::new("zip", vec![Some(12345i32), Some(7)]),
+            ],
+        )
+        .unwrap()
+        .into_series();
+        let mut df = DataFrame::new(vec![tags, address]).unwrap();
+        writer.write(&mut df).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            content,
+            "tags,address\n\
+             \"[\"\"a\"\",\"\"say \\\"\"hi\\\"\"\"\"]\",\"{\"\"street\"\":\"\"Main St\"\",\"\"zip\"\":12345}\"\n\
+             [],\"{\"\"street\"\":null,\"\"zip\"\":7}\"\n"
+        );
+        let _ = std::fs::remove_file(&path);
+    }
 }
@@ -9,7 +9,9 @@
 //! the documented regex.
 
 use crate::util::erd_ast::{Attribute, Cardinality, Entity, ErdAst, KeyKind, Relationship};
+use crate::util::modifier::{parse_modifier, ModifierError};
 use crate::util::scanner::Token;
+use crate::util::schema::Schema;
 use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::fmt;
@@ -28,9 +30,7 @@ impl fmt::Display for ParseError {
 impl Error for ParseError {}
 
 /// Mermaid attribute types accepted by the parser. Maps to the synthtab generator
-/// type used at row-generation time (see PRD §6.3). Mermaid `bool`/`boolean`
-/// is intentionally absent: it is detected and rejected with a specific
-/// "not yet supported" message per PRD §7.
+/// type used at row-generation time (see PRD §6.3).
 pub(crate) fn mermaid_type_to_synthtab(mermaid: &str) -> Option<&'static str> {
     let base = mermaid
         .split_once('(')
@@ -38,9 +38,11 @@ pub(crate) fn mermaid_type_to_synthtab(mermaid: &str) -> Option<&'static str> {
         .unwrap_or(mermaid)
         .to_lowercase();
     match base.as_str() {
-        "int" | "integer" | "bigint" => Some("INT_INC"),
+        "int" | "integer" => Some("INT_INC"),
+        "bigint" => Some("BIGINT_INC"),
         "string" | "varchar" | "text" => Some("STRING"),
         "uuid" => Some("UUID"),
+        "bool" | "boolean" => Some("BOOLEAN"),
         "date" => Some("DATE"),
         "datetime" | "timestamp" => Some("DATE_TIME"),
         "time" => Some("TIME"),
@@ -49,6 +51,125 @@ pub(crate) fn mermaid_type_to_synthtab(mermaid: &str) -> Option<&'static str> {
     }
 }
 
+/// The generator and arguments a sized Mermaid type declares: `varchar(n)`
+/// holds letters and digits, at most `n` of them, and `decimal(p,s)` exact
+/// decimals with that precision and scale. `None` when the type carries no
+/// parameters or its generator has no use for them (`int(11)`).
+pub(crate) fn sized_mermaid_type(
+    mermaid: &str,
+) -> Result<Option<(&'static str, Vec<String>)>, String> {
+    let Some((base, params)) = mermaid.split_once('(') else {
+        return Ok(None);
+    };
+    let params: Vec<&str> = params
+        .strip_suffix(')')
+        .ok_or_else(|| format!("type '{mermaid}' is missing its closing ')'"))?
+        .split(',')
+        .map(str::trim)
+        .collect();
+    let numbers: Option<Vec<u32>> = params.iter().map(|p| p.parse().ok()).collect();
+    match (base.to_lowercase().as_str(), numbers.as_deref()) {
+        ("string" | "varchar" | "text", Some(&[n])) if (1..=65535).contains(&n) => {
+            Ok(Some(("STRING", vec![format!("1..{n}")])))
+        }
+        ("string" | "varchar" | "text", _) => Err(format!(
+            "type '{mermaid}' needs one length between 1 and 65535, e.g. varchar(255)"
+        )),
+        ("decimal", Some(&[p])) if (1..=38).contains(&p) => Ok(Some((
+            "DECIMAL",
+            vec![format!("precision={p}"), "scale=0".to_string()],
+        ))),
+        ("decimal", Some(&[p, s])) if (1..=38).contains(&p) && s <= p => Ok(Some((
+            "DECIMAL",
+            vec![format!("precision={p}"), format!("scale={s}")],
+        ))),
+        ("decimal", _) => Err(format!(
+            "type '{mermaid}' needs a precision between 1 and 38 and a scale no larger than it, e.g. decimal(10,2)"
+        )),
+        _ => Ok(None),
+    }
+}
+
+/// The synthtab column `attr` generates: its mapped Mermaid type, adjusted by
+/// the attribute's comment hint. A hint reads `[TYPE][?][(args)]` — e.g.
+/// `"?"`, `"(null=0.3)"` or `"DECIMAL(0..500, scale=2)"` — where a leading
+/// TYPE replaces the mapped one and the rest works as in a flat schema.
+///
+/// A sized type such as `varchar(40)` adds its arguments to the mapped
+/// type's; hint arguments win where both set the same thing. A hint may not
+/// swap a sized type for another generator, which could not keep the length
+/// or precision the DDL declares. FK attributes take their values from the
+/// parent key, so their sizes are not used.
+pub(crate) fn attribute_schema(attr: &Attribute) -> Result<Schema, ModifierError> {
+    let err = |message: String| ModifierError {
+        message: format!("column '{}': {message}", attr.name),
+    };
+    let sized = match attr.key {
+        Some(KeyKind::Fk) => None,
+        _ => sized_mermaid_type(&attr.data_type).map_err(err)?,
+    };
+    let (mapped, declared) = match sized {
+        Some((ty, args)) => (ty, args),
+        None => (
+            mermaid_type_to_synthtab(&attr.data_type).unwrap_or("STRING"),
+            Vec::new(),
+        ),
+    };
+    let declared_modifier = (!declared.is_empty()).then(|| format!("({})", declared.join(", ")));
+    let Some(hint) = attr.hint.as_deref().map(str::trim) else {
+        return Schema::new(&attr.name, mapped, declared_modifier.as_deref());
+    };
+    let (ty, rest) = hint.split_at(hint.find(['?', '(']).unwrap_or(hint.len()));
+    let ty = match ty.trim() {
+        "" => mapped,
+        t if t
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_') =>
+        {
+            t
+        }
+        _ => {
+            return Err(ModifierError {
+                message: format!(
+                    "column '{}': hint \"{hint}\" is not of the form [TYPE][?][(args)]",
+                    attr.name
+                ),
+            })
+        }
+    };
+    if ty != mapped && !declared.is_empty() {
+        return Err(err(format!(
+            "hint type {ty} cannot keep the size of '{}'; drop the size or hint {mapped} instead",
+            attr.data_type
+        )));
+    }
+    let (question, modifier) = match rest.strip_prefix('?') {
+        Some(r) => ("?", r.trim()),
+        None => ("", rest.trim()),
+    };
+    let modifier = match (modifier, ty == mapped) {
+        ("", true) => declared_modifier,
+        (_, true) if !declared.is_empty() => {
+            let hinted = parse_modifier(modifier).map_err(|e| err(e.message))?;
+            let mut args: Vec<&str> = declared
+                .iter()
+                .map(String::as_str)
+                .filter(|arg| match arg.split_once('=') {
+                    Some((key, _)) => hinted.option(key).is_none(),
+                    None => hinted.positional().is_empty(),
+                })
+                .collect();
+            if !hinted.raw.trim().is_empty() {
+                args.push(&hinted.raw);
+            }
+            Some(format!("({})", args.join(", ")))
+        }
+        ("", false) => None,
+        (m, _) => Some(m.to_string()),
+    };
+    Schema::new(&attr.name, &format!("{ty}{question}"), modifier.as_deref())
+}
+
 /// True if `s` matches the PRD §6.2 entity-name regex `[A-Z][A-Z0-9_-]*`.
 fn is_valid_entity_name(s: &str) -> bool {
     let mut chars = s.chars();
@@ -273,38 +394,42 @@ impl Parser {
                         }
                     }
 
-                    if let Some((cl, Token::StringLit(_))) = self.current().cloned() {
-                        return Err(ParseError {
-                            message: format!(
-                                "line {cl}: attribute comments are not supported in v1; remove the trailing string"
-                            ),
-                        });
-                    }
-
-                    let lower = ty.to_lowercase();
-                    let lower_base = lower.split_once('(').map(|(b, _)| b).unwrap_or(&lower);
-                    if lower_base == "bool" || lower_base == "boolean" {
-                        return Err(ParseError {
-                            message: format!(
-                                "line {line}: type 'boolean' not yet supported (Phase 6); use 'string' as a workaround"
-                            ),
-                        });
+                    let mut hint: Option<String> = None;
+                    if let Some((_, Token::StringLit(text))) = self.current().cloned() {
+                        hint = Some(text);
+                        self.bump();
                     }
 
                     if mermaid_type_to_synthtab(&ty).is_none() {
                         return Err(ParseError {
                             message: format!(
-                                "line {line}: unknown type '{ty}'; supported types: int, string, uuid, date, datetime, decimal, time (see docs/ERD.md §Types)"
+                                "line {line}: unknown type '{ty}'; supported types: int, string, uuid, date, datetime, decimal, time, boolean (see docs/ERD.md §Types)"
                             ),
                         });
                     }
+                    sized_mermaid_type(&ty).map_err(|message| ParseError {
+                        message: format!("line {line}: {message}"),
+                    })?;
 
-                    attributes.push(Attribute {
+                    let attribute = Attribute {
                         name: attr_name,
                         data_type: ty,
                         key,
+                        hint,
                         line,
-                    });
+                    };
+                    let column = attribute_schema(&attribute).map_err(|e| ParseError {
+                        message: format!("line {line}: {}", e.message),
+                    })?;
+                    if key == Some(KeyKind::Pk) && column.is_nullable() {
+                        return Err(ParseError {
+                            message: format!(
+                                "line {line}: primary key '{}' cannot be nullable",
+                                attribute.name
+                            ),
+                        });
+                    }
+                    attributes.push(attribute);
                 }
                 Some((line, t)) => {
                     return Err(ParseError {
@@ -582,10 +707,14 @@ erDiagram
     }
 
     #[test]
-    fn rejects_boolean_type_with_specific_message() {
-        let src = "erDiagram\nU { int id PK\n boolean active }\n";
-        let err = parse_src(src).unwrap_err();
-        assert!(err.contains("'boolean' not yet supported"), "got: {err}");
+    fn boolean_types_map_to_boolean_with_optional_ratio() {
+        let src = "erDiagram\nU { int id PK\n boolean active \"(0.9)\"\n bool admin }\n";
+        let ast = parse_src(src).unwrap();
+        let active = attribute_schema(&ast.entities[0].attributes[1]).unwrap();
+        assert_eq!(active.datatype, "BOOLEAN");
+        assert_eq!(active.modifier.unwrap().raw, "0.9");
+        let admin = attribute_schema(&ast.entities[0].attributes[2]).unwrap();
+        assert_eq!(admin.datatype, "BOOLEAN");
     }
 
     #[test]
@@ -596,10 +725,38 @@ erDiagram
     }
 
     #[test]
-    fn rejects_string_attribute_comment() {
+    fn attribute_comment_is_kept_as_hint() {
+        let src = "erDiagram\nU { int id PK\n string name \"?\" }\n";
+        let ast = parse_src(src).unwrap();
+        let attr = &ast.entities[0].attributes[1];
+        assert_eq!(attr.hint.as_deref(), Some("?"));
+        let column = attribute_schema(attr).unwrap();
+        assert_eq!(column.datatype, "STRING");
+        assert!(column.is_nullable());
+    }
+
+    #[test]
+    fn attribute_hint_can_replace_type_and_add_modifier() {
+        let src = "erDiagram\nU { int id PK\n decimal price \"DECIMAL(0..500, null=0.2)\" }\n";
+        let ast = parse_src(src).unwrap();
+        let column = attribute_schema(&ast.entities[0].attributes[1]).unwrap();
+        assert_eq!(column.datatype, "DECIMAL");
+        assert_eq!(column.null_ratio, Some(0.2));
+        assert_eq!(column.modifier.unwrap().raw, "0..500");
+    }
+
+    #[test]
+    fn rejects_malformed_attribute_hint() {
         let src = "erDiagram\nU { int id PK\n string name \"the name\" }\n";
         let err = parse_src(src).unwrap_err();
-        assert!(err.contains("attribute comments"), "got: {err}");
+        assert!(err.starts_with("line 3: column 'name'"), "got: {err}");
+    }
+
+    #[test]
+    fn rejects_nullable_primary_key() {
+        let src = "erDiagram\nU { int id PK \"?\" }\n";
+        let err = parse_src(src).unwrap_err();
+        assert!(err.contains("cannot be nullable"), "got: {err}");
     }
 
     #[test]
@@ -609,6 +766,73 @@ erDiagram
         assert_eq!(ast.entities[0].attributes[1].data_type, "varchar(255)");
     }
 
+    #[test]
+    fn sized_types_carry_their_parameters() {
+        let src = "erDiagram\nU { int id PK\n varchar(40) nick\n decimal(12, 4) balance\n int(11) age }\n";
+        let ast = parse_src(src).unwrap();
+        let attrs = &ast.entities[0].attributes;
+        let nick = attribute_schema(&attrs[1]).unwrap();
+        assert_eq!(nick.datatype, "STRING");
+        assert_eq!(nick.modifier.unwrap().raw, "1..40");
+        let balance = attribute_schema(&attrs[2]).unwrap();
+        assert_eq!(balance.datatype, "DECIMAL");
+        assert_eq!(balance.modifier.unwrap().raw, "precision=12, scale=4");
+        let age = attribute_schema(&attrs[3]).unwrap();
+        assert_eq!((age.datatype.as_str(), age.modifier), ("INT_INC", None));
+    }
+
+    #[test]
+    fn hint_arguments_override_declared_sizes() {
+        let src = "erDiagram\nU { int id PK\n varchar(40) a \"(3..5, null=0.2)\"\n varchar(40) b \"?\"\n decimal(6,2) c \"(0..50, scale=1)\"\n varchar d \"EMAIL\" }\n";
+        let ast = parse_src(src).unwrap();
+        let column = |i: usize| attribute_schema(&ast.entities[0].attributes[i]).unwrap();
+        assert_eq!(column(1).modifier.unwrap().raw, "3..5");
+        assert_eq!(column(1).null_ratio, Some(0.2));
+        assert_eq!(column(2).modifier.unwrap().raw, "1..40");
+        assert!(column(2).is_nullable());
+        assert_eq!(
+            column(3).modifier.unwrap().raw,
+            "precision=6, 0..50, scale=1"
+        );
+        assert_eq!(
+            (column(4).datatype.as_str(), column(4).modifier),
+            ("EMAIL", None)
+        );
+    }
+
+    #[test]
+    fn hint_cannot_drop_a_declared_size() {
+        for (ty, hint) in [("varchar(40)", "NAME"), ("decimal(6,2)", "PRICE?")] {
+            let src = format!("erDiagram\nU {{ int id PK\n {ty} x \"{hint}\" }}\n");
+            let err = parse_src(&src).unwrap_err();
+            assert!(
+                err.starts_with("line 3: column 'x'") && err.contains("cannot keep the size"),
+                "{ty}: {err}"
+            );
+        }
+        let src = "erDiagram\nU { int id PK\n varchar(40) x \"STRING?(5..10)\" }\n";
+        let ast = parse_src(src).unwrap();
+        let column = attribute_schema(&ast.entities[0].attributes[1]).unwrap();
+        assert_eq!(column.modifier.unwrap().raw, "5..10");
+    }
+
+    #[test]
+    fn rejects_bad_type_parameters() {
+        for (ty, expected) in [
+            ("varchar(0)", "needs one length between 1 and 65535"),
+            ("varchar(a)", "needs one length"),
+            ("decimal(4,6)", "a scale no larger than it"),
+            ("decimal(40)", "a precision between 1 and 38"),
+        ] {
+            let src = format!("erDiagram\nU {{ int id PK\n {ty} x }}\n");
+            let err = parse_src(&src).unwrap_err();
+            assert!(
+                err.starts_with("line 3:") && err.contains(expected),
+                "{ty}: {err}"
+            );
+        }
+    }
+
     #[test]
     fn detects_cycle_in_fk_graph() {
         let src = "\
@@ -0,0 +1,254 @@
+//! Run-wide random-number source.
+//!
+//! Every generator, FK sampler and random delete draws from the RNG built
+//! here instead of `thread_rng()`, so `--seed` makes a whole run — flat or
+//! ER — byte-for-byte reproducible.
+//!
+//! With `--stable-rows` each cell instead gets its own RNG derived from
+//! `(seed, table, column, row)`, so a row's values do not depend on how many
+//! rows are generated or on the order columns are built in.
+//!
+//! The run's clock lives here too: `now` and `today` bounds all resolve
+//! against one instant, read once or pinned with `--now`.
+
+use chrono::{NaiveDateTime, Utc};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// The RNG threaded through generation. ChaCha8 is used over `StdRng`
+/// because its output stream is stable across platforms and `rand` releases.
+pub type SynthRng = ChaCha8Rng;
+
+/// Build the run RNG: deterministic for `Some(seed)`, OS-entropy otherwise.
+pub fn seeded_rng(seed: Option<u64>) -> SynthRng {
+    match seed {
+        Some(s) => SynthRng::seed_from_u64(s),
+        None => SynthRng::from_entropy(),
+    }
+}
+
+/// Per-cell randomness for one column. `cell` hands out the RNG for a
+/// single row; `column` hands out one for draws that span the whole column
+/// (e.g. a coverage shuffle).
+pub trait CellRng {
+    fn cell<T>(&mut self, row: usize, f: impl FnOnce(&mut SynthRng) -> T) -> T;
+    fn column<T>(&mut self, f: impl FnOnce(&mut SynthRng) -> T) -> T;
+    /// RNG for `part` of this column, such as a `STRUCT` field, so two
+    /// parts never share a cell's draws.
+    fn part(&mut self, part: &str) -> ColumnRng<'_>;
+}
+
+/// Sequential mode: every cell draws in turn from one shared stream.
+impl CellRng for SynthRng {
+    fn cell<T>(&mut self, _row: usize, f: impl FnOnce(&mut SynthRng) -> T) -> T {
+        f(self)
+    }
+
+    fn column<T>(&mut self, f: impl FnOnce(&mut SynthRng) -> T) -> T {
+        f(self)
+    }
+
+    fn part(&mut self, _part: &str) -> ColumnRng<'_> {
+        ColumnRng::Shared(self)
+    }
+}
+
+/// Stable mode: each cell's RNG is a pure function of the column key and the
+/// row index.
+#[derive(Clone, Copy, Debug)]
+pub struct KeyedRng {
+    key: u64,
+}
+
+impl KeyedRng {
+    pub fn new(seed: u64, table: &str, column: &str) -> Self {
+        Self {
+            key: column_key(seed, table, column),
+        }
+    }
+}
+
+impl CellRng for KeyedRng {
+    fn cell<T>(&mut self, row: usize, f: impl FnOnce(&mut SynthRng) -> T) -> T {
+        let cell_seed = splitmix64(
+            self.key
+                .wrapping_add((row as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)),
+        );
+        f(&mut SynthRng::seed_from_u64(cell_seed))
+    }
+
+    fn column<T>(&mut self, f: impl FnOnce(&mut SynthRng) -> T) -> T {
+        f(&mut SynthRng::seed_from_u64(self.key))
+    }
+
+    fn part(&mut self, part: &str) -> ColumnRng<'_> {
+        ColumnRng::Keyed(KeyedRng {
+            key: column_key(self.key, "", part),
+        })
+    }
+}
+
+/// A column's randomness, picked at runtime from the run's mode.
+pub enum ColumnRng<'a> {
+    Shared(&'a mut SynthRng),
+    Keyed(KeyedRng),
+}
+
+impl CellRng for ColumnRng<'_> {
+    fn cell<T>(&mut self, row: usize, f: impl FnOnce(&mut SynthRng) -> T) -> T {
+        match self {
+            ColumnRng::Shared(rng) => rng.cell(row, f),
+            ColumnRng::Keyed(rng) => rng.cell(row, f),
+        }
+    }
+
+    fn column<T>(&mut self, f: impl FnOnce(&mut SynthRng) -> T) -> T {
+        match self {
+            ColumnRng::Shared(rng) => CellRng::column(*rng, f),
+            ColumnRng::Keyed(rng) => rng.column(f),
+        }
+    }
+
+    fn part(&mut self, part: &str) -> ColumnRng<'_> {
+        match self {
+            ColumnRng::Shared(rng) => rng.part(part),
+            ColumnRng::Keyed(rng) => rng.part(part),
+        }
+    }
+}
+
+/// The run's randomness: a shared stream, plus the seed per-cell RNGs are
+/// keyed from when `--stable-rows` is on, and the run's `now`.
+pub struct RunRng {
+    stream: SynthRng,
+    stable_seed: Option<u64>,
+    now: NaiveDateTime,
+}
+
+impl RunRng {
+    /// `stable` only takes effect with a seed; the CLI enforces that pairing.
+    /// `now` is the current time until `at` pins it.
+    pub fn new(seed: Option<u64>, stable: bool) -> Self {
+        Self {
+            stream: seeded_rng(seed),
+            stable_seed: seed.filter(|_| stable),
+            now: Utc::now().naive_utc(),
+        }
+    }
+
+    /// Resolve `now` and `today` against `now` for the whole run.
+    pub fn at(self, now: NaiveDateTime) -> Self {
+        Self { now, ..self }
+    }
+
+    /// The instant `now` and `today` bounds resolve against.
+    pub fn now(&self) -> NaiveDateTime {
+        self.now
+    }
+
+    /// RNG for draws that are not tied to a column (e.g. random deletes).
+    pub fn stream(&mut self) -> &mut SynthRng {
+        &mut self.stream
+    }
+
+    /// RNG for `column` of `table`.
+    pub fn column(&mut self, table: &str, column: &str) -> ColumnRng<'_> {
+        match self.stable_seed {
+            Some(seed) => ColumnRng::Keyed(KeyedRng::new(seed, table, column)),
+            None => ColumnRng::Shared(&mut self.stream),
+        }
+    }
+
+    /// RNG that decides which cells of `column` are null. Keyed apart from
+    /// the value stream so a cell's null-ness never correlates with its value.
+    pub fn nulls(&mut self, table: &str, column: &str) -> ColumnRng<'_> {
+        self.column(table, &format!("{column}\0nulls"))
+    }
+}
+
+/// FNV-1a over the seed, table and column. Hand-rolled rather than
+/// `DefaultHasher` because std makes no stability promise for that hash.
+fn column_key(seed: u64, table: &str, column: &str) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    let mut h = OFFSET;
+    let bytes = seed
+        .to_le_bytes()
+        .into_iter()
+        .chain(table.bytes())
+        .chain(std::iter::once(0xff))
+        .chain(column.bytes());
+    for b in bytes {
+        h ^= b as u64;
+        h = h.wrapping_mul(PRIME);
+    }
+    splitmix64(h)
+}
+
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn same_seed_yields_same_stream() {
+        let mut a = seeded_rng(Some(7));
+        let mut b = seeded_rng(Some(7));
+        let xs: Vec<u64> = (0..8).map(|_| a.gen()).collect();
+        let ys: Vec<u64> = (0..8).map(|_| b.gen()).collect();
+        assert_eq!(xs, ys);
+    }
+
+    #[test]
+    fn different_seeds_diverge() {
+        let x: u64 = seeded_rng(Some(1)).gen();
+        let y: u64 = seeded_rng(Some(2)).gen();
+        assert_ne!(x, y);
+    }
+
+    #[test]
+    fn keyed_cells_ignore_draw_order() {
+        let mut k = KeyedRng::new(5, "users", "email");
+        let forward: Vec<u64> = (0..5).map(|i| k.cell(i, |r| r.gen())).collect();
+        let backward: Vec<u64> = (0..5).rev().map(|i| k.cell(i, |r| r.gen())).collect();
+        let reversed: Vec<u64> = backward.into_iter().rev().collect();
+        assert_eq!(forward, reversed);
+    }
+
+    #[test]
+    fn keyed_cells_differ_by_table_and_column() {
+        let draw = |table: &str, column: &str| -> u64 {
+            KeyedRng::new(5, table, column).cell(0, |r| r.gen())
+        };
+        assert_ne!(draw("users", "a"), draw("users", "b"));
+        assert_ne!(draw("users", "a"), draw("orders", "a"));
+        // The separator keeps ("ab","c") and ("a","bc") apart.
+        assert_ne!(draw("ab", "c"), draw("a", "bc"));
+    }
+
+    #[test]
+    fn keyed_parts_differ_from_their_column_and_each_other() {
+        let mut k = KeyedRng::new(5, "users", "address");
+        let whole: u64 = k.cell(0, |r| r.gen());
+        let street: u64 = k.part("street").cell(0, |r| r.gen());
+        let zip: u64 = k.part("zip").cell(0, |r| r.gen());
+        assert_ne!(whole, street);
+        assert_ne!(street, zip);
+        assert_eq!(street, k.part("street").cell(0, |r| r.gen::<u64>()));
+    }
+
+    #[test]
+    fn run_rng_without_stable_shares_one_stream() {
+        let mut run = RunRng::new(Some(3), false);
+        let a: u64 = run.column("t", "x").cell(0, |r| r.gen());
+        let b: u64 = run.column("t", "x").cell(0, |r| r.gen());
+        assert_ne!(a, b, "shared stream should advance between draws");
+    }
+}
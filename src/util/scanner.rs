@@ -118,7 +118,15 @@ fn scan_line(line: &str, line_no: usize, out: &mut Vec<(usize, Token)>) -> Resul
             }
             c if c.is_alphabetic() || c == '_' => {
                 let start = i;
-                while i < chars.len() && is_ident_continue(chars[i]) {
+                let mut depth = 0;
+                while i < chars.len()
+                    && (is_ident_continue(chars[i]) || (depth > 0 && matches!(chars[i], ',' | ' ')))
+                {
+                    match chars[i] {
+                        '(' => depth += 1,
+                        ')' => depth -= 1,
+                        _ => {}
+                    }
                     i += 1;
                 }
                 let lexeme: String = chars[start..i].iter().collect();
@@ -142,7 +150,8 @@ fn scan_line(line: &str, line_no: usize, out: &mut Vec<(usize, Token)>) -> Resul
 
 fn is_ident_continue(c: char) -> bool {
     // Allow `(`/`)` so parameterised types like `varchar(255)` become a single
-    // identifier token; the parser (M2) strips the parens per PRD §6.3.
+    // identifier token; the parser (M2) strips the parens per PRD §6.3. Inside
+    // the parens `,` and spaces are kept too, for `decimal(10, 2)`.
     c.is_alphanumeric() || c == '_' || c == '-' || c == '(' || c == ')'
 }
 
@@ -339,6 +348,15 @@ mod test {
         assert!(matches!(non_nl[2], Token::Ident(s) if s == "varchar(255)"));
     }
 
+    #[test]
+    fn decimal_with_precision_and_scale_is_one_ident() {
+        let src = "USER { decimal(10, 2) balance }\n";
+        let toks = scan(src).unwrap();
+        let non_nl = types_only(&toks);
+        assert!(matches!(non_nl[2], Token::Ident(s) if s == "decimal(10, 2)"));
+        assert!(matches!(non_nl[3], Token::Ident(s) if s == "balance"));
+    }
+
     #[test]
     fn line_numbers_track_across_lines() {
         let src = "erDiagram\n\nA {\n  int id PK\n}\n";
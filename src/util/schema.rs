@@ -1,33 +1,266 @@
-#[derive(Clone)]
+use crate::util::locale::{Locale, LOCALIZED_TYPES};
+use crate::util::modifier::{parse_modifier, raw_modifier, Arg, Modifier, ModifierError, Value};
+
+/// Null ratio used when a column is marked nullable with a bare `?`.
+pub const DEFAULT_NULL_RATIO: f64 = 0.1;
+
+#[derive(Clone, Debug)]
 pub struct Schema {
     pub name: String,
     pub datatype: String,
-    pub modifier: Option<String>,
+    pub modifier: Option<Modifier>,
+    /// Fraction of cells to null out; `Some` marks the column nullable.
+    pub null_ratio: Option<f64>,
+    /// Data set for localized types; `None` uses the run's `--locale`.
+    pub locale: Option<Locale>,
+    /// Element types of a nested column: the one item type of a `LIST`,
+    /// named after the column, or the fields of a `STRUCT`. Empty otherwise.
+    pub children: Vec<Schema>,
 }
 
 impl Schema {
-    pub fn from_string(input: &str) -> Option<Schema> {
-        let input = input.replace(' ', "");
-        let parts: Vec<&str> = input.split(':').collect();
-        if !(parts.len() == 2 || parts.len() == 3) {
-            eprintln!("ignoring invalid schema column: {parts:?}");
-            None
+    /// Parse one `name:TYPE`, `name:TYPE(args)` or legacy `name:TYPE:(args)`
+    /// token. Structurally broken tokens are skipped with a warning (`Ok(None)`);
+    /// a malformed modifier is an error naming the column.
+    pub fn from_string(input: &str) -> Result<Option<Schema>, ModifierError> {
+        let input = input.trim();
+        let Some((name, rest)) = input.split_once(':') else {
+            eprintln!("ignoring invalid schema column: [{input:?}]");
+            return Ok(None);
+        };
+        let name = name.trim();
+        let (datatype, modifier) = match type_end(rest) {
+            Some(idx) => {
+                let (ty, m) = rest.split_at(idx);
+                (ty.trim(), Some(m.strip_prefix(':').unwrap_or(m).trim()))
+            }
+            None => (rest.trim(), None),
+        };
+        if name.is_empty() || datatype.is_empty() || datatype == "?" {
+            eprintln!("ignoring invalid schema column: [{input:?}]");
+            return Ok(None);
+        }
+        Schema::new(name, datatype, modifier).map(Some)
+    }
+
+    /// Build a column from its parts. A trailing `?` on `datatype` or after
+    /// the argument list of `modifier`, or a `null=<ratio>` option in
+    /// `modifier`, makes the column nullable, and a
+    /// `locale=` option picks its data set. Both options are taken out of the
+    /// modifier so generators never see them.
+    ///
+    /// `EXPR` modifiers are expressions, not argument lists, so they are kept
+    /// as raw text and only `?` makes them nullable.
+    pub fn new(
+        name: &str,
+        datatype: &str,
+        modifier: Option<&str>,
+    ) -> Result<Schema, ModifierError> {
+        let err = |message: String| ModifierError {
+            message: format!("column '{name}': {message}"),
+        };
+        let (datatype, question) = match datatype.trim().strip_suffix('?') {
+            Some(ty) => (ty.trim_end(), true),
+            None => (datatype.trim(), false),
+        };
+        let (modifier, question) = match modifier.map(str::trim) {
+            Some(m) if m.ends_with(")?") => (m.strip_suffix('?'), true),
+            m => (m, question),
+        };
+        let (datatype, children) = match datatype.split_once('<') {
+            Some((base, inner)) => {
+                let inner = inner.strip_suffix('>').ok_or_else(|| {
+                    err(format!("'{datatype}' is missing its closing '>'"))
+                })?;
+                (base.trim_end(), nested_children(name, base, inner).map_err(err)?)
+            }
+            None if matches!(datatype, "LIST" | "STRUCT") => {
+                return Err(err(format!(
+                    "{datatype} needs its element types, e.g. LIST<INT> or STRUCT<street:STRING, zip:ZIP_CODE>"
+                )))
+            }
+            None => (datatype, Vec::new()),
+        };
+        let parse = if datatype == "EXPR" {
+            raw_modifier
         } else {
-            Some(Schema {
-                name: parts[0].trim().to_string(),
-                datatype: parts[1].trim().to_string(),
-                modifier: parts.get(2).map(|m| m.trim().to_string()),
-            })
+            parse_modifier
+        };
+        let mut modifier = modifier
+            .map(parse)
+            .transpose()
+            .map_err(|e| err(e.message))?;
+
+        let mut null_ratio = question.then_some(DEFAULT_NULL_RATIO);
+        let mut locale = None;
+        if let Some(m) = modifier.as_mut() {
+            if let Some(arg) = take_option(m, "null").map_err(err)? {
+                let ratio = arg
+                    .value
+                    .as_f64()
+                    .filter(|r| (0.0..=1.0).contains(r))
+                    .ok_or_else(|| {
+                        err(format!(
+                            "null ratio must be between 0 and 1, got '{}'",
+                            arg.value
+                        ))
+                    })?;
+                null_ratio = Some(ratio);
+            }
+            if let Some(arg) = take_option(m, "locale").map_err(err)? {
+                if !LOCALIZED_TYPES.contains(&datatype) {
+                    return Err(err(format!(
+                        "{datatype} does not vary by locale; locale= applies to {}",
+                        LOCALIZED_TYPES.join(", ")
+                    )));
+                }
+                let name = match &arg.value {
+                    Value::Str(s) | Value::Ident(s) => s.as_str(),
+                    other => {
+                        return Err(err(format!("expected a locale like ja_JP, got '{other}'")))
+                    }
+                };
+                locale = Some(Locale::parse(name).map_err(err)?);
+            }
+        }
+        if modifier
+            .as_ref()
+            .is_some_and(|m| m.args.is_empty() && m.raw.is_empty())
+            && (null_ratio.is_some() || locale.is_some())
+        {
+            modifier = None;
         }
+
+        Ok(Schema {
+            name: name.to_string(),
+            datatype: datatype.to_string(),
+            modifier,
+            null_ratio,
+            locale,
+            children,
+        })
+    }
+
+    pub fn is_nullable(&self) -> bool {
+        self.null_ratio.is_some()
     }
 }
 
-pub fn parse_schema(input: &str) -> Vec<Schema> {
-    input
-        .trim_end_matches(',')
-        .split(',')
-        .filter_map(Schema::from_string)
-        .collect()
+/// Where the type of a `TYPE(args)` or `TYPE:(args)` token ends: the first
+/// `(` or `:` outside a `<...>` element list, so the field names in
+/// `STRUCT<street:STRING>` stay part of the type.
+fn type_end(rest: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    for (i, c) in rest.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '<') => depth += 1,
+            (None, '>') => depth = depth.saturating_sub(1),
+            (None, '(' | ':') if depth == 0 => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// The element types inside `LIST<...>` or `STRUCT<...>`.
+fn nested_children(name: &str, base: &str, inner: &str) -> Result<Vec<Schema>, String> {
+    let child = |token: &str| -> Result<Schema, String> {
+        let column = Schema::from_string(token)
+            .map_err(|e| e.message)?
+            .ok_or_else(|| format!("'{token}' inside {base}<...> is not 'name:TYPE'"))?;
+        if matches!(column.datatype.as_str(), "TEMPLATE" | "EXPR") {
+            return Err(format!(
+                "{} reads other columns, so it cannot be nested in {base}",
+                column.datatype
+            ));
+        }
+        Ok(column)
+    };
+    match base {
+        "LIST" if inner.trim().is_empty() => Err("LIST<> needs an element type".into()),
+        "LIST" => Ok(vec![child(&format!("{name}:{inner}"))?]),
+        "STRUCT" => {
+            let fields = split_columns(inner)
+                .into_iter()
+                .map(child)
+                .collect::<Result<Vec<_>, _>>()?;
+            for (i, field) in fields.iter().enumerate() {
+                if fields[..i].iter().any(|f| f.name == field.name) {
+                    return Err(format!("STRUCT field '{}' is declared twice", field.name));
+                }
+            }
+            Ok(fields)
+        }
+        other => Err(format!(
+            "only LIST and STRUCT take element types, got '{other}<{inner}>'"
+        )),
+    }
+}
+
+/// Remove the `key=` option from `m`, rebuilding its source text so
+/// generators never see it. Errors when the option is given twice.
+fn take_option(m: &mut Modifier, key: &str) -> Result<Option<Arg>, String> {
+    let Some(idx) = m.args.iter().position(|a| a.key.as_deref() == Some(key)) else {
+        return Ok(None);
+    };
+    let arg = m.args.remove(idx);
+    if m.args.iter().any(|a| a.key.as_deref() == Some(key)) {
+        return Err(format!("option '{key}' given more than once"));
+    }
+    m.raw = m
+        .args
+        .iter()
+        .map(|a| a.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    Ok(Some(arg))
+}
+
+pub fn parse_schema(input: &str) -> Result<Vec<Schema>, ModifierError> {
+    let mut columns = Vec::new();
+    for token in split_columns(input.trim_end_matches(',')) {
+        if let Some(column) = Schema::from_string(token)? {
+            columns.push(column);
+        }
+    }
+    Ok(columns)
+}
+
+/// Split on commas that sit outside parentheses, `<...>` element lists and
+/// quotes, so modifiers such as `ENUM('a,b', 'c')` and types such as
+/// `STRUCT<a:INT, b:INT>` stay in one piece.
+fn split_columns(input: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut angles = 0usize;
+    let mut quote: Option<char> = None;
+    let mut prev = '\0';
+    let mut start = 0;
+    for (i, c) in input.char_indices() {
+        match quote {
+            Some(q) if c == q && prev != '\\' => quote = None,
+            Some(_) => {}
+            None => match c {
+                '\'' | '"' => quote = Some(c),
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                '<' if depth == 0 => angles += 1,
+                '>' if depth == 0 => angles = angles.saturating_sub(1),
+                ',' if depth == 0 && angles == 0 => {
+                    parts.push(&input[start..i]);
+                    start = i + 1;
+                }
+                _ => {}
+            },
+        }
+        prev = c;
+    }
+    parts.push(&input[start..]);
+    parts
 }
 
 pub fn default_schema() -> Vec<Schema> {
@@ -36,21 +269,33 @@ pub fn default_schema() -> Vec<Schema> {
             name: String::from("col1"),
             datatype: String::from("VALUE"),
             modifier: None,
+            null_ratio: None,
+            locale: None,
+            children: Vec::new(),
         },
         Schema {
             name: String::from("col2"),
             datatype: String::from("VALUE"),
             modifier: None,
+            null_ratio: None,
+            locale: None,
+            children: Vec::new(),
         },
         Schema {
             name: String::from("col3"),
             datatype: String::from("VALUE"),
             modifier: None,
+            null_ratio: None,
+            locale: None,
+            children: Vec::new(),
         },
         Schema {
             name: String::from("col4"),
             datatype: String::from("VALUE"),
             modifier: None,
+            null_ratio: None,
+            locale: None,
+            children: Vec::new(),
         },
     ]
 }
@@ -58,11 +303,12 @@ pub fn default_schema() -> Vec<Schema> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::util::modifier::Value;
 
     #[test]
     fn test_happy_path_schema_parser() {
         let input = "col1:STRING, col2:INT, col3:DATE, col4:INT_RNG:(1-  100) ";
-        let subject = parse_schema(input);
+        let subject = parse_schema(input).unwrap();
 
         assert_eq!(4, subject.len());
         assert_eq!("col1", subject.first().unwrap().name);
@@ -74,20 +320,203 @@ mod test {
         assert_eq!("col4", subject.get(3).unwrap().name);
         assert_eq!("INT_RNG", subject.get(3).unwrap().datatype);
         let modifier = subject.get(3).unwrap().modifier.as_ref().unwrap();
-        assert_eq!("(1-100)", modifier);
+        let (lo, hi) = modifier.positional()[0].as_range().unwrap();
+        assert_eq!((lo.as_i64(), hi.as_i64()), (Some(1), Some(100)));
     }
 
     #[test]
     fn test_empty_schema_has_no_results() {
         let input = "";
-        let subject = parse_schema(input);
+        let subject = parse_schema(input).unwrap();
         assert_eq!(0, subject.len());
     }
 
     #[test]
     fn test_bad_schema_has_no_results() {
         let input = "naughtyschema,,23234kj23lk4j232lkjc 2lkj3 ";
-        let subject = parse_schema(input);
+        let subject = parse_schema(input).unwrap();
         assert_eq!(0, subject.len());
     }
+
+    #[test]
+    fn test_modifier_attached_to_type() {
+        let subject = parse_schema("price:DECIMAL(0..500, scale=2),id:INT_INC").unwrap();
+        assert_eq!(2, subject.len());
+        assert_eq!("DECIMAL", subject[0].datatype);
+        let modifier = subject[0].modifier.as_ref().unwrap();
+        assert_eq!(modifier.raw, "0..500, scale=2");
+        assert_eq!(modifier.option("scale").and_then(|v| v.as_i64()), Some(2));
+        assert!(subject[1].modifier.is_none());
+    }
+
+    #[test]
+    fn test_commas_and_colons_inside_modifier_do_not_split_columns() {
+        let subject = parse_schema("status:ENUM('new,ish', 'a:b'),n:NAME").unwrap();
+        assert_eq!(2, subject.len());
+        let values: Vec<&str> = subject[0]
+            .modifier
+            .as_ref()
+            .unwrap()
+            .positional()
+            .iter()
+            .filter_map(|v| match v {
+                Value::Str(s) => Some(s.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(values, vec!["new,ish", "a:b"]);
+        assert_eq!("n", subject[1].name);
+    }
+
+    #[test]
+    fn test_malformed_modifier_is_an_error_naming_the_column() {
+        let err = parse_schema("id:INT_INC,score:INT_RNG(0..10").unwrap_err();
+        assert!(
+            err.message.starts_with("column 'score':"),
+            "{}",
+            err.message
+        );
+
+        let err = parse_schema("score:INT_RNG:garbage").unwrap_err();
+        assert!(
+            err.message.starts_with("column 'score':"),
+            "{}",
+            err.message
+        );
+    }
+
+    #[test]
+    fn test_question_mark_marks_column_nullable() {
+        let subject = parse_schema("email:STRING?,id:INT_INC").unwrap();
+        assert_eq!("STRING", subject[0].datatype);
+        assert_eq!(Some(DEFAULT_NULL_RATIO), subject[0].null_ratio);
+        assert!(!subject[1].is_nullable());
+    }
+
+    #[test]
+    fn test_question_mark_may_follow_the_arguments() {
+        let subject = parse_schema("a:STRING(5..10)?,b:STRING?(5..10),t:EXPR(qty * 2)?").unwrap();
+        for column in &subject {
+            assert_eq!(
+                Some(DEFAULT_NULL_RATIO),
+                column.null_ratio,
+                "{}",
+                column.name
+            );
+        }
+        assert_eq!(subject[0].modifier, subject[1].modifier);
+        assert_eq!("qty * 2", subject[2].modifier.as_ref().unwrap().raw);
+        assert!(parse_schema("a:STRING(5..10)?x").is_err());
+    }
+
+    #[test]
+    fn test_null_option_is_lifted_out_of_the_modifier() {
+        let subject = parse_schema("a:NAME(null=0.25),p:DECIMAL?(0..5, null=0.5)").unwrap();
+        assert_eq!(Some(0.25), subject[0].null_ratio);
+        assert!(subject[0].modifier.is_none());
+        assert_eq!(Some(0.5), subject[1].null_ratio);
+        let modifier = subject[1].modifier.as_ref().unwrap();
+        assert_eq!(1, modifier.args.len());
+        assert_eq!("0..5", modifier.raw);
+    }
+
+    #[test]
+    fn test_nullable_expr_keeps_its_expression() {
+        let subject = parse_schema("t:EXPR?(qty * 2)").unwrap();
+        assert_eq!(Some(DEFAULT_NULL_RATIO), subject[0].null_ratio);
+        assert_eq!("qty * 2", subject[0].modifier.as_ref().unwrap().raw);
+    }
+
+    #[test]
+    fn test_null_ratio_out_of_range_is_an_error() {
+        let err = parse_schema("a:NAME(null=1.5)").unwrap_err();
+        assert!(err.message.starts_with("column 'a':"), "{}", err.message);
+        assert!(parse_schema("a:NAME(null=high)").is_err());
+    }
+
+    #[test]
+    fn test_locale_option_is_lifted_out_of_the_modifier() {
+        let subject =
+            parse_schema("a:NAME(locale=ja_JP),b:PHONE?(locale='fr-FR', null=0.2)").unwrap();
+        assert_eq!(Some(Locale::JaJp), subject[0].locale);
+        assert!(subject[0].modifier.is_none());
+        assert_eq!(Some(Locale::FrFr), subject[1].locale);
+        assert_eq!(Some(0.2), subject[1].null_ratio);
+        assert!(subject[1].modifier.is_none());
+        assert_eq!(None, parse_schema("c:NAME").unwrap()[0].locale);
+    }
+
+    #[test]
+    fn test_lifted_options_keep_quotes_in_the_rest() {
+        let subject = parse_schema(r#"s:ENUM('it\'s', "o'k", null=0.2)"#).unwrap();
+        let m = subject[0].modifier.as_ref().unwrap();
+        assert_eq!(r"'it\'s', 'o\'k'", m.raw);
+        assert_eq!(
+            parse_modifier(&format!("({})", m.raw)).unwrap().args,
+            m.args
+        );
+    }
+
+    #[test]
+    fn test_nested_types_parse_their_element_types() {
+        let subject = parse_schema(
+            "tags:LIST<LOREM_WORD>(0..5),address:STRUCT<street:STRING, zip:ZIP_CODE?>?,n:INT",
+        )
+        .unwrap();
+        assert_eq!(3, subject.len());
+        assert_eq!("LIST", subject[0].datatype);
+        assert_eq!("0..5", subject[0].modifier.as_ref().unwrap().raw);
+        assert_eq!("tags", subject[0].children[0].name);
+        assert_eq!("LOREM_WORD", subject[0].children[0].datatype);
+        assert_eq!("STRUCT", subject[1].datatype);
+        assert!(subject[1].is_nullable());
+        let fields: Vec<(&str, &str)> = subject[1]
+            .children
+            .iter()
+            .map(|c| (c.name.as_str(), c.datatype.as_str()))
+            .collect();
+        assert_eq!(fields, [("street", "STRING"), ("zip", "ZIP_CODE")]);
+        assert!(subject[1].children[1].is_nullable());
+
+        let nested = parse_schema("x:LIST<STRUCT<a:INT(1..3), b:LIST<UUID>>>").unwrap();
+        let item = &nested[0].children[0];
+        assert_eq!("STRUCT", item.datatype);
+        assert_eq!("LIST", item.children[1].datatype);
+        assert_eq!("UUID", item.children[1].children[0].datatype);
+    }
+
+    #[test]
+    fn test_nested_types_are_checked() {
+        for (bad, message) in [
+            ("t:LIST", "LIST needs its element types"),
+            ("t:LIST<>", "LIST<> needs an element type"),
+            ("t:STRUCT<a:INT, a:STRING>", "field 'a' is declared twice"),
+            ("t:STRUCT<a>", "is not 'name:TYPE'"),
+            ("t:INT<STRING>", "only LIST and STRUCT"),
+            ("t:LIST<TEMPLATE('{x}')>", "cannot be nested"),
+        ] {
+            let err = parse_schema(bad).unwrap_err();
+            assert!(err.message.starts_with("column 't': "), "{}", err.message);
+            assert!(err.message.contains(message), "{bad}: {}", err.message);
+        }
+    }
+
+    #[test]
+    fn test_locale_option_is_checked() {
+        let err = parse_schema("n:INT(locale=ja_JP)").unwrap_err();
+        assert!(
+            err.message
+                .starts_with("column 'n': INT does not vary by locale"),
+            "{}",
+            err.message
+        );
+        let err = parse_schema("n:NAME(locale=de_DE)").unwrap_err();
+        assert!(
+            err.message
+                .starts_with("column 'n': unknown locale 'de_DE'"),
+            "{}",
+            err.message
+        );
+        assert!(parse_schema("n:NAME(locale=en, locale=fr_FR)").is_err());
+    }
 }
@@ -0,0 +1,559 @@
+//! Structured schema files for `--schema-file`.
+//!
+//! A TOML, YAML or JSON document lists the same columns the `-s` string
+//! does, one entry per column, so modifiers may contain commas and colons
+//! and long schemas stay readable. The format is picked from the file
+//! extension. Every error carries the file name and the line of the entry
+//! (or, for syntax errors, the spot) it is about: bad modifiers and unknown
+//! types included.
+//!
+//! ```toml
+//! [[columns]]
+//! name = "id"
+//! type = "INT_INC"
+//!
+//! [[columns]]
+//! name = "score"
+//! type = "INT_RNG"
+//! modifier = "(0-100)"
+//! ```
+
+use crate::util::schema::Schema;
+use crate::util::validate::check_located;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaFileError {
+    pub message: String,
+}
+
+impl fmt::Display for SchemaFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for SchemaFileError {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SchemaFormat {
+    Toml,
+    Yaml,
+    Json,
+}
+
+impl SchemaFormat {
+    fn from_path(path: &str) -> Option<Self> {
+        let ext = std::path::Path::new(path)
+            .extension()?
+            .to_str()?
+            .to_lowercase();
+        match ext.as_str() {
+            "toml" => Some(Self::Toml),
+            "yaml" | "yml" => Some(Self::Yaml),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct SchemaDoc {
+    columns: Vec<ColumnSpec>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ColumnSpec {
+    pub(crate) name: String,
+    #[serde(rename = "type")]
+    pub(crate) datatype: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) modifier: Option<String>,
+    /// Null ratio; same as `null=<ratio>` in the modifier.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) null: Option<f64>,
+}
+
+/// Read and parse the schema file at `path`.
+pub fn load_schema_file(path: &str) -> Result<SchemaFile, SchemaFileError> {
+    let format = SchemaFormat::from_path(path).ok_or_else(|| SchemaFileError {
+        message: format!(
+            "schema file '{path}': unrecognised extension; expected .toml, .yaml, .yml or .json"
+        ),
+    })?;
+    let contents = std::fs::read_to_string(path).map_err(|e| SchemaFileError {
+        message: format!("failed to read schema file '{path}': {e}"),
+    })?;
+    parse_schema_file(&contents, format, path)
+}
+
+/// Render `columns` as a schema file that `load_schema_file` reads back.
+pub(crate) fn render_schema_file(
+    columns: Vec<ColumnSpec>,
+    format: SchemaFormat,
+) -> Result<String, SchemaFileError> {
+    let doc = SchemaDoc { columns };
+    let rendered = match format {
+        SchemaFormat::Toml => toml::to_string(&doc).map_err(|e| e.to_string()),
+        SchemaFormat::Yaml => serde_yaml::to_string(&doc).map_err(|e| e.to_string()),
+        SchemaFormat::Json => serde_json::to_string_pretty(&doc)
+            .map(|json| json + "\n")
+            .map_err(|e| e.to_string()),
+    };
+    rendered.map_err(|e| SchemaFileError {
+        message: format!("failed to render schema file: {e}"),
+    })
+}
+
+/// A schema file's columns, each with the line its entry starts on.
+#[derive(Debug)]
+pub struct SchemaFile {
+    path: String,
+    pub columns: Vec<Schema>,
+    lines: Vec<Option<usize>>,
+}
+
+impl SchemaFile {
+    /// `check_columns` for the file's columns, with every error and warning
+    /// prefixed by the `path:line` of the column it is about.
+    pub fn check(
+        &self,
+        rows: usize,
+        strict: bool,
+        now: NaiveDateTime,
+    ) -> Result<(), Box<dyn Error>> {
+        check_located(&self.columns, rows, strict, now, |i| Some(self.at(i)))
+    }
+
+    fn at(&self, index: usize) -> String {
+        match self.lines.get(index).copied().flatten() {
+            Some(line) => format!("{}:{line}", self.path),
+            None => self.path.clone(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TomlDoc {
+    columns: Vec<toml::Spanned<ColumnSpec>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonDoc<'a> {
+    #[serde(borrow)]
+    columns: Vec<&'a RawValue>,
+}
+
+/// Deserialize the file's entries, each with its 1-based line.
+fn parse_entries(
+    contents: &str,
+    format: SchemaFormat,
+    path: &str,
+) -> Result<Vec<(ColumnSpec, Option<usize>)>, SchemaFileError> {
+    match format {
+        SchemaFormat::Toml => {
+            let doc: TomlDoc = toml::from_str(contents).map_err(|e| {
+                let line = e.span().map(|s| line_of_offset(contents, s.start));
+                located(path, line, e.message())
+            })?;
+            Ok(doc
+                .columns
+                .into_iter()
+                .map(|c| {
+                    let line = line_of_offset(contents, c.span().start);
+                    (c.into_inner(), Some(line))
+                })
+                .collect())
+        }
+        SchemaFormat::Yaml => {
+            let doc: SchemaDoc = serde_yaml::from_str(contents).map_err(|e| {
+                let line = e.location().map(|l| l.line());
+                located(path, line, &strip_location_suffix(&e.to_string()))
+            })?;
+            let lines = yaml_entry_lines(contents);
+            let lines: Vec<Option<usize>> = if lines.len() == doc.columns.len() {
+                lines.into_iter().map(Some).collect()
+            } else {
+                vec![None; doc.columns.len()]
+            };
+            Ok(doc.columns.into_iter().zip(lines).collect())
+        }
+        SchemaFormat::Json => {
+            let json_error = |e: serde_json::Error, first_line: usize| {
+                let line = first_line + e.line().saturating_sub(1);
+                located(path, Some(line), &strip_location_suffix(&e.to_string()))
+            };
+            let doc: JsonDoc = serde_json::from_str(contents).map_err(|e| json_error(e, 1))?;
+            doc.columns
+                .into_iter()
+                .map(|raw| {
+                    let offset = raw.get().as_ptr() as usize - contents.as_ptr() as usize;
+                    let line = line_of_offset(contents, offset);
+                    let column =
+                        serde_json::from_str(raw.get()).map_err(|e| json_error(e, line))?;
+                    Ok((column, Some(line)))
+                })
+                .collect()
+        }
+    }
+}
+
+/// Lines of the entries of a block-style `columns:` sequence. Flow-style
+/// sequences (`columns: [...]`) yield none.
+fn yaml_entry_lines(contents: &str) -> Vec<usize> {
+    let mut lines = Vec::new();
+    let mut in_columns = false;
+    let mut indent = None;
+    for (idx, line) in contents.lines().enumerate() {
+        let body = line.trim_start();
+        if body.is_empty() || body.starts_with('#') {
+            continue;
+        }
+        let depth = line.len() - body.len();
+        if depth == 0 && !body.starts_with('-') {
+            in_columns = body.trim_end() == "columns:";
+            continue;
+        }
+        if !in_columns || !(body.starts_with("- ") || body.trim_end() == "-") {
+            continue;
+        }
+        if *indent.get_or_insert(depth) == depth {
+            lines.push(idx + 1);
+        }
+    }
+    lines
+}
+
+fn parse_schema_file(
+    contents: &str,
+    format: SchemaFormat,
+    path: &str,
+) -> Result<SchemaFile, SchemaFileError> {
+    let entries = parse_entries(contents, format, path)?;
+
+    if entries.is_empty() {
+        return Err(SchemaFileError {
+            message: format!("{path}: 'columns' is empty; declare at least one column"),
+        });
+    }
+
+    let mut seen: HashSet<&str> = HashSet::new();
+    for (idx, (col, line)) in entries.iter().enumerate() {
+        let position = idx + 1;
+        if col.name.trim().is_empty() {
+            return Err(located(
+                path,
+                *line,
+                &format!("column #{position} has an empty 'name'"),
+            ));
+        }
+        if col.datatype.trim().trim_end_matches('?').is_empty() {
+            return Err(located(
+                path,
+                *line,
+                &format!("column #{position} ('{}') has an empty 'type'", col.name),
+            ));
+        }
+        if !seen.insert(col.name.as_str()) {
+            return Err(located(
+                path,
+                *line,
+                &format!("column #{position}: duplicate column name '{}'", col.name),
+            ));
+        }
+    }
+
+    let mut columns = Vec::with_capacity(entries.len());
+    let mut lines = Vec::with_capacity(entries.len());
+    for (c, line) in entries {
+        // The surrounding parentheses are optional in a file.
+        let modifier = c.modifier.as_deref().map(str::trim).map(|m| {
+            if m.starts_with('(') {
+                m.to_string()
+            } else {
+                format!("({m})")
+            }
+        });
+        let mut column = Schema::new(c.name.trim(), &c.datatype, modifier.as_deref())
+            .map_err(|e| located(path, line, &e.message))?;
+        if let Some(ratio) = c.null {
+            if !(0.0..=1.0).contains(&ratio) {
+                return Err(located(
+                    path,
+                    line,
+                    &format!(
+                        "column '{}': null ratio must be between 0 and 1, got '{ratio}'",
+                        column.name
+                    ),
+                ));
+            }
+            column.null_ratio = Some(ratio);
+        }
+        columns.push(column);
+        lines.push(line);
+    }
+    Ok(SchemaFile {
+        path: path.to_string(),
+        columns,
+        lines,
+    })
+}
+
+fn located(path: &str, line: Option<usize>, message: &str) -> SchemaFileError {
+    let message = message.trim();
+    SchemaFileError {
+        message: match line {
+            Some(line) => format!("{path}:{line}: {message}"),
+            None => format!("{path}: {message}"),
+        },
+    }
+}
+
+/// 1-based line number of byte `offset` in `contents`.
+fn line_of_offset(contents: &str, offset: usize) -> usize {
+    let end = offset.min(contents.len());
+    contents.as_bytes()[..end]
+        .iter()
+        .filter(|&&b| b == b'\n')
+        .count()
+        + 1
+}
+
+/// serde_json and serde_yaml append " at line N column M" to their messages;
+/// drop it because the line is already in our prefix.
+fn strip_location_suffix(message: &str) -> String {
+    match message.rfind(" at line ") {
+        Some(idx) => message[..idx].to_string(),
+        None => message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn format_is_picked_from_extension() {
+        assert_eq!(SchemaFormat::from_path("a.toml"), Some(SchemaFormat::Toml));
+        assert_eq!(SchemaFormat::from_path("a.YML"), Some(SchemaFormat::Yaml));
+        assert_eq!(SchemaFormat::from_path("a.yaml"), Some(SchemaFormat::Yaml));
+        assert_eq!(SchemaFormat::from_path("a.json"), Some(SchemaFormat::Json));
+        assert_eq!(SchemaFormat::from_path("a.csv"), None);
+        assert_eq!(SchemaFormat::from_path("schema"), None);
+    }
+
+    #[test]
+    fn toml_maps_onto_schema() {
+        let src = r#"
+[[columns]]
+name = "id"
+type = "INT_INC"
+
+[[columns]]
+name = "score"
+type = "INT_RNG"
+modifier = "(0-100)"
+"#;
+        let cols = parse_schema_file(src, SchemaFormat::Toml, "s.toml")
+            .unwrap()
+            .columns;
+        assert_eq!(cols.len(), 2);
+        assert_eq!(cols[0].name, "id");
+        assert_eq!(cols[0].datatype, "INT_INC");
+        assert!(cols[0].modifier.is_none());
+        assert_eq!(cols[1].modifier.as_ref().unwrap().raw, "0-100");
+    }
+
+    #[test]
+    fn yaml_and_json_map_onto_schema() {
+        let yaml = "columns:\n  - name: id\n    type: INT_INC\n  - name: who\n    type: NAME\n";
+        let cols = parse_schema_file(yaml, SchemaFormat::Yaml, "s.yaml")
+            .unwrap()
+            .columns;
+        assert_eq!(cols[1].datatype, "NAME");
+
+        let json = r#"{"columns": [{"name": "id", "type": "UUID"}]}"#;
+        let cols = parse_schema_file(json, SchemaFormat::Json, "s.json")
+            .unwrap()
+            .columns;
+        assert_eq!(cols[0].datatype, "UUID");
+    }
+
+    #[test]
+    fn toml_syntax_error_reports_line() {
+        let src = "[[columns]]\nname = \"id\"\ntype = INT_INC\n";
+        let err = parse_schema_file(src, SchemaFormat::Toml, "s.toml").unwrap_err();
+        assert!(err.message.starts_with("s.toml:3:"), "got: {}", err.message);
+    }
+
+    #[test]
+    fn unknown_field_is_rejected_with_line() {
+        let yaml = "columns:\n  - name: id\n    type: INT_INC\n    nulable: true\n";
+        let err = parse_schema_file(yaml, SchemaFormat::Yaml, "s.yaml").unwrap_err();
+        assert!(err.message.contains("nulable"), "got: {}", err.message);
+        assert!(err.message.starts_with("s.yaml:"), "got: {}", err.message);
+    }
+
+    #[test]
+    fn json_missing_type_reports_line() {
+        let json = "{\"columns\": [\n  {\"name\": \"id\"}\n]}";
+        let err = parse_schema_file(json, SchemaFormat::Json, "s.json").unwrap_err();
+        assert!(err.message.starts_with("s.json:2:"), "got: {}", err.message);
+        assert!(err.message.contains("type"), "got: {}", err.message);
+    }
+
+    #[test]
+    fn duplicate_column_names_are_rejected() {
+        let json = r#"{"columns": [{"name": "id", "type": "INT"}, {"name": "id", "type": "INT"}]}"#;
+        let err = parse_schema_file(json, SchemaFormat::Json, "s.json").unwrap_err();
+        assert!(
+            err.message.contains("duplicate column name 'id'"),
+            "got: {}",
+            err.message
+        );
+    }
+
+    #[test]
+    fn empty_column_list_is_rejected() {
+        let err = parse_schema_file("columns = []\n", SchemaFormat::Toml, "s.toml").unwrap_err();
+        assert!(err.message.contains("empty"), "got: {}", err.message);
+    }
+
+    #[test]
+    fn unknown_extension_is_rejected() {
+        let err = load_schema_file("schema.csv").unwrap_err();
+        assert!(
+            err.message.contains("unrecognised extension"),
+            "got: {}",
+            err.message
+        );
+    }
+
+    #[test]
+    fn modifier_parentheses_are_optional() {
+        let json =
+            r#"{"columns": [{"name": "p", "type": "DECIMAL", "modifier": "0..500, scale=2"}]}"#;
+        let cols = parse_schema_file(json, SchemaFormat::Json, "s.json")
+            .unwrap()
+            .columns;
+        let m = cols[0].modifier.as_ref().unwrap();
+        assert_eq!(m.option("scale").and_then(|v| v.as_i64()), Some(2));
+    }
+
+    #[test]
+    fn malformed_modifier_names_file_and_column() {
+        let json = r#"{"columns": [{"name": "p", "type": "DECIMAL", "modifier": "(0..500"}]}"#;
+        let err = parse_schema_file(json, SchemaFormat::Json, "s.json").unwrap_err();
+        assert!(
+            err.message
+                .starts_with("s.json:1: column 'p': malformed modifier"),
+            "got: {}",
+            err.message
+        );
+    }
+
+    #[test]
+    fn null_field_marks_column_nullable() {
+        let yaml = "columns:\n  - name: email\n    type: NAME\n    null: 0.2\n  - name: x\n    type: NAME?\n";
+        let cols = parse_schema_file(yaml, SchemaFormat::Yaml, "s.yaml")
+            .unwrap()
+            .columns;
+        assert_eq!(cols[0].null_ratio, Some(0.2));
+        assert!(cols[1].is_nullable());
+    }
+
+    #[test]
+    fn rendered_files_read_back() {
+        let columns = || {
+            vec![
+                ColumnSpec {
+                    name: "id".into(),
+                    datatype: "INT_INC".into(),
+                    modifier: None,
+                    null: None,
+                },
+                ColumnSpec {
+                    name: "plan".into(),
+                    datatype: "ENUM".into(),
+                    modifier: Some("('free'=3, 'it\\'s'=1)".into()),
+                    null: Some(0.25),
+                },
+            ]
+        };
+        for format in [SchemaFormat::Toml, SchemaFormat::Yaml, SchemaFormat::Json] {
+            let rendered = render_schema_file(columns(), format).unwrap();
+            let cols = parse_schema_file(&rendered, format, "s").unwrap().columns;
+            assert_eq!(cols.len(), 2, "{rendered}");
+            assert!(cols[0].modifier.is_none(), "{rendered}");
+            assert_eq!(cols[1].modifier.as_ref().unwrap().positional().len(), 2);
+            assert_eq!(cols[1].null_ratio, Some(0.25), "{rendered}");
+        }
+    }
+
+    #[test]
+    fn entries_are_located_in_every_format() {
+        let toml = "[[columns]]\nname = \"id\"\ntype = \"INT_INC\"\n\n[[columns]]\nname = \"who\"\ntype = \"NAME\"\n";
+        let yaml = "# users\ncolumns:\n- name: id\n  type: INT_INC\n- name: who\n  type: NAME\n";
+        let json = "{\"columns\": [\n  {\"name\": \"id\", \"type\": \"INT_INC\"},\n\n  {\"name\": \"who\", \"type\": \"NAME\"}\n]}";
+        for (src, format, at) in [
+            (toml, SchemaFormat::Toml, ["s:1", "s:5"]),
+            (yaml, SchemaFormat::Yaml, ["s:3", "s:5"]),
+            (json, SchemaFormat::Json, ["s:2", "s:4"]),
+        ] {
+            let file = parse_schema_file(src, format, "s").unwrap();
+            assert_eq!([file.at(0), file.at(1)], at, "{src}");
+        }
+        let flow = parse_schema_file("columns: [{name: id, type: UUID}]", SchemaFormat::Yaml, "s")
+            .unwrap();
+        assert_eq!(flow.at(0), "s");
+    }
+
+    #[test]
+    fn bad_modifiers_and_types_report_their_entry() {
+        let toml = "[[columns]]\nname = \"id\"\ntype = \"INT_INC\"\n\n[[columns]]\nname = \"age\"\ntype = \"INT\"\nmodifier = \"(10..1)\"\n";
+        let err = parse_schema_file(toml, SchemaFormat::Toml, "s.toml")
+            .unwrap()
+            .check(10, true, NaiveDateTime::default())
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.starts_with("s.toml:5: column 'age': INT modifier:"),
+            "{err}"
+        );
+
+        let yaml = "columns:\n  - name: id\n    type: INT_INC\n  - name: who\n    type: NMAE\n";
+        let err = parse_schema_file(yaml, SchemaFormat::Yaml, "s.yaml")
+            .unwrap()
+            .check(10, true, NaiveDateTime::default())
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.starts_with("s.yaml:4: column 'who': unknown type 'NMAE'"),
+            "{err}"
+        );
+
+        let json = "{\"columns\": [\n  {\"name\": \"id\", \"type\": \"INT\"},\n  {\"name\": \"id\", \"type\": \"INT\"}\n]}";
+        let err = parse_schema_file(json, SchemaFormat::Json, "s.json").unwrap_err();
+        assert!(
+            err.message.starts_with("s.json:3: column #2"),
+            "{}",
+            err.message
+        );
+        let json = "{\"columns\": [\n  {\"name\": \"id\",\n   \"type\": \"INT\", \"nul\": 1}\n]}";
+        let err = parse_schema_file(json, SchemaFormat::Json, "s.json").unwrap_err();
+        assert!(
+            err.message.starts_with("s.json:3: unknown field `nul`"),
+            "{}",
+            err.message
+        );
+    }
+}
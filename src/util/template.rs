@@ -0,0 +1,267 @@
+//! `TEMPLATE` columns: values composed from other columns of the same row.
+//!
+//! ```text
+//! email:TEMPLATE("{first_name|lower}.{last_name|lower}@example.com")
+//! handle:TEMPLATE("{name|slug|truncate(12)}")
+//! ```
+//!
+//! A placeholder is `{column}` followed by any number of `|filter`s, applied
+//! left to right: `lower`, `upper`, `slug` and `truncate(n)`. `{{` and `}}`
+//! are literal braces. A placeholder whose cell is null makes the whole cell
+//! null. Ordering against other columns is handled in `derived`.
+
+use crate::util::modifier::ModifierError;
+use crate::util::output::decimal_as_text;
+use crate::util::schema::Schema;
+use polars::prelude::*;
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Filter {
+    Lower,
+    Upper,
+    Slug,
+    Truncate(usize),
+}
+
+impl Filter {
+    fn apply(&self, s: String) -> String {
+        match self {
+            Filter::Lower => s.to_lowercase(),
+            Filter::Upper => s.to_uppercase(),
+            Filter::Slug => slug(&s),
+            Filter::Truncate(n) => s.chars().take(*n).collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Part {
+    Literal(String),
+    Column { name: String, filters: Vec<Filter> },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+impl Template {
+    /// Column names referenced by the template, in order of appearance.
+    pub fn references(&self) -> Vec<&str> {
+        self.parts
+            .iter()
+            .filter_map(|p| match p {
+                Part::Column { name, .. } => Some(name.as_str()),
+                Part::Literal(_) => None,
+            })
+            .collect()
+    }
+}
+
+/// Parse the text of a template, e.g. `{first_name|lower}@example.com`.
+pub fn parse_template(text: &str) -> Result<Template, String> {
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut inner = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(ch) => inner.push(ch),
+                        None => return Err(format!("unclosed '{{' in \"{text}\"")),
+                    }
+                }
+                if !literal.is_empty() {
+                    parts.push(Part::Literal(std::mem::take(&mut literal)));
+                }
+                parts.push(placeholder(&inner)?);
+            }
+            '}' => return Err(format!("unmatched '}}' in \"{text}\"; write '}}}}'")),
+            _ => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        parts.push(Part::Literal(literal));
+    }
+    if !parts.iter().any(|p| matches!(p, Part::Column { .. })) {
+        return Err(format!(
+            "\"{text}\" references no columns; expected placeholders like {{first_name}}"
+        ));
+    }
+    Ok(Template { parts })
+}
+
+fn placeholder(inner: &str) -> Result<Part, String> {
+    let mut pieces = inner.split('|').map(str::trim);
+    let name = pieces.next().unwrap_or_default();
+    if name.is_empty() {
+        return Err(format!("empty placeholder '{{{inner}}}'"));
+    }
+    let filters = pieces.map(filter).collect::<Result<Vec<_>, _>>()?;
+    Ok(Part::Column {
+        name: name.to_string(),
+        filters,
+    })
+}
+
+fn filter(text: &str) -> Result<Filter, String> {
+    match text {
+        "lower" => return Ok(Filter::Lower),
+        "upper" => return Ok(Filter::Upper),
+        "slug" => return Ok(Filter::Slug),
+        _ => {}
+    }
+    if let Some(arg) = text
+        .strip_prefix("truncate(")
+        .and_then(|t| t.strip_suffix(')'))
+    {
+        return arg
+            .trim()
+            .parse()
+            .map(Filter::Truncate)
+            .map_err(|_| format!("truncate expects a length, got '{arg}'"));
+    }
+    Err(format!(
+        "unknown filter '{text}'; expected one of: lower, upper, slug, truncate(n)"
+    ))
+}
+
+/// Lowercase ASCII alphanumerics separated by single dashes.
+fn slug(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(c.to_ascii_lowercase());
+        } else if !out.is_empty() && !out.ends_with('-') {
+            out.push('-');
+        }
+    }
+    while out.ends_with('-') {
+        out.pop();
+    }
+    out
+}
+
+/// `TEMPLATE("…")`: one quoted template string.
+pub(crate) fn template_spec(element: &Schema) -> Result<Template, ModifierError> {
+    let err = |msg: String| ModifierError {
+        message: format!("column '{}': TEMPLATE modifier: {msg}", element.name),
+    };
+    let Some(m) = &element.modifier else {
+        return Err(err(
+            "expected a quoted template like (\"{first_name}.{last_name}@example.com\")"
+                .to_string(),
+        ));
+    };
+    m.check_options(&[]).map_err(err)?;
+    m.check_positional(1).map_err(err)?;
+    let text = m
+        .positional()
+        .first()
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| err(format!("expected a quoted template, got '({})'", m.raw)))?;
+    parse_template(text).map_err(err)
+}
+
+impl Template {
+    /// Render row by row against `frame`, which holds every referenced column.
+    pub(crate) fn render(&self, name: &str, frame: &DataFrame) -> PolarsResult<Series> {
+        let mut sources: HashMap<&str, StringChunked> = HashMap::new();
+        for reference in self.references() {
+            if sources.contains_key(reference) {
+                continue;
+            }
+            let column = frame.column(reference)?;
+            let text = match column.dtype() {
+                DataType::Decimal(_, _) => decimal_as_text(column)?,
+                DataType::Datetime(_, _) => column
+                    .datetime()?
+                    .to_string("%Y-%m-%dT%H:%M:%S%:z")?
+                    .into_series(),
+                _ => column.cast(&DataType::String)?,
+            };
+            sources.insert(reference, text.str()?.clone());
+        }
+
+        let values: Vec<Option<String>> = (0..frame.height())
+            .map(|row| {
+                let mut out = String::new();
+                for part in &self.parts {
+                    match part {
+                        Part::Literal(s) => out.push_str(s),
+                        Part::Column { name, filters } => {
+                            let value = sources[name.as_str()].get(row)?.to_string();
+                            out.push_str(&filters.iter().fold(value, |v, f| f.apply(v)));
+                        }
+                    }
+                }
+                Some(out)
+            })
+            .collect();
+        Ok(Series::new(name, values))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_placeholders_filters_and_escapes() {
+        let t = parse_template("{{id}} {first|lower|truncate(1)}.{last}").unwrap();
+        assert_eq!(t.references(), vec!["first", "last"]);
+        assert_eq!(t.parts[0], Part::Literal("{id} ".to_string()));
+        assert_eq!(
+            t.parts[1],
+            Part::Column {
+                name: "first".to_string(),
+                filters: vec![Filter::Lower, Filter::Truncate(1)],
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_templates() {
+        for bad in [
+            "{first",
+            "first}",
+            "{}",
+            "{first|shout}",
+            "{first|truncate(x)}",
+            "no placeholders",
+        ] {
+            assert!(parse_template(bad).is_err(), "{bad} should fail");
+        }
+    }
+
+    #[test]
+    fn slug_collapses_separators() {
+        assert_eq!(slug("  Hello, World!  "), "hello-world");
+        assert_eq!(slug("Ünïcode & co"), "n-code-co");
+    }
+
+    #[test]
+    fn renders_rows_with_filters_and_nulls() {
+        let frame = DataFrame::new(vec![
+            Series::new("first", vec![Some("Ada"), None]),
+            Series::new("id", vec![7i32, 8]),
+        ])
+        .unwrap();
+        let t = parse_template("{first|lower}-{id}@{first|upper|truncate(2)}").unwrap();
+        let out = t.render("handle", &frame).unwrap();
+        let out: Vec<Option<&str>> = out.str().unwrap().into_iter().collect();
+        assert_eq!(out, vec![Some("ada-7@AD"), None]);
+    }
+}
@@ -0,0 +1,434 @@
+//! Ranges for `DATE` and `DATE_TIME` columns.
+//!
+//! ```text
+//! signup:DATE(2020-01-01..2024-12-31)
+//! shipped:DATE(today-30d..today, weekdays=true)
+//! event_ts:DATE_TIME(now-90d..now, weekdays=true, hours=9..17)
+//! ```
+//!
+//! A bound is a date (`2024-12-31`), a timestamp (`2024-12-31T08:30`, in
+//! `DATE_TIME` only), or `now`/`today` with an optional offset in weeks,
+//! days, hours, minutes or seconds (`now-90d`, `now+6h`). Both bounds are
+//! inclusive. `weekdays=true` keeps Monday to Friday; `hours=9..17` keeps
+//! times from 09:00 up to, not including, 17:00. Timestamps are UTC.
+//!
+//! Without a modifier both types span `DEFAULT_RANGE`, a fixed window rather
+//! than one relative to `now`, so seeded output never depends on the clock.
+//! `now` itself is read once per run (see `RunRng::now`).
+
+use crate::util::modifier::{Modifier, Value};
+use crate::util::schema::Schema;
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+use rand::Rng;
+
+/// The span of a `DATE` or `DATE_TIME` column without a modifier.
+const DEFAULT_RANGE: (&str, &str) = ("2020-01-01", "2024-12-31");
+
+pub(crate) struct DateSpec {
+    lo: NaiveDate,
+    hi: NaiveDate,
+    weekdays: bool,
+}
+
+impl DateSpec {
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> NaiveDate {
+        let span = (self.hi - self.lo).num_days();
+        loop {
+            let day = self.lo + Duration::days(rng.gen_range(0..=span));
+            if !self.weekdays || is_weekday(day) {
+                return day;
+            }
+        }
+    }
+}
+
+pub(crate) struct DateTimeSpec {
+    lo: NaiveDateTime,
+    hi: NaiveDateTime,
+    weekdays: bool,
+    hours: Option<(u32, u32)>,
+}
+
+impl DateTimeSpec {
+    /// Picks a valid day, then a time inside that day's allowed window.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> NaiveDateTime {
+        let (first, last) = (self.lo.date(), self.hi.date());
+        let span = (last - first).num_days();
+        loop {
+            let day = first + Duration::days(rng.gen_range(0..=span));
+            if let Some((start, end)) = self.window(day) {
+                let secs = (end - start).num_seconds();
+                return start + Duration::seconds(rng.gen_range(0..=secs));
+            }
+        }
+    }
+
+    /// The inclusive span of `day` that satisfies every constraint.
+    fn window(&self, day: NaiveDate) -> Option<(NaiveDateTime, NaiveDateTime)> {
+        if self.weekdays && !is_weekday(day) {
+            return None;
+        }
+        let (open, close) = match self.hours {
+            Some((from, to)) => (
+                day.and_time(NaiveTime::from_hms_opt(from, 0, 0)?),
+                day.and_time(NaiveTime::MIN) + Duration::hours(to as i64) - Duration::seconds(1),
+            ),
+            None => (
+                day.and_time(NaiveTime::MIN),
+                day.and_time(NaiveTime::MIN) + Duration::days(1) - Duration::seconds(1),
+            ),
+        };
+        let (start, end) = (open.max(self.lo), close.min(self.hi));
+        (start <= end).then_some((start, end))
+    }
+}
+
+/// Every day of `DEFAULT_RANGE`.
+pub(crate) fn default_date_spec() -> DateSpec {
+    let (lo, hi) = default_range();
+    DateSpec {
+        lo,
+        hi,
+        weekdays: false,
+    }
+}
+
+/// Every second of `DEFAULT_RANGE`.
+pub(crate) fn default_date_time_spec() -> DateTimeSpec {
+    let (lo, hi) = default_range();
+    DateTimeSpec {
+        lo: lo.and_time(NaiveTime::MIN),
+        hi: hi.and_hms_opt(23, 59, 59).expect("valid time"),
+        weekdays: false,
+        hours: None,
+    }
+}
+
+fn default_range() -> (NaiveDate, NaiveDate) {
+    let day = |text| NaiveDate::parse_from_str(text, "%Y-%m-%d").expect("valid default date");
+    (day(DEFAULT_RANGE.0), day(DEFAULT_RANGE.1))
+}
+
+/// Whether a `DATE` or `DATE_TIME` modifier has a `now` or `today` bound.
+pub(crate) fn is_relative(m: &Modifier) -> bool {
+    let Some((lo, hi)) = m.positional().first().and_then(|v| v.as_range()) else {
+        return false;
+    };
+    [lo, hi]
+        .into_iter()
+        .filter_map(|v| bound_text(v).ok())
+        .any(|text| text.starts_with("now") || text.starts_with("today"))
+}
+
+/// Whether any `DATE` or `DATE_TIME` column in `columns`, nested ones
+/// included, has a `now` or `today` bound.
+pub(crate) fn uses_clock(columns: &[Schema]) -> bool {
+    columns.iter().any(|column| {
+        let dated = matches!(column.datatype.as_str(), "DATE" | "DATE_TIME");
+        (dated && column.modifier.as_ref().is_some_and(is_relative)) || uses_clock(&column.children)
+    })
+}
+
+fn is_weekday(day: NaiveDate) -> bool {
+    !matches!(day.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+/// `DATE(lo..hi, weekdays=true)`, with `now` and `today` read from `now`.
+pub(crate) fn date_spec(m: &Modifier, now: NaiveDateTime) -> Result<DateSpec, String> {
+    m.check_options(&["weekdays"])?;
+    let (lo, hi) = range(m, |v| date_bound(v, now), "2020-01-01..2024-12-31")?;
+    let weekdays = flag(m, "weekdays")?;
+    let spec = DateSpec { lo, hi, weekdays };
+    let has_weekday = (0..7)
+        .map(|d| lo + Duration::days(d))
+        .take_while(|day| *day <= hi)
+        .any(is_weekday);
+    if weekdays && !has_weekday {
+        return Err(format!("{lo}..{hi} contains no weekdays"));
+    }
+    Ok(spec)
+}
+
+/// `DATE_TIME(lo..hi, weekdays=true, hours=9..17)`, with `now` and `today`
+/// read from `now`.
+pub(crate) fn date_time_spec(m: &Modifier, now: NaiveDateTime) -> Result<DateTimeSpec, String> {
+    m.check_options(&["weekdays", "hours"])?;
+    let (lo, hi) = range(m, |v| date_time_bound(v, now), "now-90d..now")?;
+    let hours = match m.option("hours") {
+        None => None,
+        Some(v) => {
+            let (from, to) = v
+                .as_range()
+                .and_then(|(a, b)| Some((a.as_i64()?, b.as_i64()?)))
+                .filter(|(a, b)| 0 <= *a && a < b && *b <= 24)
+                .ok_or_else(|| {
+                    format!("hours expects whole hours like 9..17 within 0..24, got '{v}'")
+                })?;
+            Some((from as u32, to as u32))
+        }
+    };
+    let spec = DateTimeSpec {
+        lo,
+        hi,
+        weekdays: flag(m, "weekdays")?,
+        hours,
+    };
+    // Any run of 9 days holds a full weekday, so checking that many suffices.
+    let has_window = (0..9)
+        .map(|d| lo.date() + Duration::days(d))
+        .take_while(|day| *day <= hi.date())
+        .any(|day| spec.window(day).is_some());
+    if !has_window {
+        return Err(format!(
+            "no time in {lo}..{hi} satisfies the weekdays/hours constraints"
+        ));
+    }
+    Ok(spec)
+}
+
+fn range<T: PartialOrd + std::fmt::Display>(
+    m: &Modifier,
+    bound: impl Fn(&Value) -> Result<T, String>,
+    example: &str,
+) -> Result<(T, T), String> {
+    m.check_positional(1)?;
+    let (lo, hi) = m
+        .positional()
+        .first()
+        .and_then(|v| v.as_range())
+        .ok_or_else(|| format!("expected a range like ({example}), got '({})'", m.raw))?;
+    let (lo, hi) = (bound(lo)?, bound(hi)?);
+    if lo > hi {
+        return Err(format!(
+            "empty range {lo}..{hi}; lower bound must not be after upper"
+        ));
+    }
+    Ok((lo, hi))
+}
+
+fn flag(m: &Modifier, key: &str) -> Result<bool, String> {
+    match m.option(key) {
+        None => Ok(false),
+        Some(Value::Ident(s)) if s == "true" => Ok(true),
+        Some(Value::Ident(s)) if s == "false" => Ok(false),
+        Some(v) => Err(format!("{key} expects true or false, got '{v}'")),
+    }
+}
+
+fn date_bound(v: &Value, now: NaiveDateTime) -> Result<NaiveDate, String> {
+    let text = bound_text(v)?;
+    if let Some(t) = relative(text, now, false)? {
+        return Ok(t.date());
+    }
+    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .map_err(|_| format!("invalid date '{text}'; expected YYYY-MM-DD, today or today-30d"))
+}
+
+fn date_time_bound(v: &Value, now: NaiveDateTime) -> Result<NaiveDateTime, String> {
+    let text = bound_text(v)?;
+    if let Some(t) = relative(text, now, true)? {
+        return Ok(t);
+    }
+    ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M"]
+        .iter()
+        .find_map(|f| NaiveDateTime::parse_from_str(text, f).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                .ok()
+                .map(|d| d.and_time(NaiveTime::MIN))
+        })
+        .ok_or_else(|| {
+            format!(
+                "invalid timestamp '{text}'; expected YYYY-MM-DD, YYYY-MM-DDTHH:MM[:SS], now or now-90d"
+            )
+        })
+}
+
+fn bound_text(v: &Value) -> Result<&str, String> {
+    match v {
+        Value::Ident(s) | Value::Str(s) => Ok(s),
+        other => Err(format!("invalid bound '{other}'")),
+    }
+}
+
+/// `now`, `today` or either with a `[+-]N<unit>` offset. `None` for any
+/// other text. Without `times`, only whole weeks and days are allowed.
+fn relative(text: &str, now: NaiveDateTime, times: bool) -> Result<Option<NaiveDateTime>, String> {
+    let (base, rest) = if let Some(rest) = text.strip_prefix("now") {
+        (now, rest)
+    } else if let Some(rest) = text.strip_prefix("today") {
+        (now.date().and_time(NaiveTime::MIN), rest)
+    } else {
+        return Ok(None);
+    };
+    if rest.is_empty() {
+        return Ok(Some(base));
+    }
+    let bad = || {
+        format!(
+            "invalid offset in '{text}'; expected e.g. {}",
+            if times {
+                "now-90d or now+6h"
+            } else {
+                "today-30d or today+2w"
+            }
+        )
+    };
+    let (sign, rest) = match rest.split_at(1) {
+        ("+", r) => (1, r),
+        ("-", r) => (-1, r),
+        _ => return Err(bad()),
+    };
+    let offset = span(rest, times).ok_or_else(bad)?;
+    base.checked_add_signed(offset * sign)
+        .map(Some)
+        .ok_or_else(bad)
+}
+
+/// `N<unit>` with units `w`, `d` and, with `times`, `h`, `m`, `s`.
+fn span(text: &str, times: bool) -> Option<Duration> {
+    let split = text.find(|c: char| !c.is_ascii_digit())?;
+    let n: i64 = text[..split].parse().ok()?;
+    match &text[split..] {
+        "w" => Duration::try_weeks(n),
+        "d" => Duration::try_days(n),
+        "h" if times => Duration::try_hours(n),
+        "m" if times => Duration::try_minutes(n),
+        "s" if times => Duration::try_seconds(n),
+        _ => None,
+    }
+}
+
+/// A timestamp written like a `DATE_TIME` bound: `2024-06-01`,
+/// `2024-06-01T08:30[:00]`, `now` or `now-90d`.
+pub(crate) fn timestamp(text: &str, now: NaiveDateTime) -> Result<NaiveDateTime, String> {
+    date_time_bound(&Value::Str(text.to_string()), now)
+}
+
+/// A positive duration such as `30s`, `5m`, `1h`, `1d` or `2w`.
+pub(crate) fn duration(text: &str) -> Result<Duration, String> {
+    span(text, true)
+        .filter(|d| *d > Duration::zero())
+        .ok_or_else(|| format!("invalid duration '{text}'; expected e.g. 30s, 5m, 1h, 1d or 2w"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::modifier::parse_modifier;
+    use chrono::Timelike;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 6, 12)
+            .unwrap()
+            .and_hms_opt(15, 30, 0)
+            .unwrap()
+    }
+
+    fn modifier(text: &str) -> Modifier {
+        parse_modifier(text).unwrap()
+    }
+
+    #[test]
+    fn dates_stay_in_range_and_skip_weekends() {
+        let spec = date_spec(&modifier("(2024-06-01..2024-06-30, weekdays=true)"), now()).unwrap();
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        for _ in 0..200 {
+            let d = spec.sample(&mut rng);
+            assert!(d.month() == 6 && d.year() == 2024, "{d}");
+            assert!(is_weekday(d), "{d}");
+        }
+    }
+
+    #[test]
+    fn relative_bounds_resolve_against_now() {
+        let spec = date_spec(&modifier("(today-7d..today)"), now()).unwrap();
+        assert_eq!(spec.lo.to_string(), "2024-06-05");
+        assert_eq!(spec.hi.to_string(), "2024-06-12");
+
+        let spec = date_time_spec(&modifier("(now-90d..now+6h)"), now()).unwrap();
+        assert_eq!(spec.lo.to_string(), "2024-03-14 15:30:00");
+        assert_eq!(spec.hi.to_string(), "2024-06-12 21:30:00");
+    }
+
+    #[test]
+    fn timestamps_honour_business_hours() {
+        let spec = date_time_spec(
+            &modifier("(2024-06-01T12:00..now, weekdays=true, hours=9..17)"),
+            now(),
+        )
+        .unwrap();
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+        for _ in 0..500 {
+            let t = spec.sample(&mut rng);
+            assert!(t >= spec.lo && t <= now(), "{t}");
+            assert!((9..17).contains(&t.hour()), "{t}");
+            assert!(is_weekday(t.date()), "{t}");
+        }
+    }
+
+    #[test]
+    fn durations_and_timestamps_parse_like_bounds() {
+        assert_eq!(duration("90s").unwrap(), Duration::seconds(90));
+        assert_eq!(duration("5m").unwrap(), Duration::minutes(5));
+        assert_eq!(duration("2w").unwrap(), Duration::days(14));
+        for bad in ["", "5", "m", "0s", "-5m", "5min"] {
+            assert!(duration(bad).is_err(), "{bad} should fail");
+        }
+        assert_eq!(
+            timestamp("now-1h", now()).unwrap().to_string(),
+            "2024-06-12 14:30:00"
+        );
+        assert_eq!(
+            timestamp("2024-01-02", now()).unwrap().to_string(),
+            "2024-01-02 00:00:00"
+        );
+        assert!(timestamp("yesterday", now()).is_err());
+    }
+
+    #[test]
+    fn defaults_span_a_fixed_window() {
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        let (dates, times) = (default_date_spec(), default_date_time_spec());
+        for _ in 0..200 {
+            let d = dates.sample(&mut rng);
+            assert!((2020..=2024).contains(&d.year()), "{d}");
+            let t = times.sample(&mut rng);
+            assert!((2020..=2024).contains(&t.year()), "{t}");
+        }
+        assert!(is_relative(&modifier("(2024-01-01..today)")));
+        assert!(is_relative(&modifier("(now-90d..2025-01-01, hours=9..17)")));
+        assert!(!is_relative(&modifier("(2024-01-01..2024-02-01)")));
+    }
+
+    #[test]
+    fn rejects_bad_ranges() {
+        for bad in [
+            "(2024-01-01)",
+            "(2024-12-31..2024-01-01)",
+            "(2024-02-30..2024-03-01)",
+            "(today-3h..today)",
+            "(now-90x..now)",
+            "(2024-06-08..2024-06-09, weekdays=true)",
+            "(2024-06-01..2024-06-30, weekdays=yes)",
+            "(2024-06-01..2024-06-30, business=true)",
+        ] {
+            assert!(
+                date_spec(&modifier(bad), now()).is_err(),
+                "{bad} should fail"
+            );
+        }
+        for bad in [
+            "(now-1d..now, hours=17..9)",
+            "(now-1d..now, hours=0..25)",
+            "(2024-06-12T18:00..2024-06-12T20:00, hours=9..17)",
+        ] {
+            assert!(
+                date_time_spec(&modifier(bad), now()).is_err(),
+                "{bad} should fail"
+            );
+        }
+    }
+}
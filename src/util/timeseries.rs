@@ -0,0 +1,434 @@
+//! Time-series tables for the `synthtab ts` subcommand.
+//!
+//! ```text
+//! synthtab ts -s "temp:DECIMAL(walk(20, 0.5), scale=1)" \
+//!     --start 2024-06-01 --end 2024-06-02 --interval 5m \
+//!     --key device_id --entities 50 --jitter 20s --gap-ratio 0.01
+//! ```
+//!
+//! Every entity gets one reading per tick of `--interval`, from `--start` up
+//! to, not including, `--end`. An entity's value columns are one
+//! `create_dataframe` run of one row per tick, so `walk` and `seasonal`
+//! columns evolve per entity. Jitter, gaps and late arrivals are applied on
+//! top, and rows come out in arrival order.
+
+use crate::util::dataframe::create_dataframe;
+use crate::util::fake::{create_column, date_time_series};
+use crate::util::rng::{CellRng, RunRng};
+use crate::util::schema::Schema;
+use crate::util::temporal::{duration, timestamp};
+use chrono::{Duration, NaiveDateTime};
+use polars::prelude::*;
+use rand::Rng;
+use std::error::Error;
+use uuid::Uuid;
+
+type SeriesResult = Result<(DataFrame, Vec<Schema>), Box<dyn Error>>;
+
+/// Most readings one series may hold, so a typo such as `--interval 1s`
+/// over a year fails up front instead of exhausting memory.
+const MAX_READINGS: usize = 100_000_000;
+
+/// The `synthtab ts` flags that shape the table, as given on the command line.
+#[derive(Clone, Debug)]
+pub struct SeriesOptions {
+    /// First tick, written like a `DATE_TIME` bound (`2024-06-01`, `now-1d`).
+    pub start: String,
+    /// Ticks stop before this timestamp.
+    pub end: String,
+    /// Time between ticks, e.g. `5m`.
+    pub interval: String,
+    /// `name` or `name:TYPE`; a bare name numbers entities from 1.
+    pub key: String,
+    pub entities: usize,
+    pub time_column: String,
+    /// Each timestamp moves forward by less than this.
+    pub jitter: Option<String>,
+    /// Share of readings that are never delivered.
+    pub gap_ratio: f64,
+    /// Share of readings delivered up to `max_delay` late.
+    pub late_ratio: f64,
+    pub max_delay: Option<String>,
+    /// Column that records when each reading arrived.
+    pub arrival_column: Option<String>,
+}
+
+/// The parsed timing of a series.
+struct Ticks {
+    start: NaiveDateTime,
+    interval: Duration,
+    count: usize,
+    jitter: Option<Duration>,
+    max_delay: Option<Duration>,
+}
+
+impl Ticks {
+    fn parse(options: &SeriesOptions, now: NaiveDateTime) -> Result<Ticks, String> {
+        let start = timestamp(&options.start, now).map_err(|e| format!("--start: {e}"))?;
+        let end = timestamp(&options.end, now).map_err(|e| format!("--end: {e}"))?;
+        if start >= end {
+            return Err(format!(
+                "--start {start} must be before --end {end}; the series would be empty"
+            ));
+        }
+        let interval = duration(&options.interval).map_err(|e| format!("--interval: {e}"))?;
+        let span = (end - start).num_seconds();
+        let step = interval.num_seconds();
+        let jitter = match &options.jitter {
+            Some(j) => {
+                let jitter = duration(j).map_err(|e| format!("--jitter: {e}"))?;
+                if jitter >= interval {
+                    return Err(format!(
+                        "--jitter {j} must be shorter than --interval {} so each entity's timestamps keep increasing",
+                        options.interval
+                    ));
+                }
+                Some(jitter)
+            }
+            None => None,
+        };
+        let max_delay = match &options.max_delay {
+            Some(d) => Some(duration(d).map_err(|e| format!("--max-delay: {e}"))?),
+            None if options.late_ratio > 0.0 => {
+                return Err(
+                    "--late-ratio needs --max-delay to know how late readings arrive".into(),
+                )
+            }
+            None => None,
+        };
+        Ok(Ticks {
+            start,
+            interval,
+            count: ((span + step - 1) / step) as usize,
+            jitter,
+            max_delay,
+        })
+    }
+}
+
+/// How many readings each entity's series holds, for checking its columns
+/// before generating.
+pub(crate) fn series_length(options: &SeriesOptions, now: NaiveDateTime) -> Result<usize, String> {
+    Ticks::parse(options, now).map(|ticks| ticks.count)
+}
+
+/// The key column: `name:TYPE`, or `name` numbered from 1.
+pub(crate) fn key_column(key: &str) -> Result<Schema, Box<dyn Error>> {
+    let column = if key.contains(':') {
+        Schema::from_string(key)?
+            .ok_or_else(|| format!("--key '{key}' is not 'name' or 'name:TYPE'"))?
+    } else {
+        Schema::new(key.trim(), "INT_INC", Some("(start=1)"))?
+    };
+    if column.is_nullable() {
+        return Err(format!("--key column '{}' cannot be nullable", column.name).into());
+    }
+    Ok(column)
+}
+
+/// Generate the series, returning it with the column list its DDL is
+/// emitted from. `table` keys per-cell RNGs in `--stable-rows` mode.
+pub fn create_time_series(
+    values: Vec<Schema>,
+    options: &SeriesOptions,
+    rng: &mut RunRng,
+    table: &str,
+) -> SeriesResult {
+    for (flag, ratio) in [
+        ("--gap-ratio", options.gap_ratio),
+        ("--late-ratio", options.late_ratio),
+    ] {
+        if !(0.0..=1.0).contains(&ratio) {
+            return Err(format!("{flag} must be between 0 and 1, got {ratio}").into());
+        }
+    }
+    if options.entities == 0 {
+        return Err("--entities must be at least 1".into());
+    }
+    let now = rng.now();
+    let ticks = Ticks::parse(options, now)?;
+    let readings = ticks.count.checked_mul(options.entities);
+    if !matches!(readings, Some(n) if n <= MAX_READINGS) {
+        return Err(format!(
+            "{} ticks of --interval {} for {} entities is more than {MAX_READINGS} readings; \
+             use a longer --interval, a shorter range or fewer --entities",
+            ticks.count, options.interval, options.entities
+        )
+        .into());
+    }
+    let key = key_column(&options.key)?;
+
+    let time_column = Schema::new(&options.time_column, "DATE_TIME", None)?;
+    let arrival_column = match &options.arrival_column {
+        Some(name) => Some(Schema::new(name, "DATE_TIME", None)?),
+        None => None,
+    };
+    let columns: Vec<Schema> = [Some(key.clone()), Some(time_column), arrival_column]
+        .into_iter()
+        .flatten()
+        .chain(values.iter().cloned())
+        .collect();
+    for (i, column) in columns.iter().enumerate() {
+        if columns[..i].iter().any(|c| c.name == column.name) {
+            return Err(format!("column name '{}' is used twice", column.name).into());
+        }
+    }
+    let arrival = options
+        .arrival_column
+        .clone()
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    let keys = create_column(
+        key.clone(),
+        options.entities,
+        &mut rng.column(table, &key.name),
+        now,
+    )?;
+    let mut frame: Option<DataFrame> = None;
+    for entity in 0..options.entities {
+        let entity_table = format!("{table}#{entity}");
+        let readings =
+            create_dataframe(values.clone(), ticks.count, None, None, rng, &entity_table)?;
+
+        let times: Vec<NaiveDateTime> = {
+            let mut jitter = rng.column(&entity_table, &options.time_column);
+            (0..ticks.count)
+                .map(|i| {
+                    let tick = ticks.start + ticks.interval * i as i32;
+                    match ticks.jitter {
+                        Some(j) => {
+                            tick + Duration::seconds(
+                                jitter.cell(i, |r| r.gen_range(0..j.num_seconds())),
+                            )
+                        }
+                        None => tick,
+                    }
+                })
+                .collect()
+        };
+        let arrivals: Vec<NaiveDateTime> = {
+            let mut late = rng.column(&entity_table, &format!("{}\0late", options.time_column));
+            times
+                .iter()
+                .enumerate()
+                .map(|(i, t)| match ticks.max_delay {
+                    Some(d) => late.cell(i, |r| {
+                        if r.gen_bool(options.late_ratio) {
+                            *t + Duration::seconds(r.gen_range(1..=d.num_seconds()))
+                        } else {
+                            *t
+                        }
+                    }),
+                    None => *t,
+                })
+                .collect()
+        };
+        let keep: Vec<bool> = {
+            let mut gaps = rng.column(&entity_table, &format!("{}\0gaps", options.time_column));
+            (0..ticks.count)
+                .map(|i| gaps.cell(i, |r| !r.gen_bool(options.gap_ratio)))
+                .collect()
+        };
+
+        let mut cols = vec![
+            keys.new_from_index(entity, ticks.count),
+            date_time_series(&options.time_column, times),
+            date_time_series(&arrival, arrivals),
+        ];
+        cols.extend(readings.get_columns().iter().cloned());
+        let readings = DataFrame::new(cols)
+            .and_then(|df| df.filter(&BooleanChunked::from_slice("keep", &keep)))
+            .map_err(|e| format!("failed to assemble readings for entity {entity}: {e}"))?;
+        match frame.as_mut() {
+            Some(f) => {
+                f.vstack_mut(&readings)
+                    .map_err(|e| format!("failed to stack readings: {e}"))?;
+            }
+            None => frame = Some(readings),
+        }
+    }
+
+    let mut frame = frame
+        .expect("at least one entity")
+        .sort([arrival.as_str()], false, true)
+        .map_err(|e| format!("failed to order readings by arrival: {e}"))?;
+    if options.arrival_column.is_none() {
+        frame = frame.drop(&arrival)?;
+    }
+    Ok((frame, columns))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn options() -> SeriesOptions {
+        SeriesOptions {
+            start: "2024-06-01".into(),
+            end: "2024-06-01T01:00".into(),
+            interval: "10m".into(),
+            key: "device_id".into(),
+            entities: 3,
+            time_column: "ts".into(),
+            jitter: None,
+            gap_ratio: 0.0,
+            late_ratio: 0.0,
+            max_delay: None,
+            arrival_column: None,
+        }
+    }
+
+    fn values() -> Vec<Schema> {
+        vec![Schema::new("temp", "DECIMAL", Some("(walk(20, 0.5), scale=1)")).unwrap()]
+    }
+
+    fn series(options: &SeriesOptions) -> DataFrame {
+        let mut rng = RunRng::new(Some(4), false);
+        create_time_series(values(), options, &mut rng, "readings")
+            .unwrap()
+            .0
+    }
+
+    fn seconds(df: &DataFrame, name: &str) -> Vec<i64> {
+        df.column(name)
+            .unwrap()
+            .datetime()
+            .unwrap()
+            .into_no_null_iter()
+            .map(|us| us / 1_000_000)
+            .collect()
+    }
+
+    #[test]
+    fn one_reading_per_entity_per_tick_in_time_order() {
+        let df = series(&options());
+        assert_eq!(df.get_column_names(), ["device_id", "ts", "temp"]);
+        assert_eq!(df.height(), 18);
+        let ids: Vec<i32> = df
+            .column("device_id")
+            .unwrap()
+            .i32()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        assert_eq!(&ids[..6], [1, 2, 3, 1, 2, 3]);
+        let ts = seconds(&df, "ts");
+        assert!(ts.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(ts[3] - ts[0], 600);
+    }
+
+    #[test]
+    fn jitter_keeps_each_entity_increasing() {
+        let df = series(&SeriesOptions {
+            jitter: Some("9m".into()),
+            entities: 1,
+            end: "2024-06-02".into(),
+            ..options()
+        });
+        let ts = seconds(&df, "ts");
+        assert!(ts.windows(2).all(|w| w[0] < w[1]));
+        assert!(ts.iter().any(|t| t % 600 != 0), "no jitter applied");
+    }
+
+    #[test]
+    fn gaps_drop_readings_and_late_ones_arrive_out_of_order() {
+        let df = series(&SeriesOptions {
+            end: "2024-06-02".into(),
+            gap_ratio: 0.2,
+            late_ratio: 0.2,
+            max_delay: Some("1h".into()),
+            arrival_column: Some("arrived_at".into()),
+            ..options()
+        });
+        assert!((300..400).contains(&df.height()), "{}", df.height());
+        let arrived = seconds(&df, "arrived_at");
+        assert!(arrived.windows(2).all(|w| w[0] <= w[1]));
+        let ts = seconds(&df, "ts");
+        assert!(ts.windows(2).any(|w| w[0] > w[1]), "nothing out of order");
+        assert!(ts
+            .iter()
+            .zip(&arrived)
+            .all(|(t, a)| t <= a && a - t <= 3600));
+    }
+
+    #[test]
+    fn typed_keys_repeat_for_every_reading() {
+        let df = series(&SeriesOptions {
+            key: "device:INT_INC(prefix='dev-', width=2)".into(),
+            ..options()
+        });
+        let keys: Vec<&str> = df
+            .column("device")
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        assert_eq!(&keys[..3], ["dev-00", "dev-01", "dev-02"]);
+    }
+
+    #[test]
+    fn relative_bounds_resolve_against_the_runs_now() {
+        let now =
+            NaiveDateTime::parse_from_str("2024-06-01T12:00:00", "%Y-%m-%dT%H:%M:%S").unwrap();
+        let mut rng = RunRng::new(Some(4), false).at(now);
+        let options = SeriesOptions {
+            start: "now-1h".into(),
+            end: "now".into(),
+            ..options()
+        };
+        let (df, _) = create_time_series(values(), &options, &mut rng, "readings").unwrap();
+        let first = now.and_utc().timestamp() - 3600;
+        assert_eq!(seconds(&df, "ts")[0], first);
+        assert_eq!(df.height(), 18);
+    }
+
+    #[test]
+    fn rejects_bad_options() {
+        for bad in [
+            SeriesOptions {
+                end: "2024-05-31".into(),
+                ..options()
+            },
+            SeriesOptions {
+                interval: "5".into(),
+                ..options()
+            },
+            SeriesOptions {
+                jitter: Some("10m".into()),
+                ..options()
+            },
+            SeriesOptions {
+                late_ratio: 0.1,
+                ..options()
+            },
+            SeriesOptions {
+                gap_ratio: 1.5,
+                ..options()
+            },
+            SeriesOptions {
+                time_column: "temp".into(),
+                ..options()
+            },
+            SeriesOptions {
+                key: "device_id:UUID?".into(),
+                ..options()
+            },
+            SeriesOptions {
+                entities: 0,
+                ..options()
+            },
+            SeriesOptions {
+                end: "2030-01-01".into(),
+                interval: "1s".into(),
+                ..options()
+            },
+        ] {
+            let mut rng = RunRng::new(Some(1), false);
+            assert!(
+                create_time_series(values(), &bad, &mut rng, "t").is_err(),
+                "{bad:?} should fail"
+            );
+        }
+    }
+}
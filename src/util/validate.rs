@@ -0,0 +1,276 @@
+//! Up-front checks of a column list, run before any rows are generated.
+//!
+//! Strict runs (the default) reject a column whose type synthtab does not
+//! know, suggesting the closest known one, and dry-run every other column so
+//! a bad modifier fails before the first row. `--lenient` runs only warn
+//! about unknown types and fill them with `"unknown"`.
+
+use crate::util::derived::{check_derivations, derivation_of};
+use crate::util::fake::{create_column, sized_sequence, TYPES};
+use crate::util::rng::seeded_rng;
+use crate::util::schema::Schema;
+use chrono::NaiveDateTime;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownTypeError {
+    pub message: String,
+}
+
+impl fmt::Display for UnknownTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for UnknownTypeError {}
+
+/// Check every column of `columns`, nested element types included, for a
+/// run of `rows` rows, with `now` and `today` bounds resolved against the
+/// run's `now`.
+///
+/// Strict: the first unknown type is an `UnknownTypeError` and the first bad
+/// modifier a `ModifierError`. Lenient: unknown types are reported on stderr
+/// and nothing fails here.
+pub fn check_columns(
+    columns: &[Schema],
+    rows: usize,
+    strict: bool,
+    now: NaiveDateTime,
+) -> Result<(), Box<dyn Error>> {
+    check_located(columns, rows, strict, now, |_| None)
+}
+
+/// `check_columns`, with each error and warning prefixed by `origin` of the
+/// index of the column it is about, such as the `path:line` it was read from.
+pub(crate) fn check_located(
+    columns: &[Schema],
+    rows: usize,
+    strict: bool,
+    now: NaiveDateTime,
+    origin: impl Fn(usize) -> Option<String>,
+) -> Result<(), Box<dyn Error>> {
+    let at = |index: usize, message: String| match origin(index) {
+        Some(at) => format!("{at}: {message}"),
+        None => message,
+    };
+    for (index, column) in columns.iter().enumerate() {
+        if let Some(unknown) = unknown_type(column, column) {
+            let message = at(index, unknown.message);
+            if strict {
+                return Err(UnknownTypeError { message }.into());
+            }
+            eprintln!("{message}; filling it with \"unknown\"");
+            continue;
+        }
+        if strict {
+            dry_run(column, columns, rows, now).map_err(|e| match origin(index) {
+                Some(at) => format!("{at}: {e}").into(),
+                None => e,
+            })?;
+        }
+    }
+    if strict {
+        check_derivations(columns)?;
+    }
+    Ok(())
+}
+
+/// The first type in `element` or its children that synthtab does not know,
+/// reported against the top-level `column`.
+fn unknown_type(column: &Schema, element: &Schema) -> Option<UnknownTypeError> {
+    if !TYPES.contains(&element.datatype.as_str()) {
+        let suggestion = closest_type(&element.datatype)
+            .map(|t| format!("; did you mean '{t}'?"))
+            .unwrap_or_else(|| ".".to_string());
+        return Some(UnknownTypeError {
+            message: format!(
+                "column '{}': unknown type '{}'{suggestion} Valid types: {}",
+                column.name,
+                element.datatype,
+                TYPES.join(", ")
+            ),
+        });
+    }
+    element
+        .children
+        .iter()
+        .find_map(|child| unknown_type(column, child))
+}
+
+/// Parse the column's modifier the way generation will, without generating.
+/// A derived column's references must name one of `columns`, and a sequence
+/// must number `rows` rows without overflowing.
+fn dry_run(
+    column: &Schema,
+    columns: &[Schema],
+    rows: usize,
+    now: NaiveDateTime,
+) -> Result<(), Box<dyn Error>> {
+    if let Some(derivation) = derivation_of(column)? {
+        let known = |name: &String| columns.iter().any(|c| &c.name == name);
+        return match derivation.references().into_iter().find(|r| !known(r)) {
+            Some(unknown) => Err(format!(
+                "column '{}': {} references unknown column '{unknown}'",
+                column.name,
+                derivation.kind()
+            )
+            .into()),
+            None => Ok(()),
+        };
+    }
+    // Nothing to check, and the dry run would repeat its fallback warning.
+    if column.datatype == "INT_RNG" && column.modifier.is_none() {
+        return Ok(());
+    }
+    if matches!(column.datatype.as_str(), "INT_INC" | "BIGINT_INC") {
+        return sized_sequence(column, rows).map(drop).map_err(Into::into);
+    }
+    create_column(column.clone(), 0, &mut seeded_rng(Some(0)), now)
+        .map(drop)
+        .map_err(Into::into)
+}
+
+/// The known type nearest to `datatype`, ignoring case, when it is close
+/// enough to be a typo: one edit for short names, a third of the length for
+/// longer ones.
+fn closest_type(datatype: &str) -> Option<&'static str> {
+    let wanted = datatype.to_ascii_uppercase();
+    let limit = (wanted.chars().count() / 3).max(1);
+    TYPES
+        .iter()
+        .map(|t| (edit_distance(&wanted, t), *t))
+        .filter(|(d, _)| *d <= limit)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, t)| t)
+}
+
+/// Edit distance counting an insertion, deletion, substitution or swap of
+/// two neighbouring characters as one edit each.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    d[0] = (0..=b.len()).collect();
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::schema::parse_schema;
+
+    fn check(schema: &str) -> Result<(), Box<dyn Error>> {
+        check_columns(
+            &parse_schema(schema).unwrap(),
+            10,
+            true,
+            NaiveDateTime::default(),
+        )
+    }
+
+    #[test]
+    fn unknown_types_suggest_the_closest_match() {
+        let err = check("id:INT_INC,who:NMAE").unwrap_err();
+        assert!(err.is::<UnknownTypeError>());
+        let message = err.to_string();
+        assert!(
+            message.starts_with("column 'who': unknown type 'NMAE'; did you mean 'NAME'?"),
+            "{message}"
+        );
+        assert!(message.contains("Valid types: STRING, INT,"), "{message}");
+
+        let err = check("mail:email").unwrap_err().to_string();
+        assert!(err.contains("did you mean 'EMAIL'?"), "{err}");
+        let err = check("ts:TIMESTAMP").unwrap_err().to_string();
+        assert!(!err.contains("did you mean"), "{err}");
+    }
+
+    #[test]
+    fn nested_element_types_are_checked() {
+        let err = check("tags:LIST<LOREM_WROD>").unwrap_err().to_string();
+        assert!(
+            err.starts_with("column 'tags': unknown type 'LOREM_WROD'; did you mean 'LOREM_WORD'?"),
+            "{err}"
+        );
+        let err = check("addr:STRUCT<zip:ZIPCODE, city:STRING>")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("did you mean 'ZIP_CODE'?"), "{err}");
+    }
+
+    #[test]
+    fn modifiers_are_checked_before_generation() {
+        let err = check("id:INT_INC,age:INT(10..1)").unwrap_err().to_string();
+        assert!(err.starts_with("column 'age': INT modifier:"), "{err}");
+        let err = check("s:ENUM").unwrap_err().to_string();
+        assert!(err.starts_with("column 's': ENUM modifier:"), "{err}");
+        assert!(check("full:TEMPLATE(\"{first} {last}\"),first:FIRST_NAME,last:LAST_NAME").is_ok());
+    }
+
+    #[test]
+    fn derived_columns_are_checked_before_generation() {
+        check("first:FIRST_NAME,email:TEMPLATE(\"{first}@example.com\"),n:EXPR(upper(email))")
+            .unwrap();
+        let err = check("id:INT_INC,email:TEMPLATE(\"{frist}@example.com\")")
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            err,
+            "column 'email': TEMPLATE references unknown column 'frist'"
+        );
+        let err = check("a:EXPR(b + 1),b:EXPR(a * 2)")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("cycle: a -> b -> a"), "{err}");
+    }
+
+    #[test]
+    fn sequences_are_checked_against_the_row_count() {
+        let columns = parse_schema("id:INT_INC(start=2147483640)").unwrap();
+        let now = NaiveDateTime::default();
+        check_columns(&columns, 8, true, now).unwrap();
+        let err = check_columns(&columns, 9, true, now)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            err,
+            "column 'id': INT_INC cannot number 9 rows from start=2147483640 step=1; use BIGINT_INC"
+        );
+        let columns = parse_schema("n:BIGINT_INC(start=0, step=-4611686018427387904)").unwrap();
+        assert!(check_columns(&columns, 3, true, now).is_ok());
+        assert!(check_columns(&columns, 4, true, now).is_err());
+    }
+
+    #[test]
+    fn every_known_type_passes_without_a_modifier() {
+        let needs_modifier = ["ENUM", "PATTERN", "TEMPLATE", "EXPR", "LIST", "STRUCT"];
+        let columns: Vec<Schema> = TYPES
+            .iter()
+            .filter(|t| !needs_modifier.contains(t))
+            .map(|t| Schema::new("c", t, None).unwrap())
+            .collect();
+        check_columns(&columns, 10, true, NaiveDateTime::default()).unwrap();
+    }
+
+    #[test]
+    fn lenient_runs_let_unknown_types_through() {
+        let columns = parse_schema("who:NMAE").unwrap();
+        assert!(check_columns(&columns, 10, false, NaiveDateTime::default()).is_ok());
+    }
+}
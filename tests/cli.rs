@@ -128,6 +128,8 @@ fn test_er_generates_junction_for_many_to_many() -> TestResult {
             "tests/fixtures/er/student_course_mn.mmd",
             "--out",
             out_dir.to_str().unwrap(),
+            "--target",
+            "postgres",
         ])
         .assert()
         .success()
@@ -140,6 +142,8 @@ fn test_er_generates_junction_for_many_to_many() -> TestResult {
         out_dir.join("STUDENT_COURSE.csv").exists(),
         "STUDENT_COURSE.csv missing"
     );
+    let ddl = fs::read_to_string(out_dir.join("schema.ddl.postgres.sql"))?;
+    assert!(ddl.contains("CREATE TABLE STUDENT_COURSE ("), "got: {ddl}");
     let _ = fs::remove_dir_all(&out_dir);
     Ok(())
 }
@@ -336,3 +340,1221 @@ fn test_no_ddl_suppresses_ddl_file() -> TestResult {
     let _ = fs::remove_file(&data);
     Ok(())
 }
+
+#[test]
+fn test_seed_produces_identical_flat_output() -> TestResult {
+    let args = [
+        "-s",
+        "id:UUID,name:NAME,joined:DATE,price:PRICE",
+        "-r",
+        "20",
+        "--seed",
+        "42",
+        "-d",
+        "random",
+    ];
+    let first = Command::cargo_bin(NAME)?.args(args).output()?;
+    let second = Command::cargo_bin(NAME)?.args(args).output()?;
+    assert!(first.status.success());
+    assert_eq!(first.stdout, second.stdout);
+    Ok(())
+}
+
+#[test]
+fn test_er_seed_produces_identical_files() -> TestResult {
+    let mut outputs: Vec<(String, String)> = Vec::new();
+    for run in ["a", "b"] {
+        let out_dir = std::env::temp_dir().join(format!("synthtab_cli_er_seed_{run}"));
+        let _ = fs::remove_dir_all(&out_dir);
+        Command::cargo_bin(NAME)?
+            .args([
+                "er",
+                "tests/fixtures/er/car_person.mmd",
+                "--out",
+                out_dir.to_str().unwrap(),
+                "--seed",
+                "7",
+            ])
+            .assert()
+            .success();
+        outputs.push((
+            fs::read_to_string(out_dir.join("PERSON.csv"))?,
+            fs::read_to_string(out_dir.join("CAR.csv"))?,
+        ));
+        let _ = fs::remove_dir_all(&out_dir);
+    }
+    assert_eq!(outputs[0], outputs[1]);
+    Ok(())
+}
+
+#[test]
+fn test_stable_rows_keep_earlier_rows_when_row_count_grows() -> TestResult {
+    let base = [
+        "-s",
+        "id:UUID,name:NAME,note:LOREM_WORD",
+        "--seed",
+        "42",
+        "--stable-rows",
+    ];
+    let small = Command::cargo_bin(NAME)?
+        .args(base)
+        .args(["-r", "5"])
+        .output()?;
+    let large = Command::cargo_bin(NAME)?
+        .args(base)
+        .args(["-r", "50"])
+        .output()?;
+    let small = String::from_utf8(small.stdout)?;
+    let large = String::from_utf8(large.stdout)?;
+    assert!(
+        large.starts_with(&small),
+        "small:\n{small}\nlarge:\n{large}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_stable_rows_requires_seed() -> TestResult {
+    Command::cargo_bin(NAME)?
+        .args(["-s", "id:UUID", "--stable-rows"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--seed"));
+    Ok(())
+}
+
+#[test]
+fn test_schema_file_formats_generate_same_columns() -> TestResult {
+    for file in [
+        "tests/fixtures/schema/users.toml",
+        "tests/fixtures/schema/users.yaml",
+        "tests/fixtures/schema/users.json",
+    ] {
+        Command::cargo_bin(NAME)?
+            .args(["--schema-file", file, "-r", "3"])
+            .assert()
+            .success()
+            .stdout(predicate::str::starts_with("id,name,score\n"))
+            .stdout(predicate::str::contains("\n0,"))
+            .stdout(predicate::str::contains(",10\n"));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_schema_file_error_names_file_and_line() -> TestResult {
+    Command::cargo_bin(NAME)?
+        .args(["--schema-file", "tests/fixtures/schema/invalid.toml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid.toml:7:"))
+        .stderr(predicate::str::contains("kind"));
+    Command::cargo_bin(NAME)?
+        .args(["--schema-file", "tests/fixtures/schema/unknown_type.yaml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "unknown_type.yaml:4: column 'who': unknown type 'NMAE'",
+        ));
+    Ok(())
+}
+
+#[test]
+fn test_schema_and_schema_file_conflict() -> TestResult {
+    Command::cargo_bin(NAME)?
+        .args([
+            "-s",
+            "id:INT_INC",
+            "--schema-file",
+            "tests/fixtures/schema/users.toml",
+        ])
+        .assert()
+        .failure();
+    Ok(())
+}
+
+#[test]
+fn test_decimal_modifier_with_range_and_scale() -> TestResult {
+    let output = Command::cargo_bin(NAME)?
+        .args([
+            "-s",
+            "price:DECIMAL(0..500, scale=2)",
+            "-r",
+            "20",
+            "--seed",
+            "3",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    for line in stdout.lines().skip(1) {
+        let v: f64 = line.parse()?;
+        assert!((0.0..=500.0).contains(&v), "{v} out of range");
+        assert!(line.split('.').nth(1).map_or(0, str::len) <= 2, "{line}");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_enum_column_draws_listed_values_and_maps_to_mysql_enum() -> TestResult {
+    let data = std::env::temp_dir().join("synthtab_enum_test_accounts.csv");
+    let ddl = std::env::temp_dir().join("synthtab_enum_test_accounts.ddl.mysql.sql");
+    let _ = fs::remove_file(&data);
+    let _ = fs::remove_file(&ddl);
+    Command::cargo_bin(NAME)?
+        .args([
+            "-s",
+            "id:INT_INC,status:ENUM('active'=70, 'suspended'=20, 'closed'=10)",
+            "-r",
+            "50",
+            "--seed",
+            "4",
+            "-c",
+            "-f",
+            data.to_str().unwrap(),
+            "--target",
+            "mysql",
+        ])
+        .assert()
+        .success();
+    let csv = fs::read_to_string(&data)?;
+    for line in csv.lines().skip(1) {
+        let status = line.split(',').nth(1).unwrap_or_default();
+        assert!(
+            ["active", "suspended", "closed"].contains(&status),
+            "unexpected value in {line}"
+        );
+    }
+    let actual = fs::read_to_string(&ddl)?;
+    assert!(
+        actual.contains("status ENUM('active','suspended','closed') NOT NULL"),
+        "got: {actual}"
+    );
+    let _ = fs::remove_file(&data);
+    let _ = fs::remove_file(&ddl);
+    Ok(())
+}
+
+#[test]
+fn test_pattern_column_matches_regex() -> TestResult {
+    let output = Command::cargo_bin(NAME)?
+        .args([
+            "-s",
+            r"sku:PATTERN('[A-Z]{3}-\d{6}')",
+            "-r",
+            "20",
+            "--seed",
+            "5",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    for line in stdout.lines().skip(1) {
+        let (letters, digits) = line.split_once('-').unwrap_or_default();
+        assert_eq!(letters.len(), 3, "{line}");
+        assert!(letters.bytes().all(|b| b.is_ascii_uppercase()), "{line}");
+        assert_eq!(digits.len(), 6, "{line}");
+        assert!(digits.bytes().all(|b| b.is_ascii_digit()), "{line}");
+    }
+    Command::cargo_bin(NAME)?
+        .args(["-s", "sku:PATTERN('[A-Z')"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("column 'sku': PATTERN modifier"));
+    Ok(())
+}
+
+#[test]
+fn test_template_column_composes_other_columns() -> TestResult {
+    let output = Command::cargo_bin(NAME)?
+        .args([
+            "-s",
+            r#"email:TEMPLATE("{first|lower}.{last|lower}@example.com"),first:FIRST_NAME,last:LAST_NAME"#,
+            "-r",
+            "10",
+            "--seed",
+            "6",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    let mut lines = stdout.lines();
+    assert_eq!(lines.next(), Some("email,first,last"));
+    for line in lines {
+        let fields: Vec<&str> = line.split(',').collect();
+        let expected = format!(
+            "{}.{}@example.com",
+            fields[1].to_lowercase(),
+            fields[2].to_lowercase()
+        );
+        assert_eq!(fields[0], expected);
+    }
+    Command::cargo_bin(NAME)?
+        .args(["-s", r#"a:TEMPLATE("{b}"),b:TEMPLATE("{a}")"#])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "derived columns form a cycle: a -> b -> a",
+        ));
+    Ok(())
+}
+
+#[test]
+fn test_expr_column_evaluates_expression_and_types_ddl() -> TestResult {
+    let data = std::env::temp_dir().join("synthtab_expr_test_orders.csv");
+    let ddl = std::env::temp_dir().join("synthtab_expr_test_orders.ddl.postgres.sql");
+    let _ = fs::remove_file(&data);
+    let _ = fs::remove_file(&ddl);
+    Command::cargo_bin(NAME)?
+        .args([
+            "-s",
+            "qty:INT_RNG(1..10),age:INT_RNG(10..60),total:EXPR(qty * 3),is_adult:EXPR(age >= 18)",
+            "-r",
+            "30",
+            "--seed",
+            "9",
+            "-c",
+            "-f",
+            data.to_str().unwrap(),
+            "--target",
+            "postgres",
+        ])
+        .assert()
+        .success();
+    let csv = fs::read_to_string(&data)?;
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("qty,age,total,is_adult"));
+    for line in lines {
+        let fields: Vec<&str> = line.split(',').collect();
+        let qty: i64 = fields[0].parse()?;
+        let age: i64 = fields[1].parse()?;
+        assert_eq!(fields[2], (qty * 3).to_string(), "{line}");
+        assert_eq!(fields[3], (age >= 18).to_string(), "{line}");
+    }
+    let actual = fs::read_to_string(&ddl)?;
+    assert!(
+        actual.contains("is_adult BOOLEAN NOT NULL"),
+        "got: {actual}"
+    );
+    let _ = fs::remove_file(&data);
+    let _ = fs::remove_file(&ddl);
+
+    Command::cargo_bin(NAME)?
+        .args(["-s", "qty:INT,total:EXPR(qty *)"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("column 'total': EXPR modifier"));
+    Ok(())
+}
+
+#[test]
+fn test_distribution_modifiers_shape_numeric_columns() -> TestResult {
+    let output = Command::cargo_bin(NAME)?
+        .args([
+            "-s",
+            "score:INT(normal(50, 10), clamp=0..100),visits:INT(zipf(50, 1.5)),amount:DECIMAL(exponential(0.1), clamp=0..40, scale=2)",
+            "-r",
+            "200",
+            "--seed",
+            "10",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    let rows: Vec<Vec<f64>> = stdout
+        .lines()
+        .skip(1)
+        .map(|l| l.split(',').map(|f| f.parse().unwrap()).collect())
+        .collect();
+    assert_eq!(rows.len(), 200);
+    assert!(rows.iter().all(|r| (0.0..=100.0).contains(&r[0])));
+    assert!(rows.iter().all(|r| (1.0..=50.0).contains(&r[1])));
+    assert!(rows.iter().all(|r| (0.0..=40.0).contains(&r[2])));
+    let ones = rows.iter().filter(|r| r[1] == 1.0).count();
+    assert!(ones > 60, "zipf should favour 1, got {ones} ones");
+
+    Command::cargo_bin(NAME)?
+        .args(["-s", "score:INT(gauss(50, 10))"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown distribution 'gauss'"));
+    Ok(())
+}
+
+#[test]
+fn test_internet_types_from_er_hints() -> TestResult {
+    let out_dir = std::env::temp_dir().join("synthtab_cli_er_internet_test");
+    let _ = fs::remove_dir_all(&out_dir);
+    let mmd = out_dir.join("logs.mmd");
+    fs::create_dir_all(&out_dir)?;
+    fs::write(
+        &mmd,
+        "erDiagram\nVISIT {\n int id PK\n string email \"EMAIL\"\n string ip \"IPV4?\"\n string device \"MAC_ADDRESS\"\n}\n",
+    )?;
+    Command::cargo_bin(NAME)?
+        .args([
+            "er",
+            mmd.to_str().unwrap(),
+            "--out",
+            out_dir.to_str().unwrap(),
+            "--target",
+            "postgres",
+            "--seed",
+            "2",
+        ])
+        .assert()
+        .success();
+    let ddl = fs::read_to_string(out_dir.join("schema.ddl.postgres.sql"))?;
+    assert!(ddl.contains("email VARCHAR(254) NOT NULL"), "{ddl}");
+    assert!(ddl.contains("ip INET NULL"), "{ddl}");
+    assert!(ddl.contains("device MACADDR NOT NULL"), "{ddl}");
+    let csv = fs::read_to_string(out_dir.join("VISIT.csv"))?;
+    for line in csv.lines().skip(1) {
+        let cells: Vec<&str> = line.split(',').collect();
+        assert!(cells[1].contains("@example."), "{line}");
+        assert!(
+            cells[2].is_empty() || cells[2].parse::<std::net::Ipv4Addr>().is_ok(),
+            "{line}"
+        );
+    }
+    let _ = fs::remove_dir_all(&out_dir);
+    Ok(())
+}
+
+#[test]
+fn test_int_ranges_and_bigint_keys() -> TestResult {
+    let output = Command::cargo_bin(NAME)?
+        .args([
+            "-s",
+            "die:INT(1..6),big:BIGINT(3000000000..3000000100)",
+            "-r",
+            "50",
+            "--seed",
+            "5",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    for line in String::from_utf8(output.stdout)?.lines().skip(1) {
+        let (die, big) = line.split_once(',').unwrap();
+        assert!((1..=6).contains(&die.parse::<i32>()?), "{line}");
+        assert!(
+            (3_000_000_000..=3_000_000_100).contains(&big.parse::<i64>()?),
+            "{line}"
+        );
+    }
+
+    let out_dir = std::env::temp_dir().join("synthtab_cli_er_bigint_test");
+    let _ = fs::remove_dir_all(&out_dir);
+    let mmd = out_dir.join("shop.mmd");
+    fs::create_dir_all(&out_dir)?;
+    fs::write(
+        &mmd,
+        "erDiagram\nCUSTOMER {\n bigint id PK\n}\nORDER {\n int id PK\n}\nCUSTOMER ||--o{ ORDER : places\n",
+    )?;
+    Command::cargo_bin(NAME)?
+        .args([
+            "er",
+            mmd.to_str().unwrap(),
+            "--out",
+            out_dir.to_str().unwrap(),
+            "--target",
+            "mysql",
+        ])
+        .assert()
+        .success();
+    let ddl = fs::read_to_string(out_dir.join("schema.ddl.mysql.sql"))?;
+    assert!(
+        ddl.contains("id BIGINT AUTO_INCREMENT PRIMARY KEY"),
+        "{ddl}"
+    );
+    assert!(ddl.contains("customer_id BIGINT NOT NULL"), "{ddl}");
+    let _ = fs::remove_dir_all(&out_dir);
+    Ok(())
+}
+
+#[test]
+fn test_boolean_columns_in_flat_and_er_mode() -> TestResult {
+    let output = Command::cargo_bin(NAME)?
+        .args(["-s", "active:BOOLEAN(0.9)", "-r", "200", "--seed", "4"])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    let cells: Vec<&str> = stdout.lines().skip(1).collect();
+    assert!(cells.iter().all(|c| *c == "true" || *c == "false"));
+    let trues = cells.iter().filter(|c| **c == "true").count();
+    assert!((160..=195).contains(&trues), "{trues}");
+
+    let out_dir = std::env::temp_dir().join("synthtab_cli_er_boolean_test");
+    let _ = fs::remove_dir_all(&out_dir);
+    let mmd = out_dir.join("users.mmd");
+    fs::create_dir_all(&out_dir)?;
+    fs::write(
+        &mmd,
+        "erDiagram\nUSER {\n int id PK\n boolean active \"(0.9)\"\n bool admin\n}\n",
+    )?;
+    Command::cargo_bin(NAME)?
+        .args([
+            "er",
+            mmd.to_str().unwrap(),
+            "--out",
+            out_dir.to_str().unwrap(),
+            "--target",
+            "sqlserver",
+        ])
+        .assert()
+        .success();
+    let ddl = fs::read_to_string(out_dir.join("schema.ddl.sqlserver.sql"))?;
+    assert!(ddl.contains("active BIT NOT NULL"), "{ddl}");
+    assert!(ddl.contains("admin BIT NOT NULL"), "{ddl}");
+    let csv = fs::read_to_string(out_dir.join("USER.csv"))?;
+    for line in csv.lines().skip(1) {
+        let admin = line.split(',').nth(2).unwrap();
+        assert!(admin == "true" || admin == "false", "{line}");
+    }
+    let _ = fs::remove_dir_all(&out_dir);
+    Ok(())
+}
+
+#[test]
+fn test_date_ranges_bound_dates_and_timestamps() -> TestResult {
+    use chrono::{DateTime, Datelike, NaiveDate, Timelike, Weekday};
+    let output = Command::cargo_bin(NAME)?
+        .args([
+            "-s",
+            "d:DATE(2020-01-01..2024-12-31, weekdays=true),ts:DATE_TIME(2024-03-01..2024-03-31T23:59:59, hours=9..17)",
+            "-r",
+            "100",
+            "--seed",
+            "11",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    for line in stdout.lines().skip(1) {
+        let (d, ts) = line.split_once(',').unwrap();
+        let d = NaiveDate::parse_from_str(d, "%Y-%m-%d")?;
+        assert!((2020..=2024).contains(&d.year()), "{line}");
+        assert!(
+            !matches!(d.weekday(), Weekday::Sat | Weekday::Sun),
+            "{line}"
+        );
+        let ts = DateTime::parse_from_rfc3339(ts)?;
+        assert_eq!((ts.year(), ts.month()), (2024, 3), "{line}");
+        assert!((9..17).contains(&ts.hour()), "{line}");
+    }
+
+    let out_dir = std::env::temp_dir().join("synthtab_cli_er_date_hint_test");
+    let _ = fs::remove_dir_all(&out_dir);
+    let mmd = out_dir.join("events.mmd");
+    fs::create_dir_all(&out_dir)?;
+    fs::write(
+        &mmd,
+        "erDiagram\nEVENT {\n int id PK\n date day \"(2023-01-01..2023-12-31)\"\n}\n",
+    )?;
+    Command::cargo_bin(NAME)?
+        .args([
+            "er",
+            mmd.to_str().unwrap(),
+            "--out",
+            out_dir.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+    let csv = fs::read_to_string(out_dir.join("EVENT.csv"))?;
+    for line in csv.lines().skip(1) {
+        assert!(
+            line.split(',').nth(1).unwrap().starts_with("2023-"),
+            "{line}"
+        );
+    }
+    let _ = fs::remove_dir_all(&out_dir);
+
+    Command::cargo_bin(NAME)?
+        .args(["-s", "d:DATE(2024-06-08..2024-06-09, weekdays=true)"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("column 'd': DATE modifier"))
+        .stderr(predicate::str::contains("contains no weekdays"));
+    Ok(())
+}
+
+#[test]
+fn test_default_dates_are_recent_and_now_pins_relative_ranges() -> TestResult {
+    use chrono::{DateTime, Datelike, NaiveDate};
+    let output = Command::cargo_bin(NAME)?
+        .args(["-s", "d:DATE,ts:DATE_TIME", "-r", "200"])
+        .output()?;
+    assert!(output.status.success());
+    for line in String::from_utf8(output.stdout)?.lines().skip(1) {
+        let (d, ts) = line.split_once(',').unwrap();
+        let d = NaiveDate::parse_from_str(d, "%Y-%m-%d")?;
+        assert!((2020..=2024).contains(&d.year()), "{line}");
+        let ts = DateTime::parse_from_rfc3339(ts)?;
+        assert!((2020..=2024).contains(&ts.year()), "{line}");
+    }
+
+    let schema = "d:DATE(today-30d..today),ts:DATE_TIME(now-1h..now)";
+    let pinned = |now: &str| -> Result<Vec<u8>, Box<dyn Error>> {
+        let output = Command::cargo_bin(NAME)?
+            .args(["-s", schema, "-r", "20", "--seed", "5", "--now", now])
+            .output()?;
+        assert!(output.status.success());
+        assert!(output.stderr.is_empty());
+        Ok(output.stdout)
+    };
+    let first = pinned("2024-06-01T12:00")?;
+    assert_eq!(first, pinned("2024-06-01T12:00")?);
+    for line in String::from_utf8(first)?.lines().skip(1) {
+        let (d, ts) = line.split_once(',').unwrap();
+        assert!(("2024-05-02"..="2024-06-01").contains(&d), "{line}");
+        assert!(ts.starts_with("2024-06-01T1"), "{line}");
+    }
+
+    Command::cargo_bin(NAME)?
+        .args(["-s", schema, "-r", "1", "--seed", "5"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("pin them with --now"));
+    Command::cargo_bin(NAME)?
+        .args(["-s", "d:DATE", "--now", "June"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--now:"));
+    Ok(())
+}
+
+#[test]
+fn test_typed_columns_keep_their_dtype_in_parquet_and_format_in_csv() -> TestResult {
+    use polars::prelude::*;
+    let schema = "d:DATE,t:TIME,ts:DATE_TIME,la:LAT,lo:LON,p:PRICE,x:DECIMAL,r:DECIMAL(0..1)";
+    let path = std::env::temp_dir().join("synthtab_cli_typed_columns.parquet");
+    let _ = fs::remove_file(&path);
+    Command::cargo_bin(NAME)?
+        .args(["-s", schema, "-r", "5", "-p", "-f", path.to_str().unwrap()])
+        .assert()
+        .success();
+    let df = ParquetReader::new(fs::File::open(&path)?).finish()?;
+    let dtypes: Vec<DataType> = df.dtypes();
+    assert_eq!(
+        dtypes,
+        vec![
+            DataType::Date,
+            DataType::Time,
+            DataType::Datetime(TimeUnit::Microseconds, Some("UTC".to_string())),
+            DataType::Decimal(Some(9), Some(6)),
+            DataType::Decimal(Some(9), Some(6)),
+            DataType::Decimal(Some(10), Some(2)),
+            DataType::Decimal(Some(10), Some(2)),
+            DataType::Float64,
+        ]
+    );
+    let _ = fs::remove_file(&path);
+
+    Command::cargo_bin(NAME)?
+        .args([
+            "-s",
+            "t:TIME",
+            "-r",
+            "5",
+            "-p",
+            "-f",
+            path.to_str().unwrap(),
+        ])
+        .args(["--target", "spark", "--no-ddl", "--no-load"])
+        .assert()
+        .success();
+    let df = ParquetReader::new(fs::File::open(&path)?).finish()?;
+    assert_eq!(df.dtypes(), vec![DataType::String]);
+    let _ = fs::remove_file(&path);
+
+    let output = Command::cargo_bin(NAME)?
+        .args([
+            "-s",
+            "d:DATE(2024-02-29..2024-02-29),ts:DATE_TIME(2024-02-29T08:30..2024-02-29T08:30),p:PRICE",
+            "-r",
+            "3",
+            "--date-format",
+            "%d/%m/%Y",
+            "--datetime-format",
+            "%Y-%m-%d %H:%M",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    for line in String::from_utf8(output.stdout)?.lines().skip(1) {
+        let cells: Vec<&str> = line.split(',').collect();
+        assert_eq!(cells[..2], ["29/02/2024", "2024-02-29 08:30"], "{line}");
+        let (_, cents) = cells[2].split_once('.').unwrap();
+        assert_eq!(cents.len(), 2, "{line}");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_bad_modifier_is_an_error_naming_the_column() -> TestResult {
+    Command::cargo_bin(NAME)?
+        .args(["-s", "id:INT_INC,price:DECIMAL(0..500, sacle=2)"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("column 'price'"))
+        .stderr(predicate::str::contains("unknown option 'sacle'"));
+    Command::cargo_bin(NAME)?
+        .args(["-s", "score:INT_RNG:(garbage"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "column 'score': malformed modifier",
+        ));
+    Ok(())
+}
+
+#[test]
+fn test_nullable_column_writes_null_token() -> TestResult {
+    let output = Command::cargo_bin(NAME)?
+        .args([
+            "-s",
+            "id:INT_INC,nick:NAME(null=0.5)",
+            "-r",
+            "200",
+            "--seed",
+            "9",
+            "--null-token",
+            "NULL",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    let nulls = stdout.lines().filter(|l| l.ends_with(",NULL")).count();
+    assert!((50..150).contains(&nulls), "got {nulls} nulls");
+
+    let dir = std::env::temp_dir().join("synthtab_cli_null_load");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir)?;
+    let csv = dir.join("people.csv");
+    Command::cargo_bin(NAME)?
+        .args([
+            "-s",
+            "id:INT_INC,nick:NAME(null=0.5),age:INT?",
+            "-r",
+            "200",
+            "--seed",
+            "9",
+        ])
+        .args(["-f", csv.to_str().unwrap(), "--target", "mysql", "--no-ddl"])
+        .assert()
+        .success();
+    let load = fs::read_to_string(dir.join("people.load.mysql.sql"))?;
+    assert!(
+        load.contains("(id, @nick, @age) SET nick = NULLIF(@nick, ''), age = NULLIF(@age, '')"),
+        "{load}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_er_attribute_hint_makes_column_nullable() -> TestResult {
+    let dir = std::env::temp_dir().join("synthtab_cli_er_nullable");
+    let _ = fs::remove_dir_all(&dir);
+    let mmd = std::env::temp_dir().join("synthtab_cli_er_nullable.mmd");
+    fs::write(
+        &mmd,
+        "erDiagram\nUSER {\n  int id PK\n  string nick \"(null=1)\"\n}\n",
+    )?;
+    Command::cargo_bin(NAME)?
+        .args([
+            "er",
+            mmd.to_str().unwrap(),
+            "-r",
+            "3",
+            "--out",
+            dir.to_str().unwrap(),
+            "--target",
+            "postgres",
+        ])
+        .assert()
+        .success();
+    let csv = fs::read_to_string(dir.join("USER.csv"))?;
+    assert_eq!(csv, "id,nick\n0,\n1,\n2,\n");
+    let ddl = fs::read_to_string(dir.join("schema.ddl.postgres.sql"))?;
+    assert!(ddl.contains("nick TEXT NULL"), "got: {ddl}");
+    let _ = fs::remove_dir_all(&dir);
+    let _ = fs::remove_file(&mmd);
+    Ok(())
+}
+
+#[test]
+fn test_finance_types_are_checksum_valid() -> TestResult {
+    let output = Command::cargo_bin(NAME)?
+        .args([
+            "-s",
+            "card:CREDIT_CARD('visa'),iban:IBAN('DE'),bic:BIC,currency:CURRENCY_CODE",
+            "-r",
+            "20",
+            "--seed",
+            "6",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    let mut lines = stdout.lines();
+    assert_eq!(lines.next(), Some("card,iban,bic,currency"));
+    for line in lines {
+        let cells: Vec<&str> = line.split(',').collect();
+        assert!(cells[0].len() == 16 && cells[0].starts_with('4'), "{line}");
+        assert!(cells[1].len() == 22 && cells[1].starts_with("DE"), "{line}");
+        assert_eq!(cells[3].len(), 3, "{line}");
+    }
+
+    Command::cargo_bin(NAME)?
+        .args(["-s", "card:CREDIT_CARD('diners')", "-r", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "expected one of visa, mastercard, amex, discover",
+        ));
+    Ok(())
+}
+
+#[test]
+fn test_locale_flag_and_column_override() -> TestResult {
+    let output = Command::cargo_bin(NAME)?
+        .args([
+            "-s",
+            "name:NAME,city_code:ZIP_CODE,title:JOB_TITLE(locale=en)",
+            "-r",
+            "10",
+            "--seed",
+            "4",
+            "--locale",
+            "ja_JP",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    for line in stdout.lines().skip(1) {
+        let cells: Vec<&str> = line.split(',').collect();
+        assert!(!cells[0].is_ascii(), "{line}");
+        assert!(cells[1].is_ascii() && cells[2].is_ascii(), "{line}");
+    }
+
+    let out_dir = std::env::temp_dir().join("synthtab_cli_er_locale_test");
+    let _ = fs::remove_dir_all(&out_dir);
+    let mmd = out_dir.join("people.mmd");
+    fs::create_dir_all(&out_dir)?;
+    fs::write(
+        &mmd,
+        "erDiagram\nPERSON {\n int id PK\n string name \"NAME(locale=zh_CN)\"\n string company \"COMPANY\"\n}\n",
+    )?;
+    Command::cargo_bin(NAME)?
+        .args([
+            "er",
+            mmd.to_str().unwrap(),
+            "--out",
+            out_dir.to_str().unwrap(),
+            "--locale",
+            "fr-FR",
+            "--seed",
+            "1",
+        ])
+        .assert()
+        .success();
+    let csv = fs::read_to_string(out_dir.join("PERSON.csv"))?;
+    assert!(csv.lines().skip(1).all(|l| !l.is_ascii()), "{csv}");
+    let _ = fs::remove_dir_all(&out_dir);
+
+    Command::cargo_bin(NAME)?
+        .args(["-s", "n:NAME", "--locale", "de_DE"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("possible values: en, fr_FR"));
+    Ok(())
+}
+
+#[test]
+fn test_formatted_sequence_is_the_primary_key() -> TestResult {
+    let dir = std::env::temp_dir().join("synthtab_cli_sequence_test");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir)?;
+    let data = dir.join("orders.csv");
+    Command::cargo_bin(NAME)?
+        .args([
+            "-s",
+            "id:INT_INC(start=1, prefix='ORD-', width=6),line:INT_INC(start=10, step=10)",
+            "-r",
+            "3",
+            "-f",
+            data.to_str().unwrap(),
+            "--target",
+            "postgres",
+        ])
+        .assert()
+        .success();
+    assert_eq!(
+        fs::read_to_string(&data)?,
+        "id,line\nORD-000001,10\nORD-000002,20\nORD-000003,30\n"
+    );
+    let ddl = fs::read_to_string(dir.join("orders.ddl.postgres.sql"))?;
+    assert!(ddl.contains("id VARCHAR(15) PRIMARY KEY"), "{ddl}");
+    assert!(ddl.contains("line INTEGER NOT NULL"), "{ddl}");
+    let _ = fs::remove_dir_all(&dir);
+    Ok(())
+}
+
+#[test]
+fn test_time_series_subcommand() -> TestResult {
+    let dir = std::env::temp_dir().join("synthtab_cli_ts_test");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir)?;
+    let data = dir.join("readings.csv");
+    Command::cargo_bin(NAME)?
+        .args([
+            "ts",
+            "-s",
+            "reading:INT_INC",
+            "--start",
+            "2024-06-01",
+            "--end",
+            "2024-06-01T00:30",
+            "--interval",
+            "10m",
+            "--key",
+            "device:INT_INC(prefix='d', width=2)",
+            "--entities",
+            "2",
+            "-f",
+            data.to_str().unwrap(),
+            "--target",
+            "postgres",
+        ])
+        .assert()
+        .success();
+    assert_eq!(
+        fs::read_to_string(&data)?,
+        "device,ts,reading\n\
+         d00,2024-06-01T00:00:00+00:00,0\n\
+         d01,2024-06-01T00:00:00+00:00,0\n\
+         d00,2024-06-01T00:10:00+00:00,1\n\
+         d01,2024-06-01T00:10:00+00:00,1\n\
+         d00,2024-06-01T00:20:00+00:00,2\n\
+         d01,2024-06-01T00:20:00+00:00,2\n"
+    );
+    let ddl = fs::read_to_string(dir.join("readings.ddl.postgres.sql"))?;
+    assert!(ddl.contains("device VARCHAR(12) NOT NULL"), "{ddl}");
+    assert!(ddl.contains("ts TIMESTAMP"), "{ddl}");
+    assert!(!ddl.contains("PRIMARY KEY"), "{ddl}");
+    let _ = fs::remove_dir_all(&dir);
+
+    Command::cargo_bin(NAME)?
+        .args(["ts", "-s", "v:INT", "--start", "now-1h", "--end", "now"])
+        .args(["-i", "5m", "--jitter", "5m"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--jitter 5m must be shorter"));
+    Ok(())
+}
+
+#[test]
+fn test_nested_columns_are_native_in_parquet_and_json_in_csv() -> TestResult {
+    use polars::prelude::*;
+    let dir = std::env::temp_dir().join("synthtab_cli_nested_test");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir)?;
+    let schema = "tags:LIST<LOREM_WORD>(1..3),address:STRUCT<street:STRING, zip:INT(1..9)>";
+    let data = dir.join("people.parquet");
+    Command::cargo_bin(NAME)?
+        .args(["-s", schema, "-r", "5", "-p", "-f", data.to_str().unwrap()])
+        .args(["--target", "bigquery"])
+        .assert()
+        .success();
+    let df = ParquetReader::new(fs::File::open(&data)?).finish()?;
+    assert_eq!(
+        df.dtypes(),
+        vec![
+            DataType::List(Box::new(DataType::String)),
+            DataType::Struct(vec![
+                Field::new("street", DataType::String),
+                Field::new("zip", DataType::Int32),
+            ]),
+        ]
+    );
+    let ddl = fs::read_to_string(dir.join("people.ddl.bigquery.sql"))?;
+    assert!(ddl.contains("tags ARRAY<STRING>"), "{ddl}");
+    assert!(
+        ddl.contains("address STRUCT<street STRING, zip INT64>"),
+        "{ddl}"
+    );
+
+    let data = dir.join("people.csv");
+    Command::cargo_bin(NAME)?
+        .args(["-s", schema, "-r", "5", "-f", data.to_str().unwrap()])
+        .args(["--target", "postgres"])
+        .assert()
+        .success();
+    let csv = fs::read_to_string(&data)?;
+    for line in csv.lines().skip(1) {
+        assert!(line.starts_with("\"[\"\""), "{line}");
+        assert!(line.contains(",\"{\"\"street\"\":\"\""), "{line}");
+    }
+    let ddl = fs::read_to_string(dir.join("people.ddl.postgres.sql"))?;
+    assert!(ddl.contains("tags JSONB NOT NULL"), "{ddl}");
+    assert!(ddl.contains("address JSONB NOT NULL"), "{ddl}");
+
+    for target in ["bigquery", "spark"] {
+        Command::cargo_bin(NAME)?
+            .args(["-s", schema, "-r", "5", "-f", data.to_str().unwrap()])
+            .args(["--target", target])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(format!(
+                "column 'tags': --target {target} cannot load a nested column from CSV"
+            )));
+    }
+    let _ = fs::remove_dir_all(&dir);
+    Ok(())
+}
+
+#[test]
+fn test_binary_columns_are_bytes_in_parquet_and_hex_in_csv() -> TestResult {
+    use polars::prelude::*;
+    let dir = std::env::temp_dir().join("synthtab_cli_binary_test");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir)?;
+    let schema = "blob:BINARY(4),digest:HEX,key:TOKEN('sk_test_', 16)";
+    let data = dir.join("secrets.parquet");
+    Command::cargo_bin(NAME)?
+        .args(["-s", schema, "-r", "5", "-p", "-f", data.to_str().unwrap()])
+        .assert()
+        .success();
+    let df = ParquetReader::new(fs::File::open(&data)?).finish()?;
+    assert_eq!(
+        df.dtypes(),
+        vec![DataType::Binary, DataType::String, DataType::String]
+    );
+
+    let data = dir.join("secrets.csv");
+    Command::cargo_bin(NAME)?
+        .args(["-s", schema, "-r", "5", "-f", data.to_str().unwrap()])
+        .args(["--target", "postgres"])
+        .assert()
+        .success();
+    for line in fs::read_to_string(&data)?.lines().skip(1) {
+        let cells: Vec<&str> = line.split(',').collect();
+        assert_eq!(cells[0].len(), 10, "{line}");
+        assert!(cells[0].starts_with("\\x"), "{line}");
+        assert_eq!(cells[1].len(), 64, "{line}");
+        assert!(cells[2].starts_with("sk_test_"), "{line}");
+    }
+    let ddl = fs::read_to_string(dir.join("secrets.ddl.postgres.sql"))?;
+    assert!(ddl.contains("blob BYTEA NOT NULL"), "{ddl}");
+    assert!(ddl.contains("digest VARCHAR(64) NOT NULL"), "{ddl}");
+    assert!(ddl.contains("key VARCHAR(24) NOT NULL"), "{ddl}");
+
+    for target in ["mysql", "sqlserver"] {
+        Command::cargo_bin(NAME)?
+            .args([
+                "-s",
+                "blob:BINARY(4)",
+                "-r",
+                "5",
+                "-f",
+                data.to_str().unwrap(),
+            ])
+            .args(["--target", target])
+            .assert()
+            .success();
+        for line in fs::read_to_string(&data)?.lines().skip(1) {
+            assert_eq!(line.len(), 8, "{target}: {line}");
+            assert!(line.chars().all(|c| c.is_ascii_hexdigit()), "{line}");
+        }
+    }
+    let load = fs::read_to_string(dir.join("secrets.load.mysql.sql"))?;
+    assert!(load.contains("(@blob) SET blob = UNHEX(@blob);"), "{load}");
+
+    Command::cargo_bin(NAME)?
+        .args([
+            "-s",
+            "blob:BINARY(3)",
+            "-r",
+            "5",
+            "-f",
+            data.to_str().unwrap(),
+        ])
+        .args(["--target", "bigquery"])
+        .assert()
+        .success();
+    for line in fs::read_to_string(&data)?.lines().skip(1) {
+        assert_eq!(line.len(), 4, "{line}");
+        assert!(!line.contains(['\\', '=']), "{line}");
+    }
+    Command::cargo_bin(NAME)?
+        .args([
+            "-s",
+            "blob:BINARY(3)",
+            "-r",
+            "5",
+            "-f",
+            data.to_str().unwrap(),
+        ])
+        .args(["--target", "spark"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "column 'blob': --target spark cannot load a BINARY column from CSV",
+        ));
+    let _ = fs::remove_dir_all(&dir);
+    Ok(())
+}
+
+#[test]
+fn test_er_sized_types_bound_values_and_ddl() -> TestResult {
+    let dir = std::env::temp_dir().join("synthtab_cli_er_sized");
+    let _ = fs::remove_dir_all(&dir);
+    let mmd = std::env::temp_dir().join("synthtab_cli_er_sized.mmd");
+    fs::write(
+        &mmd,
+        "erDiagram\nUSER {\n  int id PK\n  varchar(8) code\n  decimal(5, 2) fee\n}\n",
+    )?;
+    Command::cargo_bin(NAME)?
+        .args(["er", mmd.to_str().unwrap(), "-r", "50"])
+        .args(["--out", dir.to_str().unwrap(), "--target", "mysql"])
+        .assert()
+        .success();
+    for line in fs::read_to_string(dir.join("USER.csv"))?.lines().skip(1) {
+        let cells: Vec<&str> = line.split(',').collect();
+        assert!((1..=8).contains(&cells[1].len()), "{line}");
+        let (whole, cents) = cells[2].split_once('.').unwrap();
+        assert!(whole.len() <= 3 && cents.len() == 2, "{line}");
+    }
+    let ddl = fs::read_to_string(dir.join("schema.ddl.mysql.sql"))?;
+    assert!(ddl.contains("code VARCHAR(8) NOT NULL"), "got: {ddl}");
+    assert!(ddl.contains("fee DECIMAL(5,2) NOT NULL"), "got: {ddl}");
+    let _ = fs::remove_dir_all(&dir);
+    let _ = fs::remove_file(&mmd);
+    Ok(())
+}
+
+#[test]
+fn test_unknown_types_fail_unless_lenient() -> TestResult {
+    Command::cargo_bin(NAME)?
+        .args(["-s", "id:INT_INC,who:NMAE", "-r", "3"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains(
+            "column 'who': unknown type 'NMAE'; did you mean 'NAME'?",
+        ));
+    Command::cargo_bin(NAME)?
+        .args(["-s", "id:INT_INC,who:NMAE", "-r", "3", "--lenient"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1,unknown"))
+        .stderr(predicate::str::contains("unknown type 'NMAE'"));
+
+    let mmd = std::env::temp_dir().join("synthtab_cli_er_strict.mmd");
+    fs::write(
+        &mmd,
+        "erDiagram\nUSER {\n  int id PK\n  string mail \"EMIAL\"\n}\n",
+    )?;
+    Command::cargo_bin(NAME)?
+        .args(["er", mmd.to_str().unwrap(), "-r", "3"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "entity 'USER': column 'mail': unknown type 'EMIAL'; did you mean 'EMAIL'?",
+        ));
+    let _ = fs::remove_file(&mmd);
+    Ok(())
+}
+
+#[test]
+fn test_infer_prints_a_schema_that_generates() -> TestResult {
+    let sample = std::env::temp_dir().join("synthtab_cli_infer.csv");
+    fs::write(
+        &sample,
+        "id,email,plan,score,joined\n\
+         1,ann@example.com,free,10,2024-01-02\n\
+         2,bo@example.org,pro,,2024-02-03\n\
+         3,cy@example.net,free,30,2024-03-04\n\
+         4,di@example.com,free,25,2024-04-05\n",
+    )?;
+    let output = Command::cargo_bin(NAME)?
+        .args(["infer", sample.to_str().unwrap()])
+        .output()?;
+    assert!(output.status.success());
+    let schema = String::from_utf8(output.stdout)?;
+    assert_eq!(
+        schema.trim(),
+        "id:INT_INC(start=1),email:EMAIL,plan:ENUM('free'=3, 'pro'=1),\
+         score:INT(10..30, null=0.25),joined:DATE(2024-01-02..2024-04-05)"
+    );
+    Command::cargo_bin(NAME)?
+        .args(["-s", schema.trim(), "-r", "5"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "id,email,plan,score,joined\n1,",
+        ));
+
+    Command::cargo_bin(NAME)?
+        .args(["infer", sample.to_str().unwrap(), "--format", "yaml"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("- name: plan\n  type: ENUM\n"));
+    let _ = fs::remove_file(&sample);
+    Ok(())
+}
+
+#[test]
+fn test_mimic_matches_the_sample_without_copying_it() -> TestResult {
+    let dir = std::env::temp_dir().join("synthtab_cli_mimic");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir)?;
+    let sample = dir.join("sample.parquet");
+    Command::cargo_bin(NAME)?
+        .args(["-s", "id:INT_INC,tier:ENUM('gold'=1, 'basic'=3),score:INT(normal(50, 10), clamp=0..100, null=0.2)"])
+        .args(["-r", "400", "--seed", "5", "-p", "-f", sample.to_str().unwrap()])
+        .assert()
+        .success();
+
+    let out = dir.join("mimic.csv");
+    Command::cargo_bin(NAME)?
+        .args([
+            "mimic",
+            sample.to_str().unwrap(),
+            "-r",
+            "2000",
+            "--seed",
+            "1",
+        ])
+        .args(["-f", out.to_str().unwrap(), "--target", "postgres"])
+        .assert()
+        .success();
+    let csv = fs::read_to_string(&out)?;
+    let rows: Vec<Vec<&str>> = csv
+        .lines()
+        .skip(1)
+        .map(|l| l.split(',').collect())
+        .collect();
+    assert_eq!(rows.len(), 2000);
+    assert!(!csv.contains("gold") && !csv.contains("basic"));
+    let top = rows.iter().filter(|r| r[1] == "tier_1").count();
+    assert!((1300..1700).contains(&top), "tier_1 in {top} of 2000 rows");
+    let nulls = rows.iter().filter(|r| r[2].is_empty()).count();
+    assert!((300..500).contains(&nulls), "{nulls} null scores");
+    let ddl = fs::read_to_string(dir.join("mimic.ddl.postgres.sql"))?;
+    assert!(ddl.contains("CREATE TABLE"), "{ddl}");
+
+    Command::cargo_bin(NAME)?
+        .args([
+            "mimic",
+            sample.to_str().unwrap(),
+            "--reuse-categories",
+            "--seed",
+            "1",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(",basic,"))
+        .stdout(predicate::function(|s: &str| s.lines().count() == 401));
+    let _ = fs::remove_dir_all(&dir);
+    Ok(())
+}
@@ -1,5 +1,5 @@
 CREATE TABLE synthtab_ddl_test_users (
   id SERIAL PRIMARY KEY,
-  name TEXT,
-  joined DATE
+  name TEXT NOT NULL,
+  joined DATE NOT NULL
 );